@@ -0,0 +1,127 @@
+//! Benchmarks of the line and tag scanning, that every parse goes through.
+//!
+//! The `memchr` feature can be compared with the fallback by saving a baseline
+//! without it:
+//!
+//! ```text
+//! cargo bench --bench parse -- --save-baseline core
+//! cargo bench --bench parse --features memchr -- --baseline core
+//! ```
+//!
+//! The parallel parser is only benchmarked with the `rayon` feature.
+use std::fmt::Write;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use libsrt::text::TextEvent;
+use libsrt::{SubtitleIterator, Timestamp};
+
+/// Returns a file with `cues` subtitles, that uses `newline` as line break.
+fn input(cues: i64, newline: &str) -> String {
+    let mut input = String::new();
+
+    for counter in 1..=cues {
+        write!(
+            input,
+            "{counter}{newline}{} --> {}{newline}",
+            Timestamp::from_millis(counter * 2_000),
+            Timestamp::from_millis(counter * 2_000 + 1_500),
+        )
+        .unwrap();
+
+        if counter % 5 == 0 {
+            write!(input, "{{\\an8}}").unwrap();
+        }
+
+        if counter % 3 == 0 {
+            write!(
+                input,
+                "<i>An italic line</i> and <font color=\"#ffcc00\">a colored word</font>{newline}"
+            )
+            .unwrap();
+        }
+
+        write!(input, "The line number {counter} of a subtitle,{newline}").unwrap();
+        write!(
+            input,
+            "which is followed by a second line.{newline}{newline}"
+        )
+        .unwrap();
+    }
+
+    input
+}
+
+fn bench_subtitle_iterator(c: &mut Criterion) {
+    let mut group = c.benchmark_group("subtitle_iterator");
+
+    for (name, newline) in [("lf", "\n"), ("crlf", "\r\n")] {
+        let input = input(10_000, newline);
+
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                SubtitleIterator::from(black_box(input.as_str()))
+                    .map(Result::unwrap)
+                    .count()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_text_iter(c: &mut Criterion) {
+    let input = input(10_000, "\n");
+    let subtitles = SubtitleIterator::from(input.as_str())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let mut group = c.benchmark_group("text_iter");
+
+    group.throughput(Throughput::Bytes(
+        subtitles
+            .iter()
+            .map(|subtitle| subtitle.text().as_raw().len() as u64)
+            .sum(),
+    ));
+    group.bench_function("events", |b| {
+        b.iter(|| {
+            black_box(&subtitles)
+                .iter()
+                .flat_map(|subtitle| subtitle.text().iter())
+                .filter(|event| matches!(event, Ok(TextEvent::Tag(_))))
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+fn bench_par_parse(c: &mut Criterion) {
+    let input = input(100_000, "\n");
+    let mut group = c.benchmark_group("par_parse");
+
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.sample_size(10);
+    group.bench_function("sequential", |b| {
+        b.iter(|| SubtitleIterator::from(black_box(input.as_str())).count())
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| libsrt::par_parse(black_box(input.as_str())).len())
+    });
+
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+fn bench_par_parse(_: &mut Criterion) {}
+
+criterion_group!(
+    benches,
+    bench_subtitle_iterator,
+    bench_text_iter,
+    bench_par_parse
+);
+criterion_main!(benches);
@@ -0,0 +1,367 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+use libsrt::cst::{Cue, Document, Token, TokenKind};
+use libsrt::text::{TagKind, TextEvent};
+use libsrt::track::ReadingSpeed;
+use libsrt::{Subtitle, SubtitleInit, Text, Timestamp};
+
+/// Subtitles, that have to be read faster, are reported.
+pub const MAX_CPS: f64 = 20.0;
+
+/// The severity of a [`Diagnostic`], with the values of the protocol.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error = 1,
+    Warning = 2,
+    Information = 3,
+}
+
+/// A change of the document, that fixes a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub title: String,
+    /// The byte ranges in the document and their replacements.
+    pub edits: Vec<(Range<usize>, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The byte range in the document.
+    pub range: Range<usize>,
+    pub severity: Severity,
+    /// Identifies the rule, that reported the diagnostic.
+    pub code: &'static str,
+    pub message: String,
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    const fn new(
+        range: Range<usize>,
+        severity: Severity,
+        code: &'static str,
+        message: String,
+    ) -> Self {
+        Self {
+            range,
+            severity,
+            code,
+            message,
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, title: String, edits: Vec<(Range<usize>, String)>) -> Self {
+        self.fix = Some(Fix { title, edits });
+        self
+    }
+}
+
+/// The byte ranges of the counter, the timestamps and the text of a cue.
+struct CueSpans {
+    counter: Range<usize>,
+    start: Range<usize>,
+    end: Range<usize>,
+    text: Range<usize>,
+}
+
+impl CueSpans {
+    fn new(cue: &Cue<'_, '_>) -> Self {
+        let tokens = cue.tokens();
+        let mut timestamps = tokens
+            .iter()
+            .filter(|token| token.kind() == TokenKind::Timestamp)
+            .map(Token::span);
+
+        Self {
+            counter: tokens[0].span(),
+            start: timestamps.next().unwrap_or_default(),
+            end: timestamps.next().unwrap_or_default(),
+            text: tokens[tokens.len() - 1].span(),
+        }
+    }
+}
+
+/// Converts the cue into a [`Subtitle`], if it has a valid timing.
+pub fn subtitle<'a>(index: usize, cue: &Cue<'a, '_>) -> Option<Subtitle<'a>> {
+    let (start, end) = (cue.start()?, cue.end()?);
+
+    SubtitleInit {
+        counter: cue.counter().unwrap_or(index + 1),
+        start,
+        duration: end.checked_sub(start)?,
+        text: Text::from(cue.text()),
+        ..SubtitleInit::default()
+    }
+    .init()
+    .ok()
+}
+
+/// Reports tags, that could not be parsed, and tags, that are not closed in
+/// the same cue.
+fn check_tags(cue: &Cue<'_, '_>, text_span: &Range<usize>, diagnostics: &mut Vec<Diagnostic>) {
+    let text = Text::from(cue.text());
+    let mut iterator = text.iter();
+    // the names of the open tags and their byte ranges in the document
+    let mut open: Vec<(&str, Range<usize>)> = Vec::new();
+
+    loop {
+        let start = text_span.start + iterator.offset();
+        let event = {
+            if let Some(event) = iterator.next() {
+                event
+            } else {
+                break;
+            }
+        };
+        let range = start..text_span.start + iterator.offset();
+
+        match event {
+            Ok(TextEvent::Tag(tag)) if tag.kind() == TagKind::AngularBrackets => {
+                if tag.is_open() {
+                    open.push((tag.name(), range));
+                } else if let Some(index) = open.iter().rposition(|(name, _)| *name == tag.name()) {
+                    open.remove(index);
+                } else {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            range.clone(),
+                            Severity::Warning,
+                            "unmatched-tag",
+                            format!("the tag </{}> has not been opened", tag.name()),
+                        )
+                        .with_fix(
+                            format!("Remove </{}>", tag.name()),
+                            vec![(range, String::new())],
+                        ),
+                    );
+                }
+            }
+            Ok(_) => {}
+            Err(error) => {
+                diagnostics.push(Diagnostic::new(
+                    range,
+                    Severity::Error,
+                    "tag-error",
+                    error.to_string(),
+                ));
+            }
+        }
+    }
+
+    // the tags are closed in reverse order at the end of the text
+    let closing = open
+        .iter()
+        .rev()
+        .fold(String::new(), |mut closing, (name, _)| {
+            let _ = write!(closing, "</{name}>");
+            closing
+        });
+
+    for (name, range) in open {
+        diagnostics.push(
+            Diagnostic::new(
+                range,
+                Severity::Warning,
+                "unclosed-tag",
+                format!("the tag <{name}> is not closed"),
+            )
+            .with_fix(
+                "Close the open tags at the end of the cue".to_string(),
+                vec![(text_span.end..text_span.end, closing.clone())],
+            ),
+        );
+    }
+}
+
+/// Returns the diagnostics of the document, which are the errors of the
+/// parser and the tags and the violations of the lint rules.
+pub fn diagnostics(document: &Document<'_>) -> Vec<Diagnostic> {
+    let mut diagnostics = document
+        .errors()
+        .map(|error| {
+            Diagnostic::new(
+                error.span().unwrap_or_default(),
+                Severity::Error,
+                "parse-error",
+                error.to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // the end of the previous cue and the byte range of its end timestamp
+    let mut previous = None;
+
+    for (index, cue) in document.cues().enumerate() {
+        let spans = CueSpans::new(&cue);
+
+        if cue.counter() != Some(index + 1) {
+            diagnostics.push(
+                Diagnostic::new(
+                    spans.counter.clone(),
+                    Severity::Information,
+                    "counter",
+                    format!("expected the counter {}", index + 1),
+                )
+                .with_fix(
+                    format!("Change the counter to {}", index + 1),
+                    vec![(spans.counter.clone(), (index + 1).to_string())],
+                ),
+            );
+        }
+
+        if let (Some(start), Some(end)) = (cue.start(), cue.end()) {
+            if end <= start {
+                diagnostics.push(Diagnostic::new(
+                    spans.start.start..spans.end.end,
+                    Severity::Error,
+                    "duration",
+                    "the cue ends before it starts".to_string(),
+                ));
+            }
+
+            if let Some((previous_end, previous_span)) = previous.take() {
+                if previous_end > start {
+                    // the previous cue ends after the start, which has been checked above
+                    #[allow(clippy::unchecked_time_subtraction)]
+                    let overlap = Timestamp::from(previous_end - start);
+
+                    diagnostics.push(
+                        Diagnostic::new(
+                            spans.start.clone(),
+                            Severity::Warning,
+                            "overlap",
+                            format!("the cue overlaps with the previous one by {overlap}"),
+                        )
+                        .with_fix(
+                            "Trim the end of the previous cue".to_string(),
+                            vec![(previous_span, Timestamp::from(start).to_string())],
+                        ),
+                    );
+                }
+            }
+
+            previous = Some((end, spans.end.clone()));
+        } else {
+            previous = None;
+        }
+
+        if let Some(subtitle) = subtitle(index, &cue) {
+            let speed = ReadingSpeed::new(&subtitle);
+
+            if speed.cps > MAX_CPS {
+                diagnostics.push(Diagnostic::new(
+                    spans.text.clone(),
+                    Severity::Information,
+                    "reading-speed",
+                    format!(
+                        "{:.1} characters per second are faster than the maximum of {MAX_CPS}",
+                        speed.cps
+                    ),
+                ));
+            }
+        }
+
+        check_tags(&cue, &spans.text, &mut diagnostics);
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.range.start);
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const INPUT: &str = concat!(
+        "1\n",
+        "00:00:01,000 --> 00:00:03,000\n",
+        "<i>Hello\n",
+        "\n",
+        "3\n",
+        "00:00:02,500 --> 00:00:04,000\n",
+        "Bye</b>\n",
+        "\n",
+        "x\n",
+        "00:00:05,000 --> 00:00:05,500\n",
+        "This is far too much text for half a second\n",
+    );
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<(&str, &str)> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.code, &INPUT[diagnostic.range.clone()]))
+            .collect()
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let diagnostics = diagnostics(&Document::parse(INPUT));
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                ("unclosed-tag", "<i>"),
+                ("counter", "3"),
+                ("overlap", "00:00:02,500"),
+                ("unmatched-tag", "</b>"),
+                ("parse-error", "x"),
+            ]
+        );
+        assert_eq!(
+            diagnostics[2].message,
+            "the cue overlaps with the previous one by 00:00:00,500"
+        );
+    }
+
+    #[test]
+    fn test_fixes() {
+        let diagnostics = diagnostics(&Document::parse(INPUT));
+        let mut output = INPUT.to_string();
+
+        // the edits are applied from the back, so the ranges stay valid
+        let mut edits = diagnostics
+            .into_iter()
+            .filter_map(|diagnostic| diagnostic.fix)
+            .flat_map(|fix| fix.edits)
+            .collect::<Vec<_>>();
+        edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+
+        for (range, replacement) in edits {
+            output.replace_range(range, &replacement);
+        }
+
+        assert_eq!(
+            output,
+            concat!(
+                "1\n",
+                "00:00:01,000 --> 00:00:02,500\n",
+                "<i>Hello</i>\n",
+                "\n",
+                "2\n",
+                "00:00:02,500 --> 00:00:04,000\n",
+                "Bye\n",
+                "\n",
+                "x\n",
+                "00:00:05,000 --> 00:00:05,500\n",
+                "This is far too much text for half a second\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_reading_speed() {
+        let input =
+            "1\n00:00:05,000 --> 00:00:05,500\nThis is far too much text for half a second\n";
+        let diagnostics = diagnostics(&Document::parse(input));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "reading-speed");
+        assert_eq!(
+            &input[diagnostics[0].range.clone()],
+            "This is far too much text for half a second"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use libsrt::cst::Document;
+use libsrt::Timestamp;
+
+/// Returns the normalised text of the document, where the cues are numbered
+/// from `1`, the timestamps are written in the same format and trailing
+/// whitespace and surplus blank lines are removed.
+///
+/// The line breaks are the same as in the first line of the document.
+///
+/// Returns `None` if the document contains a line, that could not be parsed,
+/// because it would be removed.
+pub fn format(document: &Document<'_>) -> Option<String> {
+    if document.errors().next().is_some() {
+        return None;
+    }
+
+    let newline = {
+        let first = document
+            .tokens()
+            .iter()
+            .find(|token| token.as_str().ends_with('\n'));
+
+        if first.is_some_and(|token| token.as_str().ends_with("\r\n")) {
+            "\r\n"
+        } else {
+            "\n"
+        }
+    };
+
+    let mut output = String::new();
+
+    for (index, cue) in document.cues().enumerate() {
+        if index > 0 {
+            output.push_str(newline);
+        }
+
+        output.push_str(&(index + 1).to_string());
+        output.push_str(newline);
+        output.push_str(&Timestamp::from(cue.start()?).to_string());
+        output.push_str(" --> ");
+        output.push_str(&Timestamp::from(cue.end()?).to_string());
+        output.push_str(newline);
+
+        for line in cue.text().lines() {
+            output.push_str(line.trim_end());
+            output.push_str(newline);
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_format() {
+        let document = Document::parse(concat!(
+            "\r\n",
+            "  5  \r\n",
+            "0:0:1,500 --> 00:00:02,000\r\n",
+            "Hello  \r\n",
+            "World\r\n",
+            "\r\n",
+            "\r\n",
+            "9\r\n",
+            "00:00:03,000 --> 00:00:04,000\r\n",
+            "Bye\r\n",
+            "\r\n",
+        ));
+
+        assert_eq!(
+            format(&document).unwrap(),
+            concat!(
+                "1\r\n",
+                "00:00:01,500 --> 00:00:02,000\r\n",
+                "Hello\r\n",
+                "World\r\n",
+                "\r\n",
+                "2\r\n",
+                "00:00:03,000 --> 00:00:04,000\r\n",
+                "Bye\r\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_invalid() {
+        let document = Document::parse("one\n00:00:01,000 --> 00:00:02,000\nHello\n");
+
+        assert_eq!(format(&document), None);
+    }
+}
@@ -0,0 +1,123 @@
+use std::convert::TryFrom;
+use std::ops::Range;
+
+use serde_json::{json, Value};
+
+/// Converts between byte offsets and the positions of the protocol, which
+/// count lines and UTF-16 code units.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// The byte offset of the start of every line.
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        Self { starts }
+    }
+
+    /// Returns the position of the byte offset in the text.
+    pub fn position(&self, text: &str, offset: usize) -> Value {
+        let line = self.starts.partition_point(|start| *start <= offset) - 1;
+        let character = text[self.starts[line]..offset].encode_utf16().count();
+
+        json!({ "line": line, "character": character })
+    }
+
+    pub fn range(&self, text: &str, range: Range<usize>) -> Value {
+        json!({
+            "start": self.position(text, range.start),
+            "end": self.position(text, range.end),
+        })
+    }
+
+    /// Returns the byte offset of the position, where a position behind the
+    /// end of a line is clamped to the end of the line.
+    pub fn offset(&self, text: &str, position: &Value) -> Option<usize> {
+        let line = usize::try_from(position.get("line")?.as_u64()?).ok()?;
+        let character = usize::try_from(position.get("character")?.as_u64()?).ok()?;
+
+        let start = *self.starts.get(line)?;
+        let mut end = self.starts.get(line + 1).map_or(text.len(), |end| end - 1);
+
+        if text[start..end].ends_with('\r') {
+            end -= 1;
+        }
+
+        let mut units = 0;
+
+        for (index, c) in text[start..end].char_indices() {
+            if units >= character {
+                return Some(start + index);
+            }
+
+            units += c.len_utf16();
+        }
+
+        Some(end)
+    }
+
+    pub fn offsets(&self, text: &str, range: &Value) -> Option<Range<usize>> {
+        let start = self.offset(text, range.get("start")?)?;
+        let end = self.offset(text, range.get("end")?)?;
+
+        (start <= end).then_some(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_positions() {
+        let text = "1\r\nähm 😀 ok\n";
+        let index = LineIndex::new(text);
+
+        let emoji = text.find('😀').unwrap();
+        let ok = text.find("ok").unwrap();
+
+        assert_eq!(
+            index.position(text, 0),
+            json!({ "line": 0, "character": 0 })
+        );
+        assert_eq!(
+            index.position(text, emoji),
+            json!({ "line": 1, "character": 4 })
+        );
+        assert_eq!(
+            index.position(text, ok),
+            json!({ "line": 1, "character": 7 })
+        );
+        assert_eq!(
+            index.position(text, text.len()),
+            json!({ "line": 2, "character": 0 })
+        );
+
+        for offset in [0, 3, emoji, ok, text.len()] {
+            assert_eq!(
+                index.offset(text, &index.position(text, offset)),
+                Some(offset)
+            );
+        }
+
+        // behind the end of the line
+        assert_eq!(
+            index.offset(text, &json!({ "line": 1, "character": 100 })),
+            Some(text.len() - 1)
+        );
+        assert_eq!(
+            index.offset(text, &json!({ "line": 0, "character": 5 })),
+            Some(1)
+        );
+        assert_eq!(
+            index.offset(text, &json!({ "line": 3, "character": 0 })),
+            None
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! A language server for `.srt` files, which talks JSON-RPC over stdin and
+//! stdout.
+//!
+//! It reports the errors of the parser and the tags and the violations of a
+//! few lint rules as diagnostics, shows the duration and the reading speed of
+//! a cue on hover, lists the cues in the outline, formats (renumbers and
+//! normalises) documents and offers code actions to fix overlapping cues and
+//! unclosed tags.
+#![forbid(unsafe_code)]
+#![warn(clippy::nursery, clippy::pedantic)]
+
+mod diagnostics;
+mod format;
+mod line_index;
+mod server;
+mod transport;
+
+use std::io;
+use std::process;
+
+use serde_json::Value;
+
+use crate::server::{ResponseError, Server};
+use crate::transport::{read_message, write_message};
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut reader = stdin.lock();
+    let mut writer = stdout.lock();
+
+    let mut server = Server::new();
+
+    while let Some(message) = read_message(&mut reader)? {
+        let messages = match message {
+            Ok(message) => server.handle(&message),
+            // the id of an invalid message is unknown
+            Err(error) => vec![ResponseError::parse_error(error).response(&Value::Null)],
+        };
+
+        for message in messages {
+            write_message(&mut writer, &message)?;
+        }
+
+        if let Some(code) = server.exit_code() {
+            process::exit(code);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,581 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use libsrt::cst::Document;
+use libsrt::track::ReadingSpeed;
+use libsrt::Timestamp;
+use serde_json::{json, Value};
+
+use crate::diagnostics::{diagnostics, subtitle, Diagnostic};
+use crate::format::format;
+use crate::line_index::LineIndex;
+
+/// An error, that is returned instead of the result of a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+impl ResponseError {
+    const INVALID_PARAMS: i64 = -32602;
+    const INVALID_REQUEST: i64 = -32600;
+    const METHOD_NOT_FOUND: i64 = -32601;
+    const PARSE_ERROR: i64 = -32700;
+
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+
+    /// The error of a message, that is not valid JSON or has invalid headers.
+    pub fn parse_error(message: impl Into<String>) -> Self { Self::new(Self::PARSE_ERROR, message) }
+
+    /// Returns the response to the request with the `id`.
+    pub fn response(&self, id: &Value) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": self.code, "message": self.message },
+        })
+    }
+}
+
+/// A document, that has been opened by the client.
+#[derive(Debug)]
+struct OpenDocument {
+    text: String,
+    lines: LineIndex,
+    document: Document<'static>,
+    version: Value,
+}
+
+impl OpenDocument {
+    fn new(text: String, version: Value) -> Self {
+        Self {
+            lines: LineIndex::new(&text),
+            document: Document::parse(&text).into_owned(),
+            text,
+            version,
+        }
+    }
+
+    /// Applies a change of the document, where only the cues around an edit
+    /// of a range are parsed again.
+    fn apply(&mut self, change: &Value) -> Option<()> {
+        let text = change.get("text")?.as_str()?;
+
+        if let Some(range) = change.get("range") {
+            let range = self.lines.offsets(&self.text, range)?;
+
+            self.document.edit(range.clone(), text);
+            self.text.replace_range(range, text);
+        } else {
+            self.text = text.to_string();
+            self.document = Document::parse(&self.text).into_owned();
+        }
+
+        self.lines = LineIndex::new(&self.text);
+        Some(())
+    }
+
+    fn diagnostic(&self, diagnostic: &Diagnostic) -> Value {
+        json!({
+            "range": self.lines.range(&self.text, diagnostic.range.clone()),
+            "severity": diagnostic.severity as u8,
+            "code": diagnostic.code,
+            "source": "srt",
+            "message": diagnostic.message,
+        })
+    }
+
+    fn hover(&self, offset: usize) -> Value {
+        let cue = self.document.cues().enumerate().find(|(_, cue)| {
+            let span = cue.span();
+            span.start <= offset && offset <= span.end
+        });
+
+        let Some((index, cue)) = cue else {
+            return Value::Null;
+        };
+
+        let mut contents = format!("**Cue {}**", cue.counter().unwrap_or(index + 1));
+
+        if let Some(subtitle) = subtitle(index, &cue) {
+            let speed = ReadingSpeed::new(&subtitle);
+
+            let _ = write!(
+                contents,
+                "\n\nDuration: {:.3}s\n\nReading speed: {:.1} characters per second, {:.0} words per minute",
+                subtitle.duration().as_secs_f64(),
+                speed.cps,
+                speed.wpm,
+            );
+        }
+
+        json!({
+            "contents": { "kind": "markdown", "value": contents },
+            "range": self.lines.range(&self.text, cue.span()),
+        })
+    }
+
+    fn symbols(&self) -> Value {
+        let symbols = self
+            .document
+            .cues()
+            .enumerate()
+            .map(|(index, cue)| {
+                let counter = cue.counter().unwrap_or(index + 1);
+                let line = cue.text().lines().next().unwrap_or_default();
+                let timing = match (cue.start(), cue.end()) {
+                    (Some(start), Some(end)) => {
+                        format!("{} --> {}", Timestamp::from(start), Timestamp::from(end))
+                    }
+                    _ => String::new(),
+                };
+
+                json!({
+                    "name": format!("{counter}: {line}"),
+                    "detail": timing,
+                    // the kind `Event`
+                    "kind": 24,
+                    "range": self.lines.range(&self.text, cue.span()),
+                    "selectionRange": self.lines.range(&self.text, cue.tokens()[0].span()),
+                })
+            })
+            .collect();
+
+        Value::Array(symbols)
+    }
+
+    fn formatting(&self) -> Value {
+        match format(&self.document) {
+            Some(text) if text == self.text => json!([]),
+            Some(text) => {
+                json!([{
+                    "range": self.lines.range(&self.text, 0..self.text.len()),
+                    "newText": text,
+                }])
+            }
+            None => Value::Null,
+        }
+    }
+
+    fn code_actions(&self, uri: &str, range: &std::ops::Range<usize>) -> Value {
+        let mut actions: Vec<Value> = Vec::new();
+        let mut fixes = Vec::new();
+
+        for diagnostic in diagnostics(&self.document) {
+            let Some(fix) = &diagnostic.fix else {
+                continue;
+            };
+
+            if diagnostic.range.start > range.end
+                || range.start > diagnostic.range.end
+                || fixes.contains(fix)
+            {
+                continue;
+            }
+
+            let edits = fix
+                .edits
+                .iter()
+                .map(|(range, text)| {
+                    json!({ "range": self.lines.range(&self.text, range.clone()), "newText": text })
+                })
+                .collect::<Vec<_>>();
+
+            actions.push(json!({
+                "title": fix.title,
+                "kind": "quickfix",
+                "diagnostics": [self.diagnostic(&diagnostic)],
+                "edit": { "changes": { uri: edits } },
+            }));
+            fixes.push(fix.clone());
+        }
+
+        Value::Array(actions)
+    }
+}
+
+/// Handles the messages of a client.
+#[derive(Debug, Default)]
+pub struct Server {
+    documents: HashMap<String, OpenDocument>,
+    is_shutdown: bool,
+    exit_code: Option<i32>,
+}
+
+fn uri(params: &Value) -> Result<&str, ResponseError> {
+    params
+        .pointer("/textDocument/uri")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ResponseError::invalid_params("missing textDocument.uri"))
+}
+
+impl Server {
+    pub fn new() -> Self { Self::default() }
+
+    /// Returns the exit code of the process, after the client sent `exit`.
+    pub const fn exit_code(&self) -> Option<i32> { self.exit_code }
+
+    /// Handles a request or a notification and returns the messages, that
+    /// have to be sent to the client.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let params = message.get("params").unwrap_or(&Value::Null);
+
+        match (
+            message.get("method").and_then(Value::as_str),
+            message.get("id"),
+        ) {
+            (Some(method), Some(id)) => {
+                let response = match self.request(method, params) {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err(error) => error.response(id),
+                };
+
+                vec![response]
+            }
+            (Some(method), None) => self.notification(method, params),
+            // the server does not send requests, so there are no responses
+            (None, _) => Vec::new(),
+        }
+    }
+
+    fn document<'p>(&self, params: &'p Value) -> Result<(&'p str, &OpenDocument), ResponseError> {
+        let uri = uri(params)?;
+        let document = self.documents.get(uri).ok_or_else(|| {
+            ResponseError::invalid_params(format!("the document {uri} is not open"))
+        })?;
+
+        Ok((uri, document))
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, ResponseError> {
+        if self.is_shutdown {
+            return Err(ResponseError::new(
+                ResponseError::INVALID_REQUEST,
+                "the server has been shut down",
+            ));
+        }
+
+        match method {
+            "initialize" => {
+                Ok(json!({
+                    "capabilities": {
+                        // incremental changes
+                        "textDocumentSync": { "openClose": true, "change": 2 },
+                        "hoverProvider": true,
+                        "documentSymbolProvider": true,
+                        "documentFormattingProvider": true,
+                        "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                    },
+                    "serverInfo": { "name": "srt-lsp", "version": env!("CARGO_PKG_VERSION") },
+                }))
+            }
+            "shutdown" => {
+                self.is_shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => {
+                let (_, document) = self.document(params)?;
+                let offset = params
+                    .get("position")
+                    .and_then(|position| document.lines.offset(&document.text, position))
+                    .ok_or_else(|| ResponseError::invalid_params("invalid position"))?;
+
+                Ok(document.hover(offset))
+            }
+            "textDocument/documentSymbol" => Ok(self.document(params)?.1.symbols()),
+            "textDocument/formatting" => Ok(self.document(params)?.1.formatting()),
+            "textDocument/codeAction" => {
+                let (uri, document) = self.document(params)?;
+                let range = params
+                    .get("range")
+                    .and_then(|range| document.lines.offsets(&document.text, range))
+                    .ok_or_else(|| ResponseError::invalid_params("invalid range"))?;
+
+                Ok(document.code_actions(uri, &range))
+            }
+            _ => {
+                Err(ResponseError::new(
+                    ResponseError::METHOD_NOT_FOUND,
+                    format!("unknown method {method}"),
+                ))
+            }
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let Ok(uri) = uri(params).map(str::to_string) else {
+            if method == "exit" {
+                self.exit_code = Some(i32::from(!self.is_shutdown));
+            }
+
+            return Vec::new();
+        };
+
+        let mut messages = Vec::new();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params.pointer("/textDocument/text").and_then(Value::as_str);
+                let version = params.pointer("/textDocument/version").cloned();
+
+                if let Some(text) = text {
+                    let document = OpenDocument::new(text.to_string(), version.unwrap_or_default());
+                    self.documents.insert(uri.clone(), document);
+                }
+            }
+            "textDocument/didChange" => {
+                let Some(document) = self.documents.get_mut(&uri) else {
+                    return Vec::new();
+                };
+
+                for change in params
+                    .get("contentChanges")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    if document.apply(change).is_none() {
+                        messages.push(json!({
+                            "jsonrpc": "2.0",
+                            "method": "window/logMessage",
+                            // a warning
+                            "params": { "type": 2, "message": format!("ignored an invalid change of {uri}") },
+                        }));
+                    }
+                }
+
+                document.version = params
+                    .pointer("/textDocument/version")
+                    .cloned()
+                    .unwrap_or_default();
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+
+                return vec![json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": uri, "diagnostics": [] },
+                })];
+            }
+            _ => return Vec::new(),
+        }
+
+        if let Some(document) = self.documents.get(&uri) {
+            let diagnostics = diagnostics(&document.document)
+                .iter()
+                .map(|diagnostic| document.diagnostic(diagnostic))
+                .collect::<Vec<_>>();
+
+            messages.push(json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/publishDiagnostics",
+                "params": { "uri": uri, "version": document.version, "diagnostics": diagnostics },
+            }));
+        }
+
+        messages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const URI: &str = "file:///example.srt";
+
+    fn request(server: &mut Server, method: &str, params: &Value) -> Value {
+        let mut messages = server
+            .handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }));
+
+        assert_eq!(messages.len(), 1);
+        messages.remove(0)["result"].take()
+    }
+
+    fn notify(server: &mut Server, method: &str, params: &Value) -> Vec<Value> {
+        server.handle(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+
+    fn codes(messages: &[Value]) -> Vec<&str> {
+        messages[0]["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|diagnostic| diagnostic["code"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_session() {
+        let mut server = Server::new();
+
+        let result = request(&mut server, "initialize", &json!({ "capabilities": {} }));
+        assert_eq!(result["capabilities"]["textDocumentSync"]["change"], 2);
+
+        let messages = notify(
+            &mut server,
+            "textDocument/didOpen",
+            &json!({ "textDocument": {
+                "uri": URI,
+                "languageId": "srt",
+                "version": 1,
+                "text": "1\n00:00:01,000 --> 00:00:03,000\n<i>Hello\n\n2\n00:00:02,000 --> 00:00:04,000\nBye\n",
+            } }),
+        );
+        assert_eq!(codes(&messages), vec!["unclosed-tag", "overlap"]);
+
+        // close the tag by typing into the first cue
+        let messages = notify(
+            &mut server,
+            "textDocument/didChange",
+            &json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{
+                    "range": {
+                        "start": { "line": 2, "character": 8 },
+                        "end": { "line": 2, "character": 8 },
+                    },
+                    "text": "</i>",
+                }],
+            }),
+        );
+        assert_eq!(codes(&messages), vec!["overlap"]);
+        assert_eq!(messages[0]["params"]["version"], 2);
+
+        let hover = request(
+            &mut server,
+            "textDocument/hover",
+            &json!({ "textDocument": { "uri": URI }, "position": { "line": 4, "character": 0 } }),
+        );
+        assert_eq!(
+            hover["contents"]["value"],
+            "**Cue 2**\n\nDuration: 2.000s\n\nReading speed: 1.5 characters per second, 30 words per minute"
+        );
+
+        let symbols = request(
+            &mut server,
+            "textDocument/documentSymbol",
+            &json!({ "textDocument": { "uri": URI } }),
+        );
+        assert_eq!(
+            symbols
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|symbol| {
+                    (
+                        symbol["name"].as_str().unwrap(),
+                        symbol["detail"].as_str().unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                ("1: <i>Hello</i>", "00:00:01,000 --> 00:00:03,000"),
+                ("2: Bye", "00:00:02,000 --> 00:00:04,000"),
+            ]
+        );
+
+        let actions = request(
+            &mut server,
+            "textDocument/codeAction",
+            &json!({
+                "textDocument": { "uri": URI },
+                "range": { "start": { "line": 5, "character": 0 }, "end": { "line": 5, "character": 0 } },
+                "context": { "diagnostics": [] },
+            }),
+        );
+        assert_eq!(actions[0]["title"], "Trim the end of the previous cue");
+        assert_eq!(
+            actions[0]["edit"]["changes"][URI],
+            json!([{
+                "range": { "start": { "line": 1, "character": 17 }, "end": { "line": 1, "character": 29 } },
+                "newText": "00:00:02,000",
+            }])
+        );
+
+        assert_eq!(request(&mut server, "shutdown", &Value::Null), Value::Null);
+        assert_eq!(
+            notify(&mut server, "exit", &Value::Null),
+            Vec::<Value>::new()
+        );
+        assert_eq!(server.exit_code(), Some(0));
+    }
+
+    #[test]
+    fn test_formatting() {
+        let mut server = Server::new();
+
+        notify(
+            &mut server,
+            "textDocument/didOpen",
+            &json!({ "textDocument": { "uri": URI, "version": 1, "text": "3\n00:00:01,000 --> 00:00:02,000\nHello  \n\n\n" } }),
+        );
+
+        let edits = request(
+            &mut server,
+            "textDocument/formatting",
+            &json!({ "textDocument": { "uri": URI } }),
+        );
+        assert_eq!(
+            edits[0]["newText"],
+            "1\n00:00:01,000 --> 00:00:02,000\nHello\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_change() {
+        let mut server = Server::new();
+
+        notify(
+            &mut server,
+            "textDocument/didOpen",
+            &json!({ "textDocument": { "uri": URI, "version": 1, "text": "1\n00:00:01,000 --> 00:00:02,000\nHello\n" } }),
+        );
+
+        let messages = notify(
+            &mut server,
+            "textDocument/didChange",
+            &json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{
+                    "range": {
+                        "start": { "line": 9, "character": 0 },
+                        "end": { "line": 9, "character": 0 },
+                    },
+                    "text": "Bye",
+                }],
+            }),
+        );
+
+        assert_eq!(messages[0]["method"], "window/logMessage");
+        assert_eq!(messages[0]["params"]["type"], 2);
+        assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+    }
+
+    #[test]
+    fn test_unknown_method() {
+        let mut server = Server::new();
+        let messages =
+            server.handle(&json!({ "jsonrpc": "2.0", "id": 7, "method": "workspace/symbol" }));
+
+        assert_eq!(messages[0]["id"], 7);
+        assert_eq!(
+            messages[0]["error"]["code"],
+            ResponseError::METHOD_NOT_FOUND
+        );
+
+        notify(&mut server, "exit", &Value::Null);
+        assert_eq!(server.exit_code(), Some(1));
+    }
+}
@@ -0,0 +1,130 @@
+use std::io::{self, BufRead, Write};
+
+use serde_json::Value;
+
+/// Returns whether the name of a header is a token, which can not contain
+/// whitespace or separators like the `{` of a JSON body.
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_graphic() && !b"\"(),/:;<=>?@[\\]{}".contains(&byte))
+}
+
+/// Reads the next message, which is prefixed by a `Content-Length` header, or
+/// returns `None` if the client closed the connection.
+///
+/// A message with an invalid header or content is returned as an error
+/// message, after which the next message can be read. If the length of the
+/// invalid message is unknown, its content is skipped until the next header.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Result<Value, String>>> {
+    let mut length = None;
+    let mut has_headers = false;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let mut header = line.trim_end();
+
+        // the content of a skipped message can be in front of the next header
+        if let Some(index) = header.to_ascii_lowercase().find("content-length:") {
+            header = &header[index..];
+        }
+
+        match header.split_once(':') {
+            Some((name, value)) if is_token(name) => {
+                has_headers = true;
+
+                if name.eq_ignore_ascii_case("Content-Length") {
+                    length = Some(
+                        value
+                            .trim()
+                            .parse::<usize>()
+                            .map_err(|error| format!("invalid Content-Length {value:?}: {error}")),
+                    );
+                }
+            }
+            _ if header.is_empty() && has_headers => break,
+            // skips the content of a message without a valid length
+            _ if !has_headers => {}
+            _ => return Ok(Some(Err(format!("invalid header {header:?}")))),
+        }
+    }
+
+    let length = match length {
+        Some(Ok(length)) => length,
+        Some(Err(error)) => return Ok(Some(Err(error))),
+        None => return Ok(Some(Err("missing Content-Length header".to_string()))),
+    };
+
+    let mut content = vec![0; length];
+    reader.read_exact(&mut content)?;
+
+    Ok(Some(
+        serde_json::from_slice(&content).map_err(|error| error.to_string()),
+    ))
+}
+
+/// Writes the message with a `Content-Length` header.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_roundtrip() {
+        let message = json!({ "jsonrpc": "2.0", "method": "exit" });
+        let mut output = Vec::new();
+
+        write_message(&mut output, &message).unwrap();
+        write_message(&mut output, &message).unwrap();
+
+        let mut input = output.as_slice();
+
+        assert_eq!(read_message(&mut input).unwrap(), Some(Ok(message.clone())));
+        assert_eq!(read_message(&mut input).unwrap(), Some(Ok(message)));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_content() {
+        let mut input = "Content-Length: 2\r\n\r\n{]Content-Length: 2\r\n\r\n{}".as_bytes();
+
+        assert!(matches!(read_message(&mut input), Ok(Some(Err(_)))));
+        assert_eq!(read_message(&mut input).unwrap(), Some(Ok(json!({}))));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let mut input = concat!(
+            "Content-Type: application/json\r\n\r\n{\"id\": 1,\r\n\r\n\"x\": 2}",
+            "Content-Length: 2\r\n\r\n[]",
+            "Content-Length: two\r\n\r\n{}",
+            "content-length: 2\r\n\r\n{}",
+        )
+        .as_bytes();
+
+        assert_eq!(
+            read_message(&mut input).unwrap(),
+            Some(Err("missing Content-Length header".to_string()))
+        );
+        assert_eq!(read_message(&mut input).unwrap(), Some(Ok(json!([]))));
+        assert!(matches!(read_message(&mut input), Ok(Some(Err(_)))));
+        assert_eq!(read_message(&mut input).unwrap(), Some(Ok(json!({}))));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+}
@@ -0,0 +1,169 @@
+use core::fmt;
+use core::ops::Deref;
+use core::str;
+
+use super::Buffer;
+
+/// The error of an [`ArrayBuffer`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ArrayBufferError {
+    /// The output does not fit into the buffer.
+    CapacityExceeded {
+        /// The capacity of the buffer.
+        capacity: usize,
+    },
+}
+
+// the error of a buffer has to implement `Display` without `std`
+impl fmt::Display for ArrayBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityExceeded { capacity } => {
+                write!(f, "capacity of {capacity} bytes exceeded")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ArrayBufferError {}
+
+/// A [`Buffer`] with a fixed capacity of `N` bytes, which does not need an
+/// allocator.
+///
+/// A write, that does not fit into the buffer, fails with
+/// [`ArrayBufferError::CapacityExceeded`] and does not write anything.
+/// [`Buffer::reserve`] never fails, because the serializers only reserve an
+/// estimate of the required space.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::{ArrayBuffer, ArrayBufferError, Serialize, Timestamp};
+///
+/// let timestamp = Timestamp::from_millis(3_723_004);
+///
+/// let mut buffer = ArrayBuffer::<16>::new();
+/// timestamp.serialize(&mut buffer)?;
+/// assert_eq!(buffer.as_str(), "01:02:03,004");
+///
+/// let mut buffer = ArrayBuffer::<8>::new();
+/// assert_eq!(
+///     timestamp.serialize(&mut buffer),
+///     Err(ArrayBufferError::CapacityExceeded { capacity: 8 })
+/// );
+/// # Ok::<(), ArrayBufferError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArrayBuffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> ArrayBuffer<N> {
+    /// Creates an empty buffer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the written output.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: only complete `str`s are copied into the buffer
+        unsafe { str::from_utf8_unchecked(&self.bytes[..self.len]) }
+    }
+
+    /// Returns the number of written bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize { self.len }
+
+    /// Returns `true` if nothing has been written.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Returns the number of bytes, that fit into the buffer.
+    #[must_use]
+    pub const fn capacity(&self) -> usize { N }
+
+    /// Removes the written output.
+    pub const fn clear(&mut self) { self.len = 0; }
+}
+
+impl<const N: usize> Default for ArrayBuffer<N> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<const N: usize> Deref for ArrayBuffer<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target { self.as_str() }
+}
+
+impl<const N: usize> fmt::Display for ArrayBuffer<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl<const N: usize> Buffer for ArrayBuffer<N> {
+    type Error = ArrayBufferError;
+
+    fn reserve(&mut self, _: usize) -> Result<(), Self::Error> { Ok(()) }
+
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        self.write_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    fn write_str(&mut self, string: &str) -> Result<(), Self::Error> {
+        let end = self.len + string.len();
+
+        if end > N {
+            return Err(ArrayBufferError::CapacityExceeded { capacity: N });
+        }
+
+        self.bytes[self.len..end].copy_from_slice(string.as_bytes());
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_write() {
+        let mut buffer = ArrayBuffer::<8>::new();
+
+        assert_eq!(buffer.write_str("abc"), Ok(()));
+        assert_eq!(buffer.write_char('ä'), Ok(()));
+        assert_eq!(write!(buffer, "{}", 12), Ok(()));
+        assert_eq!(buffer.as_str(), "abcä12");
+        assert_eq!(buffer.len(), 7);
+
+        // nothing is written, if it does not fit
+        assert_eq!(
+            buffer.write_char('ä'),
+            Err(ArrayBufferError::CapacityExceeded { capacity: 8 })
+        );
+        assert_eq!(buffer.write_char('!'), Ok(()));
+        assert_eq!(&*buffer, "abcä12!");
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_write_fmt() {
+        let mut buffer = ArrayBuffer::<4>::new();
+
+        assert_eq!(
+            write!(buffer, "{}", 123_456),
+            Err(ArrayBufferError::CapacityExceeded { capacity: 4 })
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::io;
+
+use thiserror::Error;
+
+use super::Buffer;
+
+/// The error of an [`IoBuffer`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum IoBufferError {
+    /// The writer failed.
+    #[error("failed to write the output: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// A [`Buffer`], that writes to an [`io::Write`], like a file or a socket.
+///
+/// Every write is passed on to the writer, so a writer, that is expensive to
+/// write to, should be wrapped in an [`io::BufWriter`].
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::{IoBuffer, Serialize, SubtitleIterator};
+///
+/// let input = "1\n00:00:01,000 --> 00:00:02,000\nHello\n";
+/// let subtitle = SubtitleIterator::from(input).next().unwrap()?;
+///
+/// let mut buffer = IoBuffer::new(Vec::new());
+/// subtitle.serialize(&mut buffer)?;
+///
+/// assert_eq!(buffer.into_inner(), input.as_bytes());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IoBuffer<W> {
+    writer: W,
+}
+
+impl<W> IoBuffer<W> {
+    /// Creates a buffer, that writes to the writer.
+    #[must_use]
+    pub const fn new(writer: W) -> Self { Self { writer } }
+
+    /// Returns a reference to the writer.
+    #[must_use]
+    pub const fn get_ref(&self) -> &W { &self.writer }
+
+    /// Returns a mutable reference to the writer, which can be used to flush
+    /// it.
+    #[must_use]
+    pub const fn get_mut(&mut self) -> &mut W { &mut self.writer }
+
+    /// Returns the writer.
+    #[must_use]
+    pub fn into_inner(self) -> W { self.writer }
+}
+
+impl<W: io::Write> Buffer for IoBuffer<W> {
+    type Error = IoBufferError;
+
+    fn reserve(&mut self, _: usize) -> Result<(), Self::Error> { Ok(()) }
+
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        self.write_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    fn write_str(&mut self, string: &str) -> Result<(), Self::Error> {
+        Ok(self.writer.write_all(string.as_bytes())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// A writer, that only accepts a few bytes.
+    struct Full(usize);
+
+    impl io::Write for Full {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero, "full"));
+            }
+
+            let length = buf.len().min(self.0);
+            self.0 -= length;
+
+            Ok(length)
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    #[test]
+    fn test_write() {
+        let mut buffer = IoBuffer::new(Vec::new());
+
+        buffer.write_str("hällo").unwrap();
+        buffer.write_char('!').unwrap();
+        write!(buffer, " {}", 42).unwrap();
+
+        assert_eq!(buffer.get_ref().as_slice(), "hällo! 42".as_bytes());
+    }
+
+    #[test]
+    fn test_error() {
+        let mut buffer = IoBuffer::new(Full(4));
+
+        buffer.write_str("1234").unwrap();
+
+        // the error of the writer is kept, even if it occurs inside of `write!`
+        for result in [buffer.write_str("5"), write!(buffer, "{}", 5)] {
+            match result {
+                Err(IoBufferError::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::WriteZero),
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+    }
+}
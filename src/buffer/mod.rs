@@ -0,0 +1,362 @@
+use core::fmt;
+use core::marker::{Send, Sized, Sync};
+
+#[cfg(feature = "alloc")]
+use core::convert::Infallible;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "lexical-core")]
+use core::str;
+#[cfg(feature = "lexical-core")]
+use lexical_core::Number;
+
+mod array_buffer;
+#[cfg(feature = "std")]
+mod io_buffer;
+
+pub use array_buffer::{ArrayBuffer, ArrayBufferError};
+#[cfg(feature = "std")]
+pub use io_buffer::{IoBuffer, IoBufferError};
+
+/// An output, to which values are serialized.
+///
+/// Only [`Buffer::reserve`] and [`Buffer::write_char`] have to be
+/// implemented, but most buffers can write a `str` at once.
+///
+/// The trait does not extend [`fmt::Write`], so that it can be implemented
+/// for outputs like `Vec<u8>`, and so that the error of the buffer is
+/// returned instead of [`fmt::Error`]. The [`write!`] macro works with it
+/// through [`Buffer::write_fmt`].
+pub trait Buffer {
+    type Error: fmt::Display + Sized + Send + Sync + 'static;
+
+    /// Reserve at least `n` bytes in the output buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can not grow.
+    fn reserve(&mut self, n: usize) -> Result<(), Self::Error>;
+
+    /// Writes a `char` to the output buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can not take the output.
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error>;
+
+    /// Writes a `str` to the output buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can not take the output.
+    fn write_str(&mut self, string: &str) -> Result<(), Self::Error> {
+        for c in string.chars() {
+            self.write_char(c)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a `String` to the output buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can not take the output.
+    #[cfg(feature = "alloc")]
+    fn write_string(&mut self, string: String) -> Result<(), Self::Error> {
+        self.write_str(&string)
+    }
+
+    /// Writes the formatted arguments to the output buffer, which is used by
+    /// the [`write!`] macro.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can not take the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`format!`], if a formatting trait implementation returns
+    /// an error, although the buffer did not fail.
+    fn write_fmt(&mut self, arguments: fmt::Arguments<'_>) -> Result<(), Self::Error> {
+        let mut writer = FmtWriter::new(self);
+        let result = fmt::write(&mut writer, arguments);
+
+        writer.finish(result)
+    }
+
+    /// Writes an `f32` to the output buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can not take the output.
+    fn write_f32(&mut self, float: f32) -> Result<(), Self::Error> {
+        #[cfg(feature = "lexical-core")]
+        {
+            let mut buffer = [0u8; f32::FORMATTED_SIZE];
+            let string = str::from_utf8(lexical_core::write(float, &mut buffer)).unwrap();
+            self.write_str(string)?;
+        }
+        #[cfg(not(feature = "lexical-core"))]
+        {
+            write!(self, "{float}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes an `f64` to the output buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer can not take the output.
+    fn write_f64(&mut self, float: f64) -> Result<(), Self::Error> {
+        #[cfg(feature = "lexical-core")]
+        {
+            let mut buffer = [0u8; f64::FORMATTED_SIZE];
+            let string = str::from_utf8(lexical_core::write(float, &mut buffer)).unwrap();
+
+            self.write_str(string)?;
+        }
+        #[cfg(not(feature = "lexical-core"))]
+        {
+            write!(self, "{float}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Implements [`fmt::Write`] for a [`Buffer`] and keeps the first error of the
+/// buffer, which would otherwise be lost, because [`fmt::Write`] can only
+/// return a [`fmt::Error`].
+pub(crate) struct FmtWriter<'a, B: Buffer + ?Sized> {
+    buffer: &'a mut B,
+    error: Option<B::Error>,
+}
+
+impl<'a, B: Buffer + ?Sized> FmtWriter<'a, B> {
+    pub(crate) const fn new(buffer: &'a mut B) -> Self {
+        Self {
+            buffer,
+            error: None,
+        }
+    }
+
+    /// Returns the error of the buffer, if the result is an error.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`format!`], if the result is an error, but the buffer did
+    /// not fail.
+    pub(crate) fn finish(self, result: fmt::Result) -> Result<(), B::Error> {
+        match (result, self.error) {
+            (Ok(()), _) => Ok(()),
+            (Err(_), Some(error)) => Err(error),
+            (Err(_), None) => panic!("a formatting trait implementation returned an error"),
+        }
+    }
+}
+
+impl<B: Buffer + ?Sized> fmt::Write for FmtWriter<'_, B> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.buffer.write_str(string).map_err(|error| {
+            self.error.get_or_insert(error);
+            fmt::Error
+        })
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.buffer.write_char(c).map_err(|error| {
+            self.error.get_or_insert(error);
+            fmt::Error
+        })
+    }
+}
+
+impl<B: Buffer + ?Sized> Buffer for &mut B {
+    type Error = B::Error;
+
+    #[inline]
+    fn reserve(&mut self, n: usize) -> Result<(), Self::Error> { (**self).reserve(n) }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> { (**self).write_char(c) }
+
+    #[inline]
+    fn write_str(&mut self, string: &str) -> Result<(), Self::Error> { (**self).write_str(string) }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn write_string(&mut self, string: String) -> Result<(), Self::Error> {
+        (**self).write_string(string)
+    }
+
+    #[inline]
+    fn write_f32(&mut self, float: f32) -> Result<(), Self::Error> { (**self).write_f32(float) }
+
+    #[inline]
+    fn write_f64(&mut self, float: f64) -> Result<(), Self::Error> { (**self).write_f64(float) }
+}
+
+#[cfg(feature = "alloc")]
+impl Buffer for String {
+    type Error = Infallible;
+
+    fn reserve(&mut self, n: usize) -> Result<(), Self::Error> {
+        Self::reserve(self, n);
+
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        self.push(c);
+
+        Ok(())
+    }
+
+    fn write_str(&mut self, string: &str) -> Result<(), Self::Error> {
+        self.push_str(string);
+
+        Ok(())
+    }
+}
+
+/// Writes the UTF-8 encoded output.
+#[cfg(feature = "alloc")]
+impl Buffer for Vec<u8> {
+    type Error = Infallible;
+
+    fn reserve(&mut self, n: usize) -> Result<(), Self::Error> {
+        Self::reserve(self, n);
+
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        self.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+
+        Ok(())
+    }
+
+    fn write_str(&mut self, string: &str) -> Result<(), Self::Error> {
+        self.extend_from_slice(string.as_bytes());
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Serialize, SerializeToString};
+    use pretty_assertions::assert_eq;
+
+    struct MinimalBuffer(pub String);
+
+    impl Buffer for MinimalBuffer {
+        type Error = Infallible;
+
+        fn reserve(&mut self, n: usize) -> Result<(), Self::Error> {
+            self.0.reserve(n);
+
+            Ok(())
+        }
+
+        fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+            self.0.push(c);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_default_buffer_write_str() {
+        let mut buffer = MinimalBuffer(String::new());
+
+        assert_eq!(buffer.write_str("hello "), Ok(()));
+        assert_eq!(buffer.write_str("world"), Ok(()));
+        assert_eq!(&buffer.0, "hello world");
+    }
+
+    #[test]
+    fn test_default_buffer_write_string() {
+        let mut buffer = MinimalBuffer(String::new());
+
+        assert_eq!(buffer.write_string(String::from("hello ")), Ok(()));
+        assert_eq!(buffer.write_string(String::from("world")), Ok(()));
+        assert_eq!(&buffer.0, "hello world");
+    }
+
+    #[test]
+    fn test_default_buffer_write_f32() {
+        let mut buffer = MinimalBuffer(String::new());
+
+        assert_eq!(buffer.write_f32(core::f32::consts::PI), Ok(()));
+        assert_eq!(&buffer.0, "3.1415927");
+    }
+
+    #[test]
+    fn test_default_buffer_write_f64() {
+        let mut buffer = MinimalBuffer(String::new());
+
+        assert_eq!(buffer.write_f64(core::f64::consts::PI), Ok(()));
+        assert_eq!(&buffer.0, "3.141592653589793");
+    }
+
+    #[test]
+    fn test_string_buffer_reserve() {
+        let mut buffer = String::with_capacity(10);
+
+        assert_eq!(buffer.capacity(), 10);
+        assert_eq!(<String as Buffer>::reserve(&mut buffer, 200), Ok(()));
+        assert_eq!(buffer.capacity(), 200);
+    }
+
+    #[test]
+    fn test_string_buffer_write_str() {
+        let mut buffer = String::new();
+
+        assert_eq!(<String as Buffer>::write_str(&mut buffer, "hello"), Ok(()));
+        assert_eq!(<String as Buffer>::write_str(&mut buffer, " world"), Ok(()));
+        assert_eq!(&buffer, "hello world");
+    }
+
+    #[test]
+    fn test_string_buffer_write_char() {
+        let mut buffer = String::new();
+
+        assert_eq!(<String as Buffer>::write_char(&mut buffer, 'h'), Ok(()));
+        assert_eq!(<String as Buffer>::write_char(&mut buffer, 'e'), Ok(()));
+        assert_eq!(<String as Buffer>::write_char(&mut buffer, 'l'), Ok(()));
+        assert_eq!(<String as Buffer>::write_char(&mut buffer, 'l'), Ok(()));
+        assert_eq!(<String as Buffer>::write_char(&mut buffer, 'o'), Ok(()));
+        assert_eq!(&buffer, "hello");
+    }
+
+    #[test]
+    fn test_vec_buffer() {
+        let mut buffer = Vec::new();
+
+        assert_eq!(<Vec<u8> as Buffer>::write_str(&mut buffer, "hällo"), Ok(()));
+        assert_eq!(<Vec<u8> as Buffer>::write_char(&mut buffer, '!'), Ok(()));
+        assert_eq!(write!(buffer, " {}", 42), Ok(()));
+        assert_eq!(buffer, "hällo! 42".as_bytes());
+    }
+
+    #[test]
+    fn test_serialize_to_vec() {
+        let subtitle = crate::SubtitleIterator::from("1\n00:00:01,000 --> 00:00:02,000\nHello\n")
+            .next()
+            .unwrap()
+            .unwrap();
+        let mut buffer = Vec::new();
+
+        assert_eq!(subtitle.serialize(&mut buffer), Ok(()));
+        assert_eq!(buffer, subtitle.serialize_to_string().unwrap().as_bytes());
+    }
+}
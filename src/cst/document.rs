@@ -0,0 +1,911 @@
+use core::fmt;
+use core::ops::Range;
+use core::time::Duration;
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::{Token, TokenKind};
+use crate::parser::{Event, EventParser, ParserError};
+use crate::time::{ParseMode, Timestamp};
+use crate::utils::{Arrow, Pattern};
+use crate::{Buffer, ExactSerializedLength, Serialize};
+
+/// The indices of the tokens of a complete cue in [`Document::tokens`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CueNode {
+    counter: usize,
+    start: usize,
+    end: usize,
+    text: usize,
+    /// The tokens from the counter up to and including the text.
+    tokens: Range<usize>,
+}
+
+/// A lossless concrete syntax tree of an `srt` file.
+///
+/// Every byte of the source is part of exactly one [`Token`], including the
+/// whitespace, line breaks and lines, that could not be parsed. Serializing a
+/// document returns the source unchanged, except for the tokens, that have
+/// been modified with [`CueMut`] and the edits made with [`Document::edit`].
+///
+/// ## Example
+///
+/// ```
+/// use core::time::Duration;
+/// use libsrt::cst::Document;
+///
+/// let source = "1\r\n00:00:01,000 --> 00:00:02,000\r\nHello\r\nWorld\r\n\r\n\r\n\
+///               02\r\n00:00:03,000 --> 00:00:04,000\r\n<i>Bye</i>\r\n";
+/// let mut document = Document::parse(source);
+///
+/// assert_eq!(document.to_string(), source);
+///
+/// let mut cue = document.cue_mut(1).unwrap();
+/// cue.set_timing(Duration::from_secs(3), Duration::from_millis(4_500));
+/// cue.set_text("Good\nbye");
+///
+/// assert_eq!(
+///     document.to_string(),
+///     "1\r\n00:00:01,000 --> 00:00:02,000\r\nHello\r\nWorld\r\n\r\n\r\n\
+///      02\r\n00:00:03,000 --> 00:00:04,500\r\nGood\r\nbye\r\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document<'a> {
+    /// The source including all changes, which is the text of the tokens.
+    source: Cow<'a, str>,
+    tokens: Vec<Token<'a>>,
+    cues: Vec<CueNode>,
+    /// The errors and the index of the first token of their line.
+    errors: Vec<(usize, ParserError)>,
+    /// The indices of the tokens, at which parsing can be resumed.
+    sync: Vec<usize>,
+}
+
+/// Splits the range into whitespace and line break tokens.
+fn push_trivia<'a>(tokens: &mut Vec<Token<'a>>, source: &'a str, range: Range<usize>) {
+    let mut start = range.start;
+
+    while start < range.end {
+        let rest = &source[start..range.end];
+
+        let (kind, length) = {
+            if rest.starts_with("\r\n") {
+                (TokenKind::Newline, 2)
+            } else if rest.starts_with('\n') {
+                (TokenKind::Newline, 1)
+            } else {
+                let end = rest.find('\n').unwrap_or(rest.len());
+
+                // a "\r" is only part of the line break if it is followed by a "\n"
+                if end < rest.len() && rest[..end].ends_with('\r') {
+                    (TokenKind::Whitespace, end - 1)
+                } else {
+                    (TokenKind::Whitespace, end)
+                }
+            }
+        };
+
+        tokens.push(Token::new(kind, source, start..start + length));
+        start += length;
+    }
+}
+
+/// Pushes the content tokens of a line and the trivia in between them.
+fn push_line<'a>(
+    tokens: &mut Vec<Token<'a>>,
+    source: &'a str,
+    range: Range<usize>,
+    content: &[(TokenKind, Range<usize>)],
+) {
+    let mut position = range.start;
+
+    for (kind, span) in content.iter().filter(|(_, span)| !span.is_empty()) {
+        push_trivia(tokens, source, position..span.start);
+        tokens.push(Token::new(*kind, source, span.clone()));
+        position = span.end;
+    }
+
+    push_trivia(tokens, source, position..range.end);
+}
+
+/// Returns the range of the line without the line break and the surrounding
+/// whitespace.
+fn trimmed(source: &str, range: Range<usize>) -> Range<usize> {
+    let line = source[range.clone()].trim_end_matches(&['\r', '\n'][..]);
+    let start = range.start + (line.len() - line.trim_start().len());
+    let end = range.start + line.trim_end().len();
+
+    start..end.max(start)
+}
+
+/// The result of parsing a complete source or a region of a document.
+struct Parsed<'a> {
+    tokens: Vec<Token<'a>>,
+    cues: Vec<CueNode>,
+    /// The errors and the index of the first token of their line.
+    errors: Vec<(usize, ParserError)>,
+    /// The indices of the tokens, in front of which the parser expected a
+    /// counter, so parsing can be resumed there.
+    sync: Vec<usize>,
+    /// `true` if the source ends with a blank line, after which the parser
+    /// expects a counter.
+    is_synced: bool,
+}
+
+#[allow(clippy::too_many_lines)]
+fn parse_tokens(source: &str) -> Parsed<'_> {
+    let mut parser = EventParser::from(source);
+    let mut tokens = Vec::new();
+    let mut cues = Vec::new();
+    let mut errors = Vec::new();
+    let mut sync = Vec::new();
+
+    // the token indices of the counter and the timestamps of the current cue
+    let mut counter = None;
+    let mut timing = None;
+
+    loop {
+        if parser.expects_counter() && sync.last() != Some(&tokens.len()) {
+            sync.push(tokens.len());
+        }
+
+        let start = parser.offset();
+        let event = {
+            if let Some(event) = parser.next() {
+                event
+            } else {
+                break;
+            }
+        };
+        let range = start..parser.offset();
+        let first = tokens.len();
+
+        match event {
+            Ok(Event::Counter(_)) => {
+                push_line(
+                    &mut tokens,
+                    source,
+                    range.clone(),
+                    &[(TokenKind::Counter, trimmed(source, range))],
+                );
+                counter = tokens
+                    .iter()
+                    .skip(first)
+                    .position(|token| token.kind() == TokenKind::Counter)
+                    .map(|index| first + index);
+                timing = None;
+            }
+            Ok(Event::Duration { .. }) => {
+                let line = trimmed(source, range.clone());
+                let arrow = Arrow
+                    .find_in(&source[line.clone()])
+                    .map_or(line.end..line.end, |(start, end)| {
+                        line.start + start..line.start + end
+                    });
+
+                push_line(
+                    &mut tokens,
+                    source,
+                    range,
+                    &[
+                        (TokenKind::Timestamp, line.start..arrow.start),
+                        (TokenKind::Arrow, arrow.clone()),
+                        (TokenKind::Timestamp, arrow.end..line.end),
+                    ],
+                );
+                timing = counter.map(|_| (first, tokens.len()));
+            }
+            Ok(Event::Text(text)) => {
+                let span = text
+                    .span()
+                    .unwrap_or_else(|| trimmed(source, range.clone()));
+
+                push_line(&mut tokens, source, range, &[(TokenKind::Text, span)]);
+
+                let text = first
+                    + tokens[first..]
+                        .iter()
+                        .position(|token| token.kind() == TokenKind::Text)
+                        .unwrap_or_default();
+
+                if let (Some(counter), Some((timing_start, timing_end))) = (counter, timing) {
+                    let timestamps = (timing_start..timing_end)
+                        .filter(|index| tokens[*index].kind() == TokenKind::Timestamp)
+                        .collect::<Vec<_>>();
+
+                    cues.push(CueNode {
+                        counter,
+                        start: timestamps[0],
+                        end: timestamps[1],
+                        text,
+                        tokens: counter..text + 1,
+                    });
+                }
+
+                counter = None;
+                timing = None;
+            }
+            Ok(Event::Empty) => push_trivia(&mut tokens, source, range),
+            Err(error) => {
+                let line = trimmed(source, range.clone());
+
+                // the span of the parser is not always relative to the source
+                errors.push((first, error.with_span(line.clone())));
+                push_line(&mut tokens, source, range, &[(TokenKind::Invalid, line)]);
+                counter = None;
+                timing = None;
+            }
+        }
+    }
+
+    // a region can only be parsed on its own, if the parser did not stop
+    // early and the last cue has been ended by a blank line
+    let is_synced = parser.expects_counter()
+        && parser.offset() == source.len()
+        && (source.is_empty() || source.ends_with("\n\n") || source.ends_with("\n\r\n"));
+
+    // the parser ignores trailing empty lines
+    let end = tokens.last().map_or(0, |token| token.span().end);
+    push_trivia(&mut tokens, source, end..source.len());
+
+    // resuming at the end of the document is the same as appending
+    sync.retain(|index| *index < tokens.len());
+
+    Parsed {
+        tokens,
+        cues,
+        errors,
+        sync,
+        is_synced,
+    }
+}
+
+impl CueNode {
+    /// Moves the token indices, after `removed` tokens in front of them have
+    /// been replaced with `inserted` tokens.
+    fn shift(&mut self, removed: usize, inserted: usize) {
+        let shift = |index: usize| index + inserted - removed;
+
+        self.counter = shift(self.counter);
+        self.start = shift(self.start);
+        self.end = shift(self.end);
+        self.text = shift(self.text);
+        self.tokens = shift(self.tokens.start)..shift(self.tokens.end);
+    }
+}
+
+impl<'a> Document<'a> {
+    /// Parses the source, which never fails, because lines, that can not be
+    /// parsed, are kept as [`TokenKind::Invalid`] and their errors are
+    /// returned by [`Document::errors`].
+    #[must_use]
+    pub fn parse(source: &'a str) -> Self {
+        let Parsed {
+            tokens,
+            cues,
+            errors,
+            sync,
+            ..
+        } = parse_tokens(source);
+
+        Self {
+            source: Cow::Borrowed(source),
+            tokens,
+            cues,
+            errors,
+            sync,
+        }
+    }
+
+    /// Returns the byte offset of the token at the index in the current text,
+    /// which is the length of the text for the index behind the last token.
+    fn offset(&self, index: usize) -> usize {
+        self.tokens.get(index).map_or_else(
+            || self.tokens.last().map_or(0, |token| token.span().end),
+            |token| token.span().start,
+        )
+    }
+
+    /// Replaces the byte range of the current text with the replacement and
+    /// re-parses only the cues, that are affected by the edit.
+    ///
+    /// Parsing is resumed at the last cue in front of the edit and stops as
+    /// soon as the parser is back in sync with the cues behind it, whose
+    /// tokens are kept and only moved. The result is the same as parsing the
+    /// edited text from scratch, but only the edited cues are tokenized again.
+    ///
+    /// Returns the byte range of the re-parsed region in the edited text.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds or does not lie on `char` boundaries.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use libsrt::cst::Document;
+    ///
+    /// let mut document = Document::parse(
+    ///     "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nBye\n",
+    /// );
+    ///
+    /// let end = document.cue(0).unwrap().span().end;
+    /// document.edit(end..end, " there");
+    ///
+    /// assert_eq!(document.cue(0).unwrap().text(), "Hello there");
+    /// assert_eq!(
+    ///     document.cue(1).unwrap().start(),
+    ///     Some(Duration::from_secs(3))
+    /// );
+    /// ```
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> Range<usize> {
+        let length = self.offset(self.tokens.len());
+        assert!(
+            range.start <= range.end && range.end <= length,
+            "the range {:?} is out of bounds for a document of length {}",
+            range,
+            length
+        );
+
+        // the parser does not depend on anything in front of a sync point,
+        // but an edit directly behind one might change the line in front of it
+        let first = self.sync[..self
+            .sync
+            .partition_point(|index| self.offset(*index) < range.start)]
+            .last()
+            .copied()
+            .unwrap_or(0);
+        let position = self.offset(first);
+
+        let candidates = self.sync[self
+            .sync
+            .partition_point(|index| self.offset(*index) < range.end)..]
+            .iter()
+            .copied()
+            .filter(|index| *index < self.tokens.len())
+            .chain(Some(self.tokens.len()));
+
+        for last in candidates {
+            let mut text = self.tokens[first..last]
+                .iter()
+                .map(Token::as_str)
+                .collect::<String>();
+            text.replace_range(range.start - position..range.end - position, replacement);
+
+            let parsed = parse_tokens(&text);
+
+            if !parsed.is_synced && last < self.tokens.len() {
+                continue;
+            }
+
+            self.splice(first..last, position, parsed);
+            self.source.to_mut().replace_range(range, replacement);
+
+            return position..position + text.len();
+        }
+
+        unreachable!("the end of the document is always a candidate")
+    }
+
+    /// Replaces the tokens in the range with the ones of the re-parsed region,
+    /// which starts at the byte offset `position`.
+    fn splice(&mut self, range: Range<usize>, position: usize, parsed: Parsed<'_>) {
+        let removed = (range.len(), self.offset(range.end) - position);
+        let inserted = (
+            parsed.tokens.len(),
+            parsed.tokens.last().map_or(0, |token| token.span().end),
+        );
+
+        for token in &mut self.tokens[range.end..] {
+            token.shift(removed.1, inserted.1);
+        }
+
+        self.tokens.splice(
+            range.clone(),
+            parsed.tokens.into_iter().map(|token| {
+                let mut token = token.into_owned();
+                token.shift(0, position);
+                token
+            }),
+        );
+
+        let cues = self.cues.partition_point(|cue| cue.counter < range.start)
+            ..self.cues.partition_point(|cue| cue.counter < range.end);
+
+        for cue in &mut self.cues[cues.end..] {
+            cue.shift(removed.0, inserted.0);
+        }
+
+        self.cues.splice(
+            cues,
+            parsed.cues.into_iter().map(|mut cue| {
+                cue.shift(0, range.start);
+                cue
+            }),
+        );
+
+        let errors = self
+            .errors
+            .partition_point(|(index, _)| *index < range.start)
+            ..self.errors.partition_point(|(index, _)| *index < range.end);
+
+        let shifted = self.errors.drain(errors.end..).map(|(index, error)| {
+            (
+                index + inserted.0 - removed.0,
+                error.shift(removed.1, inserted.1),
+            )
+        });
+        let mut tail = shifted.collect::<Vec<_>>();
+
+        self.errors.truncate(errors.start);
+        self.errors.extend(
+            parsed
+                .errors
+                .into_iter()
+                .map(|(index, error)| (index + range.start, error.shift(0, position))),
+        );
+        self.errors.append(&mut tail);
+
+        let sync = self.sync.partition_point(|index| *index < range.start)
+            ..self.sync.partition_point(|index| *index < range.end);
+
+        for index in &mut self.sync[sync.end..] {
+            *index = *index + inserted.0 - removed.0;
+        }
+
+        self.sync.splice(
+            sync,
+            parsed.sync.into_iter().map(|index| index + range.start),
+        );
+        self.sync.dedup();
+    }
+
+    /// Moves the tokens and errors behind the token at the index, after it
+    /// has been replaced.
+    fn shift_behind(&mut self, index: usize, removed: usize, inserted: usize) {
+        for token in &mut self.tokens[index + 1..] {
+            token.shift(removed, inserted);
+        }
+
+        for (_, error) in self.errors.iter_mut().filter(|(error, _)| *error > index) {
+            *error = error.clone().shift(removed, inserted);
+        }
+    }
+
+    /// Converts the document into one, that does not borrow from the source.
+    #[must_use]
+    pub fn into_owned(self) -> Document<'static> {
+        Document {
+            source: Cow::Owned(self.source.into_owned()),
+            tokens: self.tokens.into_iter().map(Token::into_owned).collect(),
+            cues: self.cues,
+            errors: self.errors,
+            sync: self.sync,
+        }
+    }
+
+    /// Returns the source, that has been parsed, with all changes made since
+    /// then.
+    #[must_use]
+    pub fn source(&self) -> &str { &self.source }
+
+    /// Returns all tokens of the document in order.
+    #[must_use]
+    pub fn tokens(&self) -> &[Token<'a>] { &self.tokens }
+
+    /// Returns the errors of the lines, that could not be parsed, where the
+    /// span of an error is the one of its [`TokenKind::Invalid`] token.
+    pub fn errors(&self) -> impl Iterator<Item = &ParserError> + '_ {
+        self.errors.iter().map(|(_, error)| error)
+    }
+
+    /// Returns the number of complete cues.
+    #[must_use]
+    pub const fn len(&self) -> usize { self.cues.len() }
+
+    /// Returns `true` if the document does not contain a complete cue.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.cues.is_empty() }
+
+    /// Returns the cue at the index (starting at `0`), which is not
+    /// necessarily its counter.
+    #[must_use]
+    pub fn cue(&self, index: usize) -> Option<Cue<'_, 'a>> {
+        Some(Cue {
+            document: self,
+            node: self.cues.get(index)?,
+        })
+    }
+
+    /// Returns an iterator over all complete cues.
+    pub fn cues(&self) -> impl Iterator<Item = Cue<'_, 'a>> + '_ {
+        self.cues.iter().map(move |node| {
+            Cue {
+                document: self,
+                node,
+            }
+        })
+    }
+
+    /// Returns the cue at the index, so it can be modified.
+    #[must_use]
+    pub fn cue_mut(&mut self, index: usize) -> Option<CueMut<'_, 'a>> {
+        let node = self.cues.get(index)?.clone();
+
+        Some(CueMut {
+            document: self,
+            node,
+        })
+    }
+}
+
+impl fmt::Display for Document<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for token in &self.tokens {
+            f.write_str(token.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ExactSerializedLength for Document<'_> {
+    fn serialized_len(&self) -> usize { self.tokens.iter().map(|token| token.as_str().len()).sum() }
+}
+
+impl<B: Buffer> Serialize<B> for Document<'_> {
+    type Error = B::Error;
+
+    fn serialize(&self, mut buffer: B) -> Result<(), Self::Error> {
+        buffer.reserve(self.serialized_len())?;
+
+        for token in &self.tokens {
+            buffer.write_str(token.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A complete cue of a [`Document`].
+#[derive(Debug, Copy, Clone)]
+pub struct Cue<'d, 'a> {
+    document: &'d Document<'a>,
+    node: &'d CueNode,
+}
+
+impl<'d> Cue<'d, '_> {
+    fn token(&self, index: usize) -> &'d str { self.document.tokens[index].as_str() }
+
+    /// Returns the counter of the cue, or `None` if it has been replaced by
+    /// something, that is not a number.
+    #[must_use]
+    pub fn counter(&self) -> Option<usize> { self.token(self.node.counter).parse().ok() }
+
+    /// Returns the start of the cue, or `None` if it can not be parsed.
+    #[must_use]
+    pub fn start(&self) -> Option<Duration> {
+        Timestamp::parse_srt(self.token(self.node.start), ParseMode::Lenient)
+            .ok()?
+            .to_duration()
+    }
+
+    /// Returns the end of the cue, or `None` if it can not be parsed.
+    #[must_use]
+    pub fn end(&self) -> Option<Duration> {
+        Timestamp::parse_srt(self.token(self.node.end), ParseMode::Lenient)
+            .ok()?
+            .to_duration()
+    }
+
+    /// Returns the (raw) text of the cue.
+    #[must_use]
+    pub fn text(&self) -> &'d str { self.token(self.node.text) }
+
+    /// Returns the tokens of the cue, from the counter up to and including
+    /// the text.
+    #[must_use]
+    pub fn tokens(&self) -> &'d [Token<'d>] { &self.document.tokens[self.node.tokens.clone()] }
+
+    /// Returns the byte range of the cue in the current text of the document,
+    /// from the counter up to the end of the text.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        let tokens = &self.document.tokens;
+
+        tokens[self.node.counter].span().start..tokens[self.node.text].span().end
+    }
+}
+
+/// A cue of a [`Document`], that can be modified.
+///
+/// Only the modified tokens change, so everything else is serialized like in
+/// the source.
+#[derive(Debug)]
+pub struct CueMut<'d, 'a> {
+    document: &'d mut Document<'a>,
+    node: CueNode,
+}
+
+impl CueMut<'_, '_> {
+    fn replace(&mut self, index: usize, text: String) {
+        let token = &mut self.document.tokens[index];
+
+        if token.as_str() != text {
+            let span = token.span();
+            let removed = token.replace(text);
+            let inserted = token.span().len();
+
+            self.document
+                .source
+                .to_mut()
+                .replace_range(span, self.document.tokens[index].as_str());
+
+            self.document.shift_behind(index, removed, inserted);
+        }
+    }
+
+    /// Replaces the counter.
+    pub fn set_counter(&mut self, counter: usize) {
+        self.replace(self.node.counter, counter.to_string());
+    }
+
+    /// Replaces the start and the end, where a timestamp, that did not change,
+    /// keeps its original formatting.
+    pub fn set_timing(&mut self, start: Duration, end: Duration) {
+        let cue = Cue {
+            document: self.document,
+            node: &self.node,
+        };
+        let (old_start, old_end) = (cue.start(), cue.end());
+
+        if old_start != Some(start) {
+            self.replace(self.node.start, Timestamp::from(start).to_string());
+        }
+
+        if old_end != Some(end) {
+            self.replace(self.node.end, Timestamp::from(end).to_string());
+        }
+    }
+
+    /// Replaces the text, where the line breaks are written like the ones
+    /// in the source.
+    ///
+    /// The text should neither be empty nor contain an empty line, because
+    /// that would end the cue.
+    pub fn set_text(&mut self, text: &str) {
+        let is_crlf = self.document.tokens[self.node.text..]
+            .iter()
+            .find(|token| token.kind() == TokenKind::Newline)
+            .is_some_and(|token| token.as_str() == "\r\n");
+
+        let mut result = String::with_capacity(text.len());
+
+        for (index, line) in text.lines().enumerate() {
+            if index > 0 {
+                result.push_str(if is_crlf { "\r\n" } else { "\n" });
+            }
+
+            result.push_str(line);
+        }
+
+        self.replace(self.node.text, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use crate::serialize::SerializeToString;
+    use pretty_assertions::assert_eq;
+
+    const INPUTS: &[&str] = &[
+        "",
+        "\n\n",
+        "1\n00:00:01,000 --> 00:00:02,000\nHello\n",
+        "1\n00:00:01,000 --> 00:00:02,000\nHello",
+        "\r\n  1  \r\n00:00:01,000 --> 00:00:02,000\r\nHello\r\nWorld\r\n\r\n\r\n\r\n",
+        "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n007\n00:00:03,000 --> 00:00:04,000\nBye\n\n\n",
+        "one\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\n00:00:03 --> 00:00:04\nBye\n",
+    ];
+
+    #[test]
+    fn test_lossless() {
+        for input in INPUTS {
+            let document = Document::parse(input);
+
+            assert_eq!(document.serialize_to_string().unwrap(), *input);
+            crate::serialize::tests::assert_exact_length(&document);
+
+            // the tokens cover the source without gaps
+            let mut position = 0;
+
+            for token in document.tokens() {
+                assert_eq!(token.span().start, position);
+                assert!(!token.as_str().is_empty());
+                position = token.span().end;
+            }
+
+            assert_eq!(position, input.len());
+        }
+    }
+
+    #[test]
+    fn test_cues() {
+        let document = Document::parse(INPUTS[5]);
+
+        assert_eq!(document.len(), 2);
+        assert_eq!(document.errors().count(), 0);
+
+        let cue = document.cue(1).unwrap();
+
+        assert_eq!(cue.counter(), Some(7));
+        assert_eq!(cue.start(), Some(Duration::from_secs(3)));
+        assert_eq!(cue.end(), Some(Duration::from_secs(4)));
+        assert_eq!(cue.text(), "Bye");
+        assert_eq!(
+            &INPUTS[5][cue.span()],
+            "007\n00:00:03,000 --> 00:00:04,000\nBye"
+        );
+        assert_eq!(
+            cue.tokens()
+                .iter()
+                .filter(|token| !token.kind().is_trivia())
+                .map(|token| (token.kind(), token.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (TokenKind::Counter, "007"),
+                (TokenKind::Timestamp, "00:00:03,000"),
+                (TokenKind::Arrow, " --> "),
+                (TokenKind::Timestamp, "00:00:04,000"),
+                (TokenKind::Text, "Bye"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let document = Document::parse(INPUTS[6]);
+
+        assert_eq!(document.len(), 0);
+        assert_eq!(document.errors().count(), 2);
+        assert_eq!(
+            document
+                .tokens()
+                .iter()
+                .filter(|token| token.kind() == TokenKind::Invalid)
+                .map(Token::as_str)
+                .collect::<Vec<_>>(),
+            vec!["one", "00:00:03 --> 00:00:04"]
+        );
+    }
+
+    #[test]
+    fn test_edit() {
+        let mut document = Document::parse(INPUTS[4]);
+        let mut cue = document.cue_mut(0).unwrap();
+
+        cue.set_counter(1);
+        cue.set_timing(Duration::from_secs(1), Duration::from_secs(3));
+        cue.set_text("Hello\nthere");
+
+        assert_eq!(
+            document.to_string(),
+            "\r\n  1  \r\n00:00:01,000 --> 00:00:03,000\r\nHello\r\nthere\r\n\r\n\r\n\r\n"
+        );
+        assert_eq!(document.source(), document.to_string());
+        assert_eq!(
+            document
+                .tokens()
+                .iter()
+                .filter(|token| token.is_modified())
+                .map(Token::as_str)
+                .collect::<Vec<_>>(),
+            vec!["00:00:03,000", "Hello\r\nthere"]
+        );
+    }
+
+    #[test]
+    fn test_edit_spans() {
+        let mut document = Document::parse(INPUTS[5]);
+        document.cue_mut(0).unwrap().set_counter(1000);
+
+        let cue = document.cue(1).unwrap();
+
+        assert_eq!(
+            &document.to_string()[cue.span()],
+            "007\n00:00:03,000 --> 00:00:04,000\nBye"
+        );
+    }
+
+    /// Applies the edit incrementally and checks, that the result is the same
+    /// as parsing the edited text from scratch.
+    fn check_edit(input: &str, range: Range<usize>, replacement: &str) -> Range<usize> {
+        let mut document = Document::parse(input).into_owned();
+        let reparsed = document.edit(range.clone(), replacement);
+
+        let mut expected = input.to_string();
+        expected.replace_range(range, replacement);
+
+        assert_eq!(document.to_string(), expected);
+        assert_eq!(document, Document::parse(&expected));
+
+        reparsed
+    }
+
+    #[test]
+    fn test_incremental() {
+        let edits: &[(Range<usize>, &str)] = &[
+            (0..0, ""),
+            (0..0, "\n"),
+            (0..1, "2"),
+            (0..2, ""),
+            (2..2, "x"),
+            (32..32, "\n"),
+            (37..37, "\n\n"),
+            (37..38, ""),
+            (38..39, ""),
+            (38..38, "1\n00:00:02,500 --> 00:00:02,700\nNew\n\n"),
+            (39..42, "13"),
+            (45..45, "00:00:03 --> 00:00:04\n"),
+            (71..71, "More\n"),
+            (0..75, ""),
+        ];
+
+        for input in INPUTS {
+            for (range, replacement) in edits {
+                if range.end <= input.len() && input.is_char_boundary(range.start) {
+                    check_edit(input, range.clone(), replacement);
+                }
+            }
+
+            // every possible deletion of a single byte and insertion of a line break
+            for index in 0..input.len() {
+                check_edit(input, index..index + 1, "");
+                check_edit(input, index..index, "\n");
+                check_edit(input, index..index, "\r\n\r\n");
+                check_edit(input, index..index, "9");
+            }
+
+            check_edit(
+                input,
+                input.len()..input.len(),
+                "\n\n3\n00:00:05,000 --> 00:00:06,000\nEnd\n",
+            );
+        }
+    }
+
+    #[test]
+    fn test_incremental_region() {
+        let mut input = String::new();
+
+        for counter in 1..=5_000 {
+            writeln!(
+                input,
+                "{counter}\n{} --> {}\nLine {counter}\n",
+                Timestamp::from_millis(counter * 1_000),
+                Timestamp::from_millis(counter * 1_000 + 500)
+            )
+            .unwrap();
+        }
+
+        let mut document = Document::parse(&input);
+        let cue = document.cue(2_500).unwrap().span();
+
+        let reparsed = document.edit(cue.end..cue.end, "\nwith a second line");
+
+        // only the edited cue has been parsed again
+        assert!(reparsed.len() < 100, "{:?}", reparsed);
+        assert_eq!(
+            document.cue(2_500).unwrap().text(),
+            "Line 2501\nwith a second line"
+        );
+        assert_eq!(document.cue(2_501).unwrap().counter(), Some(2_502));
+        assert_eq!(document.len(), 5_000);
+    }
+}
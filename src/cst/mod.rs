@@ -0,0 +1,15 @@
+//! A lossless concrete syntax tree (CST) for edits, that preserve the
+//! formatting of a file.
+//!
+//! Unlike [`SubtitleIterator`], a [`Document`] keeps every byte of the source,
+//! like the line breaks (`\n` or `\r\n`), additional empty lines, whitespace
+//! and the formatting of counters and timestamps, so editing a single cue
+//! results in a minimal diff.
+//!
+//! [`SubtitleIterator`]: crate::SubtitleIterator
+
+mod document;
+mod token;
+
+pub use document::{Cue, CueMut, Document};
+pub use token::{Token, TokenKind};
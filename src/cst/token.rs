@@ -0,0 +1,101 @@
+use core::ops::Range;
+
+use alloc::borrow::Cow;
+
+/// The kind of a [`Token`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum TokenKind {
+    /// Spaces or tabs in front of or behind the content of a line.
+    Whitespace,
+    /// A line break, which is either `"\n"` or `"\r\n"`.
+    Newline,
+    /// The counter of a cue.
+    Counter,
+    /// The start or end of a cue.
+    Timestamp,
+    /// The `-->` in between two timestamps, including the surrounding spaces.
+    Arrow,
+    /// The (possibly multi-line) text of a cue.
+    Text,
+    /// A line, that could not be parsed.
+    Invalid,
+}
+
+impl TokenKind {
+    /// Returns `true` for tokens, that do not carry any meaning, like
+    /// whitespace and line breaks.
+    #[must_use]
+    pub const fn is_trivia(self) -> bool { matches!(self, Self::Whitespace | Self::Newline) }
+}
+
+/// A part of a [`Document`], which is either a part of the source or an edit.
+///
+/// [`Document`]: super::Document
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token<'a> {
+    kind: TokenKind,
+    span: Range<usize>,
+    text: Cow<'a, str>,
+    modified: bool,
+}
+
+impl<'a> Token<'a> {
+    pub(crate) fn new(kind: TokenKind, source: &'a str, span: Range<usize>) -> Self {
+        Self {
+            kind,
+            text: Cow::Borrowed(&source[span.clone()]),
+            span,
+            modified: false,
+        }
+    }
+
+    /// Replaces the text and returns the previous length of the token.
+    pub(crate) fn replace(&mut self, text: impl Into<Cow<'a, str>>) -> usize {
+        let length = self.span.len();
+
+        self.text = text.into();
+        self.modified = true;
+        self.span.end = self.span.start + self.text.len();
+
+        length
+    }
+
+    /// Moves the token, after `removed` bytes in front of it have been
+    /// replaced with `inserted` bytes.
+    pub(crate) const fn shift(&mut self, removed: usize, inserted: usize) {
+        self.span = self.span.start + inserted - removed..self.span.end + inserted - removed;
+    }
+
+    /// Converts the token into one, that does not borrow from the source.
+    ///
+    /// This does not mark the token as modified.
+    #[must_use]
+    pub fn into_owned(self) -> Token<'static> {
+        Token {
+            kind: self.kind,
+            span: self.span,
+            text: Cow::Owned(self.text.into_owned()),
+            modified: self.modified,
+        }
+    }
+
+    /// Returns the kind of the token.
+    #[must_use]
+    pub const fn kind(&self) -> TokenKind { self.kind }
+
+    /// Returns the byte range of the token in the current text of the
+    /// document, which includes all edits in front of it.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> { self.span.clone() }
+
+    /// Returns the current text of the token.
+    #[must_use]
+    pub fn as_str(&self) -> &str { &self.text }
+
+    /// Returns `true` if the token has been modified with [`CueMut`].
+    ///
+    /// [`CueMut`]: super::CueMut
+    #[must_use]
+    pub const fn is_modified(&self) -> bool { self.modified }
+}
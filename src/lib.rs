@@ -12,35 +12,45 @@
 #![warn(clippy::nursery, clippy::pedantic)]
 #![allow(clippy::module_inception, clippy::redundant_pub_crate)]
 #![warn(missing_debug_implementations)]
-#![feature(never_type)]
-#![feature(result_flattening)]
-#![feature(trait_alias)]
-#![feature(maybe_uninit_uninit_array)]
-#![feature(pattern)]
-#![feature(array_map)]
-#![feature(associated_type_bounds)]
+#![cfg_attr(feature = "nightly", feature(iter_next_chunk))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+pub mod cst;
+#[cfg(feature = "mmap")]
+mod mapped;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod parser;
 mod subtitle;
 mod subtitle_iterator;
 pub mod text;
+pub mod time;
+#[cfg(feature = "alloc")]
+pub mod track;
 
 mod buffer;
 mod serialize;
 mod utils;
 
-pub use crate::buffer::Buffer;
+pub use crate::buffer::{ArrayBuffer, ArrayBufferError, Buffer};
+#[cfg(feature = "std")]
+pub use crate::buffer::{IoBuffer, IoBufferError};
 
 #[cfg(feature = "alloc")]
-pub use crate::serialize::SerializeToString;
+pub use crate::serialize::{SerializeToString, SerializeWithConfigToString};
 
 pub use crate::serialize::{ExactSerializedLength, Serialize, SerializeWithConfig};
 
 pub use crate::subtitle::{InitError, Subtitle, SubtitleError, SubtitleInit};
 pub use crate::text::Text;
+pub use crate::time::Timestamp;
 
+#[cfg(feature = "mmap")]
+pub use crate::mapped::{Encoding, SubtitleFile, SubtitleFileError};
+#[cfg(feature = "rayon")]
+pub use crate::parallel::par_parse;
 pub use crate::parser::ParserError;
 pub use crate::subtitle_iterator::SubtitleIterator;
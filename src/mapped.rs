@@ -0,0 +1,342 @@
+use core::str;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use alloc::string::String;
+use memmap2::Mmap;
+use thiserror::Error;
+
+use crate::SubtitleIterator;
+
+/// The error of a [`SubtitleFile`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SubtitleFileError {
+    /// The file could not be opened or mapped.
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    /// The file is neither valid UTF-8 nor UTF-16 with a byte order mark.
+    #[error("invalid UTF-8 at byte {position}")]
+    InvalidUtf8 {
+        /// The byte offset in the file, at which the invalid sequence starts.
+        position: usize,
+    },
+    /// The file starts with a UTF-16 byte order mark, but is not valid UTF-16.
+    #[error("invalid UTF-16 at byte {position}")]
+    InvalidUtf16 {
+        /// The byte offset in the file, at which the invalid code unit starts.
+        position: usize,
+    },
+}
+
+/// The encoding of a [`SubtitleFile`], which is detected by its byte order
+/// mark.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// UTF-8 with or without a byte order mark.
+    Utf8,
+    /// UTF-16 little endian.
+    Utf16Le,
+    /// UTF-16 big endian.
+    Utf16Be,
+}
+
+impl Encoding {
+    const UTF16BE_BOM: &'static [u8] = b"\xFE\xFF";
+    const UTF16LE_BOM: &'static [u8] = b"\xFF\xFE";
+    const UTF8_BOM: &'static [u8] = b"\xEF\xBB\xBF";
+
+    /// Returns the encoding and the length of the byte order mark.
+    fn detect(bytes: &[u8]) -> (Self, usize) {
+        if bytes.starts_with(Self::UTF8_BOM) {
+            (Self::Utf8, Self::UTF8_BOM.len())
+        } else if bytes.starts_with(Self::UTF16LE_BOM) {
+            (Self::Utf16Le, Self::UTF16LE_BOM.len())
+        } else if bytes.starts_with(Self::UTF16BE_BOM) {
+            (Self::Utf16Be, Self::UTF16BE_BOM.len())
+        } else {
+            (Self::Utf8, 0)
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Source {
+    /// A mapping, which is valid UTF-8 from `start` on.
+    Mapped { map: Mmap, start: usize },
+    /// The decoded content of a file, that is not encoded in UTF-8.
+    Decoded { text: String, encoding: Encoding },
+}
+
+/// A memory mapped `.srt` file, whose subtitles borrow from the mapping.
+///
+/// A UTF-8 file is only validated, so parsing it does not copy the file or
+/// allocate for each subtitle. A UTF-16 file, which has to start with a byte
+/// order mark, is decoded once into an owned string.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::SubtitleFile;
+/// # let path = std::env::temp_dir().join("libsrt-subtitle-file-example.srt");
+/// # std::fs::write(&path, "1\n00:00:01,000 --> 00:00:02,000\nHello\n")?;
+///
+/// // SAFETY: the file is not modified, while it is mapped
+/// let file = unsafe { SubtitleFile::open(&path)? };
+///
+/// for subtitle in file.subtitles() {
+///     assert_eq!(subtitle?.text().as_raw(), "Hello");
+/// }
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct SubtitleFile {
+    source: Source,
+}
+
+impl SubtitleFile {
+    /// Opens and maps the file at the `path`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, while it is mapped,
+    /// otherwise the content might no longer be valid UTF-8 or not be
+    /// readable at all, see [`Mmap::map`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the file can not be opened or mapped or if it is
+    /// not encoded in UTF-8 or UTF-16.
+    pub unsafe fn open(path: impl AsRef<Path>) -> Result<Self, SubtitleFileError> {
+        let file = File::open(path)?;
+
+        unsafe { Self::map(&file) }
+    }
+
+    /// Maps the `file`, which can be closed afterwards.
+    ///
+    /// # Safety
+    ///
+    /// See [`SubtitleFile::open`].
+    ///
+    /// # Errors
+    ///
+    /// See [`SubtitleFile::open`].
+    pub unsafe fn map(file: &File) -> Result<Self, SubtitleFileError> {
+        // SAFETY: the caller guarantees, that the file is not modified
+        let map = unsafe { Mmap::map(file)? };
+        let (encoding, start) = Encoding::detect(&map);
+
+        let source = match encoding {
+            Encoding::Utf8 => {
+                str::from_utf8(&map[start..]).map_err(|error| {
+                    SubtitleFileError::InvalidUtf8 {
+                        position: start + error.valid_up_to(),
+                    }
+                })?;
+
+                Source::Mapped { map, start }
+            }
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                Source::Decoded {
+                    text: decode_utf16(&map[start..], encoding).map_err(|position| {
+                        SubtitleFileError::InvalidUtf16 {
+                            position: start + position,
+                        }
+                    })?,
+                    encoding,
+                }
+            }
+        };
+
+        Ok(Self { source })
+    }
+
+    /// Returns the content of the file without the byte order mark.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match &self.source {
+            // SAFETY: the mapping has been validated in `Self::map`
+            Source::Mapped { map, start } => unsafe { str::from_utf8_unchecked(&map[*start..]) },
+            Source::Decoded { text, .. } => text,
+        }
+    }
+
+    /// Returns the encoding of the file.
+    #[must_use]
+    pub const fn encoding(&self) -> Encoding {
+        match &self.source {
+            Source::Mapped { .. } => Encoding::Utf8,
+            Source::Decoded { encoding, .. } => *encoding,
+        }
+    }
+
+    /// Returns `true` if the content borrows from the mapping, instead of
+    /// having been decoded into an owned string.
+    #[must_use]
+    pub const fn is_mapped(&self) -> bool { matches!(self.source, Source::Mapped { .. }) }
+
+    /// Returns an iterator over the subtitles of the file.
+    pub fn subtitles(&self) -> SubtitleIterator<'_> { SubtitleIterator::from(self.as_str()) }
+}
+
+/// Decodes UTF-16 `bytes` or returns the byte offset of the first invalid code
+/// unit.
+fn decode_utf16(bytes: &[u8], encoding: Encoding) -> Result<String, usize> {
+    let units = bytes.chunks(2).map(|unit| {
+        match (encoding, unit) {
+            (Encoding::Utf16Be, &[high, low]) => u16::from_be_bytes([high, low]),
+            (_, &[low, high]) => u16::from_le_bytes([low, high]),
+            // an odd number of bytes is decoded as an unpaired surrogate
+            _ => 0xD800,
+        }
+    });
+
+    let mut text = String::with_capacity(bytes.len() / 2);
+    let mut position = 0;
+
+    for c in char::decode_utf16(units) {
+        let c = c.map_err(|_| position)?;
+
+        text.push(c);
+        position += c.len_utf16() * 2;
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    const INPUT: &str =
+        "1\n00:00:01,000 --> 00:00:02,000\nHällo\n\n2\n00:00:03,000 --> 00:00:04,000\nWörld\n";
+
+    /// A file in the temporary directory, which is removed when dropped.
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str, content: &[u8]) -> Self {
+            let path = std::env::temp_dir().join(format!("libsrt-{}-{name}", std::process::id()));
+            std::fs::write(&path, content).unwrap();
+
+            Self(path)
+        }
+
+        fn open(&self) -> Result<SubtitleFile, SubtitleFileError> {
+            unsafe { SubtitleFile::open(&self.0) }
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) { std::fs::remove_file(&self.0).unwrap(); }
+    }
+
+    fn utf16(bom: &[u8], text: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+        bom.iter()
+            .copied()
+            .chain(text.encode_utf16().flat_map(to_bytes))
+            .collect()
+    }
+
+    fn texts(file: &SubtitleFile) -> Vec<String> {
+        file.subtitles()
+            .map(|subtitle| subtitle.unwrap().text().as_raw().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_utf8() {
+        let temp = TempFile::new("utf8.srt", INPUT.as_bytes());
+        let file = temp.open().unwrap();
+
+        assert!(file.is_mapped());
+        assert_eq!(file.encoding(), Encoding::Utf8);
+        assert_eq!(file.as_str(), INPUT);
+        assert_eq!(texts(&file), vec!["Hällo", "Wörld"]);
+
+        // the subtitles borrow from the mapping
+        let subtitle = file.subtitles().next().unwrap().unwrap();
+        let range = file.as_str().as_bytes().as_ptr_range();
+        assert!(range.contains(&subtitle.text().as_raw().as_ptr()));
+    }
+
+    #[test]
+    fn test_utf8_bom() {
+        let temp = TempFile::new(
+            "utf8-bom.srt",
+            &[Encoding::UTF8_BOM, INPUT.as_bytes()].concat(),
+        );
+        let file = temp.open().unwrap();
+
+        assert!(file.is_mapped());
+        assert_eq!(file.as_str(), INPUT);
+        assert_eq!(texts(&file), vec!["Hällo", "Wörld"]);
+    }
+
+    #[test]
+    fn test_utf16() {
+        let le = TempFile::new(
+            "utf16le.srt",
+            &utf16(Encoding::UTF16LE_BOM, INPUT, u16::to_le_bytes),
+        );
+        let be = TempFile::new(
+            "utf16be.srt",
+            &utf16(Encoding::UTF16BE_BOM, INPUT, u16::to_be_bytes),
+        );
+
+        for (temp, encoding) in [(le, Encoding::Utf16Le), (be, Encoding::Utf16Be)] {
+            let file = temp.open().unwrap();
+
+            assert!(!file.is_mapped());
+            assert_eq!(file.encoding(), encoding);
+            assert_eq!(file.as_str(), INPUT);
+            assert_eq!(texts(&file), vec!["Hällo", "Wörld"]);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let temp = TempFile::new("empty.srt", b"");
+        let file = temp.open().unwrap();
+
+        assert_eq!(file.as_str(), "");
+        assert_eq!(file.subtitles().count(), 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let temp = TempFile::new("invalid-utf8.srt", b"\xEF\xBB\xBF1\n\xFF\n");
+        assert!(matches!(
+            temp.open(),
+            Err(SubtitleFileError::InvalidUtf8 { position: 5 })
+        ));
+
+        // an unpaired surrogate
+        let temp = TempFile::new("invalid-utf16.srt", b"\xFF\xFE1\x00\x00\xD8\n\x00");
+        assert!(matches!(
+            temp.open(),
+            Err(SubtitleFileError::InvalidUtf16 { position: 4 })
+        ));
+
+        // an odd number of bytes
+        let temp = TempFile::new("odd-utf16.srt", b"\xFE\xFF\x001\x00");
+        assert!(matches!(
+            temp.open(),
+            Err(SubtitleFileError::InvalidUtf16 { position: 4 })
+        ));
+
+        let path = std::env::temp_dir().join("libsrt-does-not-exist.srt");
+        assert!(matches!(
+            unsafe { SubtitleFile::open(path) },
+            Err(SubtitleFileError::Io(_))
+        ));
+    }
+}
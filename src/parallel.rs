@@ -0,0 +1,218 @@
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use crate::utils::{find_byte, Lines};
+use crate::{Subtitle, SubtitleError, SubtitleIterator};
+
+/// The minimum number of bytes, that are parsed by a single task.
+const MIN_CHUNK_LEN: usize = 16 * 1024;
+
+/// The subtitles of a chunk, that starts at a boundary.
+struct Chunk<'a> {
+    subtitles: Vec<Result<Subtitle<'a>, SubtitleError>>,
+    /// The index of the boundary, at which the sequential parser continues
+    /// after this chunk.
+    next: usize,
+}
+
+/// Parses the subtitles of the `input` on multiple threads.
+///
+/// The input is split into chunks at the blank lines between subtitles, which
+/// are parsed concurrently. The result is the same as collecting a
+/// [`SubtitleIterator`], including the errors of malformed subtitles and the
+/// spans, which are relative to the start of the `input`.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::{par_parse, SubtitleIterator};
+///
+/// let input =
+///     "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n";
+///
+/// assert_eq!(
+///     par_parse(input),
+///     SubtitleIterator::from(input).collect::<Vec<_>>()
+/// );
+/// ```
+#[must_use]
+pub fn par_parse(input: &str) -> Vec<Result<Subtitle<'_>, SubtitleError>> {
+    let chunk_len = input.len() / (rayon::current_num_threads() * 4);
+
+    par_parse_chunked(input, chunk_len.max(MIN_CHUNK_LEN))
+}
+
+fn par_parse_chunked(input: &str, chunk_len: usize) -> Vec<Result<Subtitle<'_>, SubtitleError>> {
+    let boundaries = boundaries(input, chunk_len);
+
+    let mut chunks = (0..boundaries.len())
+        .into_par_iter()
+        .map(|index| Some(parse_chunk(input, &boundaries, index)))
+        .collect::<Vec<_>>();
+
+    // the chunks, that the sequential parser would not start at, are skipped
+    let mut subtitles = Vec::new();
+    let mut index = 0;
+
+    while let Some(chunk) = chunks.get_mut(index).and_then(Option::take) {
+        subtitles.extend(chunk.subtitles);
+        index = chunk.next;
+    }
+
+    subtitles
+}
+
+/// Returns the byte offsets, at which the chunks start, which are roughly
+/// `chunk_len` bytes apart.
+fn boundaries(input: &str, chunk_len: usize) -> Vec<usize> {
+    let mut boundaries = Vec::from([0]);
+    let mut position = 0;
+
+    while let Some(boundary) = next_boundary(input, position + chunk_len) {
+        boundaries.push(boundary);
+        position = boundary;
+    }
+
+    boundaries
+}
+
+/// Returns the byte offset behind the first blank line after `position`, that
+/// follows a line with content, which is where the sequential parser usually
+/// expects the counter of the next subtitle.
+fn next_boundary(input: &str, position: usize) -> Option<usize> {
+    let line_start = position + find_byte(b'\n', input.get(position..)?)? + 1;
+
+    let mut lines = Lines::new_at(input, line_start);
+    // the line in front of the first one is unknown
+    let mut previous_is_empty = true;
+
+    while let Some(line) = lines.next() {
+        if line.is_empty() && !previous_is_empty {
+            return Some(lines.index()).filter(|index| *index < input.len());
+        }
+
+        previous_is_empty = line.is_empty();
+    }
+
+    None
+}
+
+/// Parses the subtitles from the boundary at `index`, until the parser reaches
+/// a later boundary in the state, in which it starts.
+///
+/// This is the state, in which the chunk of that boundary has been parsed, so
+/// the sequential parser would return the same subtitles from there on. If a
+/// malformed subtitle hides a boundary, the chunk continues to the next one.
+fn parse_chunk<'a>(input: &'a str, boundaries: &[usize], index: usize) -> Chunk<'a> {
+    let mut iterator = SubtitleIterator::new_at(input, boundaries[index]);
+    let mut subtitles = Vec::new();
+    let mut next = index + 1;
+
+    while let Some(subtitle) = iterator.next() {
+        subtitles.push(subtitle);
+
+        let Some(offset) = iterator.sync_point() else {
+            continue;
+        };
+
+        // skip the boundaries, that the parser has passed
+        next += boundaries[next..].partition_point(|boundary| *boundary < offset);
+
+        if boundaries.get(next) == Some(&offset) {
+            return Chunk { subtitles, next };
+        }
+    }
+
+    Chunk {
+        subtitles,
+        next: boundaries.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use pretty_assertions::assert_eq;
+
+    use crate::Buffer;
+    use crate::Timestamp;
+
+    const INPUTS: &[&str] = &[
+        "",
+        "\n\n\n",
+        "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n",
+        "1\r\n00:00:01,000 --> 00:00:02,000\r\nHello\r\n\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\nWorld\r\n\r\n",
+        // malformed subtitles, after which the parser expects the counter at
+        // a different line
+        "1\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n\n3\n00:00:05,000 --> 00:00:06,000\nBye\n",
+        "1\n00:00:01,000 --> 00:00:02,000\n\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n\n",
+        "one\n00:00:01,000 --> 00:00:02,000\nHello\n\n\n\n2\n00:00:03 --> 00:00:04\n\n3\nWorld\n\n",
+        "\n\n1\n00:00:01,000 --> 00:00:02,000\nHello\nthere\n\n \n\n2\n00:00:02,000 --> 00:00:02,000\nWorld",
+    ];
+
+    #[test]
+    fn test_par_parse() {
+        for input in INPUTS {
+            let expected = SubtitleIterator::from(*input).collect::<Vec<_>>();
+
+            assert_eq!(par_parse(input), expected);
+
+            // a boundary after every line
+            for chunk_len in 0..=input.len() {
+                assert_eq!(
+                    par_parse_chunked(input, chunk_len),
+                    expected,
+                    "{:?}",
+                    (input, chunk_len)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_par_parse_spans() {
+        let mut input = String::new();
+
+        for counter in 1..=1_000 {
+            writeln!(
+                input,
+                "{counter}\n{} --> {}\nLine {counter}\n",
+                Timestamp::from_millis(counter * 1_000),
+                Timestamp::from_millis(counter * 1_000 + 500)
+            )
+            .unwrap();
+        }
+
+        let boundaries = boundaries(&input, 1_000);
+        assert_eq!(boundaries.len(), 44);
+
+        // every chunk ends at the next boundary
+        for index in 0..boundaries.len() {
+            assert_eq!(parse_chunk(&input, &boundaries, index).next, index + 1);
+        }
+
+        let subtitles = par_parse_chunked(&input, 1_000);
+        assert_eq!(
+            subtitles,
+            SubtitleIterator::from(&*input).collect::<Vec<_>>()
+        );
+
+        for subtitle in subtitles {
+            let subtitle = subtitle.unwrap();
+            let span = subtitle.text().span().unwrap();
+
+            assert_eq!(&input[span], subtitle.text().as_raw());
+        }
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let input = "1\nA\n\n2\nB\n\n\n3\r\nC\r\n\r\n4\nD\n\n";
+
+        assert_eq!(boundaries(input, 0), vec![0, 5, 10, 19]);
+        assert_eq!(boundaries(input, 6), vec![0, 10]);
+        assert_eq!(boundaries(input, input.len()), vec![0]);
+    }
+}
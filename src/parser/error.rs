@@ -1,24 +1,23 @@
 use core::num::ParseIntError;
+use core::ops::Range;
 
-// TODO: implement fmt::Display for no_std, see https://github.com/dtolnay/thiserror/pull/64
-#[cfg(feature = "std")]
 use thiserror::Error;
 
 use crate::utils::{Span, Spanned};
 
-#[cfg_attr(feature = "std", derive(Error))]
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "std", error("{0}"))]
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{0}")]
 pub struct ParserError(Spanned<ParserErrorKind>);
 
-#[cfg_attr(feature = "std", derive(Error))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
 #[non_exhaustive]
 pub enum ParserErrorKind {
-    #[cfg_attr(feature = "std", error("{source}"))]
+    #[error("{source}")]
     ParseIntError { source: ParseIntError },
-    #[cfg_attr(feature = "std", error("invalid duration"))]
+    #[error("invalid duration")]
     InvalidDuration,
+    #[error("value out of range")]
+    OutOfRange,
 }
 
 impl ParserError {
@@ -34,9 +33,38 @@ impl ParserError {
         Self::new(ParserErrorKind::InvalidDuration, range)
     }
 
+    #[inline]
+    #[must_use]
+    pub(crate) fn out_of_range(range: impl Into<Span>) -> Self {
+        Self::new(ParserErrorKind::OutOfRange, range)
+    }
+
     #[inline]
     #[must_use]
     pub(crate) fn parse_int_error(source: ParseIntError, range: impl Into<Span>) -> Self {
         Self::new(ParserErrorKind::ParseIntError { source }, range)
     }
+
+    /// Returns the byte range in the input, at which the error occurred.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.0.span().map(|span| span.start()..span.end())
+    }
+
+    /// Replaces the span of the error.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub(crate) fn with_span(self, range: impl Into<Span>) -> Self { Self(self.0.with_span(range)) }
+
+    /// Moves the span of the error, after `removed` bytes in front of it have
+    /// been replaced with `inserted` bytes.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub(crate) fn shift(self, removed: usize, inserted: usize) -> Self {
+        Self(self.0.map_span(|span| {
+            span.map(|span| {
+                span.map(|start, end| (start + inserted - removed, end + inserted - removed))
+            })
+        }))
+    }
 }
@@ -7,6 +7,6 @@ mod state;
 
 pub use error::ParserError;
 pub use event::Event;
-pub(crate) use parse_duration::parse_duration;
+pub(crate) use parse_duration::{parse_duration, parse_timestamp, Notation};
 pub use parser::EventParser;
 pub(crate) use state::State;
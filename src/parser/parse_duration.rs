@@ -1,19 +1,62 @@
 use core::time::Duration;
 
 use super::ParserError;
+use crate::time::ParseMode;
 use crate::utils::Spanned;
 
-trait DurationExt {
-    #[inline]
-    #[must_use]
-    fn from_hours(hours: u64) -> Duration { Duration::from_mins(hours * 60) }
+/// The notation in which a timestamp is written.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum Notation {
+    /// `hours:minutes:seconds,milliseconds`
+    Srt,
+    /// `[hours:]minutes:seconds.milliseconds`
+    WebVtt,
+}
 
-    #[inline]
+impl Notation {
     #[must_use]
-    fn from_mins(mins: u64) -> Duration { Duration::from_secs(mins * 60) }
+    const fn separator(self) -> char {
+        match self {
+            Self::Srt => ',',
+            Self::WebVtt => '.',
+        }
+    }
 }
 
-impl DurationExt for Duration {}
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Field {
+    Hours,
+    Minutes,
+    Seconds,
+    Millis,
+}
+
+impl Field {
+    /// Parses the field and, in [`ParseMode::Strict`], verifies the number of
+    /// digits and the range of the value.
+    fn parse(self, string: Spanned<&str>, mode: ParseMode) -> Result<u64, ParserError> {
+        if mode == ParseMode::Strict {
+            let has_valid_digits = match self {
+                Self::Hours => string.len() >= 2,
+                Self::Minutes | Self::Seconds => string.len() == 2,
+                Self::Millis => string.len() == 3,
+            };
+
+            if !has_valid_digits || !string.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(ParserError::invalid_duration(string.range()));
+            }
+        }
+
+        let value = parse_u64(string)?;
+
+        if mode == ParseMode::Strict && matches!(self, Self::Minutes | Self::Seconds) && value >= 60
+        {
+            return Err(ParserError::out_of_range(string.range()));
+        }
+
+        Ok(value)
+    }
+}
 
 fn parse_u64(string: Spanned<&str>) -> Result<u64, ParserError> {
     string
@@ -21,23 +64,48 @@ fn parse_u64(string: Spanned<&str>) -> Result<u64, ParserError> {
         .map_err(|e| ParserError::parse_int_error(e, string.range()))
 }
 
-/// Parses a `String` of the following format:
-/// `hours:minutes:seconds,milliseconds`
-pub(crate) fn parse_duration(input: Spanned<&str>) -> Result<Duration, ParserError> {
-    if let [Some(hours), Some(minutes), Some(seconds_millis)] = input.split_at_most::<_, 3>(':') {
-        if let (seconds, Some(millis)) = seconds_millis.split_once(',') {
-            let mut result = Duration::from_secs(0);
+/// Parses an unsigned timestamp in the provided [`Notation`].
+pub(crate) fn parse_timestamp(
+    input: Spanned<&str>,
+    notation: Notation,
+    mode: ParseMode,
+) -> Result<Duration, ParserError> {
+    let (hours, minutes, seconds_millis) = {
+        match (notation, input.split_at_most::<_, 3>(':')) {
+            (_, [Some(hours), Some(minutes), Some(seconds_millis)]) => {
+                (Some(hours), minutes, seconds_millis)
+            }
+            // the hours are optional in WebVTT
+            (Notation::WebVtt, [Some(minutes), Some(seconds_millis), None]) => {
+                (None, minutes, seconds_millis)
+            }
+            _ => return Err(ParserError::invalid_duration(input.range())),
+        }
+    };
 
-            result += Duration::from_hours(parse_u64(hours)?);
-            result += Duration::from_mins(parse_u64(minutes)?);
-            result += Duration::from_secs(parse_u64(seconds)?);
-            result += Duration::from_millis(parse_u64(millis)?);
+    if let (seconds, Some(millis)) = seconds_millis.split_once(notation.separator()) {
+        let hours = hours.map_or(Ok(0), |hours| Field::Hours.parse(hours, mode))?;
+        let minutes = Field::Minutes.parse(minutes, mode)?;
+        let seconds = Field::Seconds.parse(seconds, mode)?;
+        let millis = Field::Millis.parse(millis, mode)?;
 
-            return Ok(result);
-        }
+        // in lenient mode the fields are unbounded, so a large value could overflow
+        return hours
+            .checked_mul(60)
+            .and_then(|total| total.checked_add(minutes))
+            .and_then(|total| total.checked_mul(60))
+            .and_then(|total| total.checked_add(seconds))
+            .and_then(|total| Duration::from_secs(total).checked_add(Duration::from_millis(millis)))
+            .ok_or_else(|| ParserError::out_of_range(input.range()));
     }
 
-    Err(ParserError::invalid_duration(0..input.len()))
+    Err(ParserError::invalid_duration(input.range()))
+}
+
+/// Parses a `String` of the following format:
+/// `hours:minutes:seconds,milliseconds`
+pub(crate) fn parse_duration(input: Spanned<&str>) -> Result<Duration, ParserError> {
+    parse_timestamp(input, Notation::Srt, ParseMode::Lenient)
 }
 
 #[cfg(test)]
@@ -72,4 +140,102 @@ mod tests {
                 + Duration::from_millis(999))
         );
     }
+
+    #[test]
+    fn test_parse_duration_overflow() {
+        assert_eq!(
+            parse_duration(Spanned::new("99999999999999999:00:00,000")),
+            Err(ParserError::out_of_range(0..27))
+        );
+        assert_eq!(
+            parse_duration(Spanned::new("00:999999999999999999:00,000")),
+            Err(ParserError::out_of_range(0..28))
+        );
+        assert_eq!(
+            parse_duration(Spanned::new("00:00:18446744073709551615,1000")),
+            Err(ParserError::out_of_range(0..31))
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_strict() {
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("123:59:59,999"),
+                Notation::Srt,
+                ParseMode::Strict
+            ),
+            Ok(Duration::from_hours(123)
+                + Duration::from_mins(59)
+                + Duration::from_secs(59)
+                + Duration::from_millis(999))
+        );
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("999:99:99,999"),
+                Notation::Srt,
+                ParseMode::Strict
+            ),
+            Err(ParserError::out_of_range(4..6))
+        );
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("00:00:60,000"),
+                Notation::Srt,
+                ParseMode::Strict
+            ),
+            Err(ParserError::out_of_range(6..8))
+        );
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("00:00:11,00"),
+                Notation::Srt,
+                ParseMode::Strict
+            ),
+            Err(ParserError::invalid_duration(9..11))
+        );
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("0:00:11,000"),
+                Notation::Srt,
+                ParseMode::Strict
+            ),
+            Err(ParserError::invalid_duration(0..1))
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp_webvtt() {
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("01:02:03.004"),
+                Notation::WebVtt,
+                ParseMode::Strict
+            ),
+            Ok(Duration::from_hours(1)
+                + Duration::from_mins(2)
+                + Duration::from_secs(3)
+                + Duration::from_millis(4))
+        );
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("02:03.004"),
+                Notation::WebVtt,
+                ParseMode::Strict
+            ),
+            Ok(Duration::from_mins(2) + Duration::from_secs(3) + Duration::from_millis(4))
+        );
+        assert_eq!(
+            parse_timestamp(Spanned::new("02:03.004"), Notation::Srt, ParseMode::Lenient),
+            Err(ParserError::invalid_duration(0..9))
+        );
+        assert_eq!(
+            parse_timestamp(
+                Spanned::new("00:02:03,004"),
+                Notation::WebVtt,
+                ParseMode::Lenient
+            ),
+            Err(ParserError::invalid_duration(0..12))
+        );
+    }
 }
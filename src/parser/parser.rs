@@ -1,17 +1,17 @@
 use core::str::FromStr;
 
 use super::{parse_duration, Event, ParserError, State};
-use crate::utils::{Lines, Span};
+use crate::utils::{Arrow, Lines, Span};
 
 /// The [`EventParser`] parses each line in an `srt`-file and returns an
 /// [`Event`] for it.
+#[derive(Debug)]
 pub struct EventParser<'a> {
     lines: Lines<'a>,
     state: State,
 }
 
 impl<'a> From<&'a str> for EventParser<'a> {
-    #[must_use]
     fn from(value: &'a str) -> Self {
         Self {
             lines: Lines::new(value),
@@ -20,6 +20,32 @@ impl<'a> From<&'a str> for EventParser<'a> {
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<'a> EventParser<'a> {
+    /// Creates a parser for `input[offset..]`, whose spans are relative to the
+    /// start of the `input`.
+    pub(crate) const fn new_at(input: &'a str, offset: usize) -> Self {
+        Self {
+            lines: Lines::new_at(input, offset),
+            state: State::Counter,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EventParser<'_> {
+    /// Returns the byte offset in the input, at which the next event starts.
+    #[inline]
+    #[must_use]
+    pub const fn offset(&self) -> usize { self.lines.index() }
+
+    /// Returns `true` if the next line is expected to be the counter of a new
+    /// cue, so that the parser does not depend on anything in front of it.
+    #[inline]
+    #[must_use]
+    pub(crate) const fn expects_counter(&self) -> bool { matches!(self.state, State::Counter) }
+}
+
 impl<'a> Iterator for EventParser<'a> {
     type Item = Result<Event<'a>, ParserError>;
 
@@ -55,7 +81,7 @@ impl<'a> Iterator for EventParser<'a> {
                 let line = self.lines.next()?;
                 // TODO: maybe this also needs to split for " -> " (I saw this in some files)
 
-                if let (start_string, Some(end_string)) = line.split_once(" --> ") {
+                if let (start_string, Some(end_string)) = line.split_once(Arrow) {
                     let start = match parse_duration(start_string) {
                         Ok(v) => v,
                         Err(e) => return Some(Err(e)),
@@ -75,12 +101,12 @@ impl<'a> Iterator for EventParser<'a> {
                 self.state = State::Empty;
                 let mut range_end = None;
 
-                while let Some(line) = self.lines.next() {
+                for line in self.lines.by_ref() {
                     if line.is_empty() {
                         break;
-                    } else {
-                        range_end = line.span().map(|span| span.end());
                     }
+
+                    range_end = line.span().map(Span::end);
                 }
 
                 // TODO: is the range correct?
@@ -8,7 +8,7 @@ pub(crate) enum State {
 
 impl State {
     #[inline]
-    pub fn update(&mut self) {
+    pub const fn update(&mut self) {
         match self {
             Self::Counter => *self = Self::Duration,
             Self::Duration => *self = Self::Text,
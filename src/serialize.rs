@@ -4,17 +4,61 @@ use super::buffer::Buffer;
 
 /// Trait alias that contains the bare minimum of constraints that an error
 /// should satisfy
-pub trait BasicError = fmt::Display + Sized + Send + Sync + 'static;
+pub trait BasicError: fmt::Display + Sized + Send + Sync + 'static {}
+
+impl<T: fmt::Display + Sized + Send + Sync + 'static> BasicError for T {}
 
 pub trait Serialize<B: Buffer> {
     type Error: BasicError + From<B::Error>;
 
+    /// Writes the value into the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer returns an error.
     fn serialize(&self, buffer: B) -> Result<(), Self::Error>;
 }
 
-// TODO: implement? and use with specialization
+/// Computes the exact number of bytes, that [`Serialize`] writes with the
+/// default configuration.
+///
+/// The serializers reserve this length once up front, so that a growing
+/// buffer never has to reallocate.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::{ArrayBuffer, ArrayBufferError, ExactSerializedLength, Serialize, Timestamp};
+///
+/// let timestamp = Timestamp::from_millis(-3_723_004);
+///
+/// let mut buffer = ArrayBuffer::<32>::new();
+/// timestamp.serialize(&mut buffer)?;
+///
+/// assert_eq!(buffer.as_str(), "-01:02:03,004");
+/// assert_eq!(timestamp.serialized_len(), buffer.len());
+/// # Ok::<(), ArrayBufferError>(())
+/// ```
 pub trait ExactSerializedLength {
-    fn len(&self) -> usize;
+    /// Returns the number of bytes of the serialized output.
+    fn serialized_len(&self) -> usize;
+}
+
+impl<T: ExactSerializedLength + ?Sized> ExactSerializedLength for &T {
+    #[inline]
+    fn serialized_len(&self) -> usize { (**self).serialized_len() }
+}
+
+/// Returns the number of decimal digits of the value.
+pub(crate) const fn decimal_digits(mut value: u128) -> usize {
+    let mut digits = 1;
+
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+
+    digits
 }
 
 pub trait SerializeWithConfig<B: Buffer> {
@@ -22,19 +66,37 @@ pub trait SerializeWithConfig<B: Buffer> {
     type Config: Default + ?Sized;
     type Error: BasicError + From<B::Error>;
 
+    /// Writes the value into the buffer, formatted as described by the
+    /// config.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the buffer returns an error.
     fn serialize_with_config(&self, buffer: B, config: &Self::Config) -> Result<(), Self::Error>;
 }
 
 #[cfg(feature = "alloc")]
 mod serialize_to_string {
-    use super::*;
+    use super::{Serialize, SerializeWithConfig};
     use alloc::string::String;
 
-    pub trait SerializeToString: Serialize<String> {
+    pub trait SerializeToString {
+        type Error;
+
+        /// Serializes the value into a new `String`.
+        ///
+        /// # Errors
+        ///
+        /// Fails if the value could not be formatted.
         fn serialize_to_string(&self) -> Result<String, Self::Error>;
     }
 
-    impl<S: Serialize<String>> SerializeToString for S {
+    impl<S, E> SerializeToString for S
+    where
+        S: ?Sized + for<'a> Serialize<&'a mut String, Error = E>,
+    {
+        type Error = E;
+
         fn serialize_to_string(&self) -> Result<String, Self::Error> {
             let mut buffer = String::new();
 
@@ -44,14 +106,30 @@ mod serialize_to_string {
         }
     }
 
-    pub trait SerializeWithConfigToString: SerializeWithConfig<String> {
+    pub trait SerializeWithConfigToString {
+        type Config: ?Sized;
+        type Error;
+
+        /// Serializes the value into a new `String`, formatted as described
+        /// by the config.
+        ///
+        /// # Errors
+        ///
+        /// Fails if the value could not be formatted.
         fn serialize_with_config_to_string(
             &self,
             config: &Self::Config,
         ) -> Result<String, Self::Error>;
     }
 
-    impl<S: SerializeWithConfig<String>> SerializeWithConfigToString for S {
+    impl<S, C, E> SerializeWithConfigToString for S
+    where
+        S: ?Sized + for<'a> SerializeWithConfig<&'a mut String, Config = C, Error = E>,
+        C: Default,
+    {
+        type Config = C;
+        type Error = E;
+
         fn serialize_with_config_to_string(
             &self,
             config: &Self::Config,
@@ -66,4 +144,65 @@ mod serialize_to_string {
 }
 
 #[cfg(feature = "alloc")]
-use serialize_to_string::*;
+pub use serialize_to_string::{SerializeToString, SerializeWithConfigToString};
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Counts the written bytes and remembers the first reservation, which is
+    /// the one of the outermost serializer.
+    #[derive(Debug, Default)]
+    pub(crate) struct CountingBuffer {
+        reserved: Option<usize>,
+        written: usize,
+    }
+
+    impl CountingBuffer {
+        pub(crate) const fn written(&self) -> usize { self.written }
+    }
+
+    impl Buffer for CountingBuffer {
+        type Error = fmt::Error;
+
+        fn reserve(&mut self, n: usize) -> Result<(), Self::Error> {
+            self.reserved.get_or_insert(n);
+
+            Ok(())
+        }
+
+        fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+            self.write_str(c.encode_utf8(&mut [0; 4]))
+        }
+
+        fn write_str(&mut self, string: &str) -> Result<(), Self::Error> {
+            self.written += string.len();
+
+            Ok(())
+        }
+    }
+
+    /// Asserts, that the value reserves its exact length once, before
+    /// anything is written, and that it writes exactly that many bytes.
+    pub(crate) fn assert_exact_length<T>(value: &T)
+    where
+        T: ?Sized + ExactSerializedLength + for<'a> Serialize<&'a mut CountingBuffer>,
+    {
+        let mut buffer = CountingBuffer::default();
+
+        assert!(value.serialize(&mut buffer).is_ok());
+        assert_eq!(buffer.written, value.serialized_len());
+        assert_eq!(buffer.reserved, Some(buffer.written));
+    }
+
+    #[test]
+    fn test_decimal_digits() {
+        assert_eq!(decimal_digits(0), 1);
+        assert_eq!(decimal_digits(9), 1);
+        assert_eq!(decimal_digits(10), 2);
+        assert_eq!(decimal_digits(999), 3);
+        assert_eq!(decimal_digits(1_000), 4);
+        assert_eq!(decimal_digits(u128::MAX), 39);
+    }
+}
@@ -3,43 +3,39 @@ use core::str;
 use core::time::Duration;
 
 use crate::parser::ParserError;
+use crate::serialize::decimal_digits;
 use crate::subtitle_iterator::SubtitleIterator;
 use crate::text::Text;
+use crate::time::Timestamp;
+use crate::{Buffer, ExactSerializedLength, Serialize};
 
-// TODO: implement fmt::Display for no_std, see https://github.com/dtolnay/thiserror/pull/64
-#[cfg(feature = "std")]
 use thiserror::Error;
 
-#[cfg_attr(feature = "std", derive(Error))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
 #[non_exhaustive]
 pub enum InitError {
-    #[cfg_attr(feature = "std", error("subtitle text is empty (\"\")"))]
+    #[error("subtitle text is empty (\"\")")]
     MissingSubtitleText,
-    #[cfg_attr(feature = "std", error("duration should not be `0s`"))]
+    #[error("duration should not be `0s`")]
     ZeroDuration,
 }
 
-#[cfg_attr(feature = "std", derive(Error))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
 #[non_exhaustive]
 pub enum SubtitleError {
-    #[cfg_attr(feature = "std", error("{0}"))]
+    #[error("{0}")]
     Parser(ParserError),
-    #[cfg_attr(feature = "std", error("{0}"))]
+    #[error("{0}")]
     Init(InitError),
-    #[cfg_attr(feature = "std", error("missing duration"))]
+    #[error("missing duration")]
     MissingDuration,
-    #[cfg_attr(feature = "std", error("missing text"))]
+    #[error("missing text")]
     MissingText,
-    #[cfg_attr(feature = "std", error("missing counter"))]
+    #[error("missing counter")]
     MissingCounter,
-    #[cfg_attr(feature = "std", error("empty string"))]
+    #[error("empty string")]
     EmptyString,
-    #[cfg_attr(
-        feature = "std",
-        error("encountered multiple subtitle with the same counter")
-    )]
+    #[error("encountered multiple subtitle with the same counter")]
     DuplicateEntry,
 }
 
@@ -54,7 +50,7 @@ impl From<InitError> for SubtitleError {
 }
 
 /// Initializes a [`Subtitle`].
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct SubtitleInit<'a> {
     pub counter: usize,
     pub start: Duration,
@@ -73,6 +69,10 @@ impl<'a> SubtitleInit<'a> {
     /// The field [`SubtitleInit::duration`] must not be `0s` and
     /// [`SubtitleInit::text`] should not be `""`.
     ///
+    /// # Errors
+    ///
+    /// Fails if the duration is zero or if the text is empty.
+    ///
     /// # Examples
     ///
     /// ```
@@ -130,7 +130,7 @@ impl<'a> SubtitleInit<'a> {
     pub fn init(self) -> Result<Subtitle<'a>, InitError> {
         if self.duration == Duration::default() {
             return Err(InitError::ZeroDuration);
-        } else if self.text == "" {
+        } else if self.text.is_empty() {
             return Err(InitError::MissingSubtitleText);
         }
 
@@ -156,7 +156,7 @@ impl Default for SubtitleInit<'_> {
 }
 
 /// This struct represents a `Subtitle` for a `SubRipTitle`.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Subtitle<'a> {
     counter: usize,
     start: Duration,
@@ -165,6 +165,21 @@ pub struct Subtitle<'a> {
 }
 
 impl<'a> Subtitle<'a> {
+    /// Creates a subtitle without verifying the fields, which is only done by
+    /// code that already upholds the invariants of [`SubtitleInit::init`].
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub(crate) fn new(counter: usize, start: Duration, duration: Duration, text: Text<'a>) -> Self {
+        debug_assert!(duration != Duration::default() && !text.is_empty());
+
+        Self {
+            counter,
+            start,
+            duration,
+            text,
+        }
+    }
+
     /// Each subtitle has a counter associated with it that is returned by this
     /// function.
     ///
@@ -185,6 +200,76 @@ impl<'a> Subtitle<'a> {
     #[inline]
     #[must_use]
     pub const fn start(&self) -> Duration { self.start }
+
+    /// Returns the point in time at which the subtitle disappears.
+    #[inline]
+    #[must_use]
+    pub fn end(&self) -> Duration { self.start + self.duration }
+
+    /// Returns for how long the subtitle is shown.
+    #[inline]
+    #[must_use]
+    pub const fn duration(&self) -> Duration { self.duration }
+}
+
+impl ExactSerializedLength for Subtitle<'_> {
+    fn serialized_len(&self) -> usize {
+        // "{counter}\nHH:MM:SS,mmm --> HH:MM:SS,mmm\n{text}\n"
+        decimal_digits(self.counter as u128)
+            + 1
+            + Timestamp::from(self.start).serialized_len()
+            + " --> ".len()
+            + Timestamp::from(self.end()).serialized_len()
+            + 1
+            + self.text.serialized_len()
+            + 1
+    }
+}
+
+impl<B: Buffer> Serialize<B> for Subtitle<'_> {
+    type Error = B::Error;
+
+    fn serialize(&self, mut buffer: B) -> Result<(), Self::Error> {
+        buffer.reserve(self.serialized_len())?;
+
+        writeln!(buffer, "{}", self.counter)?;
+        Timestamp::from(self.start).serialize(&mut buffer)?;
+        buffer.write_str(" --> ")?;
+        Timestamp::from(self.end()).serialize(&mut buffer)?;
+        buffer.write_char('\n')?;
+        self.text.serialize(&mut buffer)?;
+        buffer.write_char('\n')?;
+
+        Ok(())
+    }
+}
+
+impl ExactSerializedLength for [Subtitle<'_>] {
+    fn serialized_len(&self) -> usize {
+        let separators = self.len().saturating_sub(1);
+
+        self.iter().map(Subtitle::serialized_len).sum::<usize>() + separators
+    }
+}
+
+/// Serializes the subtitles as a `.srt` file, with an empty line between each
+/// subtitle.
+impl<B: Buffer> Serialize<B> for [Subtitle<'_>] {
+    type Error = B::Error;
+
+    fn serialize(&self, mut buffer: B) -> Result<(), Self::Error> {
+        buffer.reserve(self.serialized_len())?;
+
+        for (index, subtitle) in self.iter().enumerate() {
+            if index > 0 {
+                buffer.write_char('\n')?;
+            }
+
+            subtitle.serialize(&mut buffer)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<'a> TryFrom<&'a str> for Subtitle<'a> {
@@ -194,13 +279,17 @@ impl<'a> TryFrom<&'a str> for Subtitle<'a> {
         SubtitleIterator::from(input)
             .next()
             .ok_or(SubtitleError::EmptyString)
-            .flatten()
+            .and_then(|result| result)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::serialize::tests::assert_exact_length;
+    #[cfg(feature = "alloc")]
+    use crate::serialize::SerializeToString;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -218,8 +307,8 @@ mod tests {
             )),
             Ok(SubtitleInit {
                 counter: 1,
-                start: Duration::from_secs_f32(1.6),
-                duration: Duration::from_secs_f32(4.2 - 1.6),
+                start: Duration::from_secs_f64(1.6),
+                duration: Duration::from_secs_f64(4.2 - 1.6),
                 text: ("English (US)", 32..).into(),
                 ..SubtitleInit::default()
             }
@@ -261,4 +350,74 @@ mod tests {
             .unwrap())
         );
     }
+
+    #[test]
+    fn test_end_before_start() {
+        assert_eq!(
+            Subtitle::try_from("1\n00:00:02,000 --> 00:00:01,000\nText\n"),
+            Err(SubtitleError::Init(InitError::ZeroDuration))
+        );
+    }
+
+    #[test]
+    fn test_end() {
+        let subtitle = Subtitle::try_from(concat!(
+            "1\n",
+            "00:00:01,600 --> 00:00:04,200\n",
+            "English (US)\n",
+        ))
+        .unwrap();
+
+        assert_eq!(subtitle.end(), Duration::from_millis(4200));
+        assert_eq!(subtitle.duration(), Duration::from_millis(2600));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_serialize() {
+        let input = concat!(
+            "1\n",
+            "00:00:01,600 --> 00:00:04,200\n",
+            "English (US)\n",
+            "\n",
+            "2\n",
+            "00:00:05,900 --> 00:01:07,999\n",
+            "This is a subtitle\n",
+            "in American English\n",
+        );
+        let subtitles = SubtitleIterator::from(input)
+            .collect::<Result<alloc::vec::Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            subtitles[0].serialize_to_string().unwrap(),
+            concat!("1\n", "00:00:01,600 --> 00:00:04,200\n", "English (US)\n")
+        );
+        assert_eq!(subtitles[..].serialize_to_string().unwrap(), input);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_serialized_len() {
+        let input = concat!(
+            "1\n",
+            "00:00:01,600 --> 00:00:04,200\n",
+            "English (US)\n",
+            "\n",
+            "1234\n",
+            "100:00:05,900 --> 100:01:07,999\n",
+            "<i>Ünïcödé</i>\n",
+        );
+        let subtitles = SubtitleIterator::from(input)
+            .collect::<Result<alloc::vec::Vec<_>, _>>()
+            .unwrap();
+
+        for subtitle in &subtitles {
+            assert_exact_length(subtitle);
+        }
+
+        assert_exact_length(&subtitles[..]);
+        assert_exact_length(&subtitles[..0]);
+        assert_eq!(subtitles[..].serialized_len(), input.len());
+    }
 }
@@ -1,6 +1,25 @@
 use crate::parser::{Event, EventParser};
 use crate::{Subtitle, SubtitleError, SubtitleInit};
 
+/// An iterator over the [`Subtitle`]s of an `.srt` file.
+///
+/// The subtitles borrow from the input and parsing does not allocate, so the
+/// iterator is available without the `alloc` and `std` features.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::{ArrayBuffer, Buffer, SubtitleIterator};
+///
+/// let input = "1\n00:00:01,000 --> 00:00:0x,000\nHello\n\n";
+/// let error = SubtitleIterator::from(input).next().unwrap().unwrap_err();
+///
+/// let mut buffer = ArrayBuffer::<64>::new();
+/// write!(buffer, "{}", error)?;
+/// assert_eq!(buffer.as_str(), "invalid digit found in string");
+/// # Ok::<(), libsrt::ArrayBufferError>(())
+/// ```
+#[derive(Debug)]
 #[must_use]
 pub struct SubtitleIterator<'a> {
     parser: EventParser<'a>,
@@ -14,6 +33,27 @@ impl<'a> From<&'a str> for SubtitleIterator<'a> {
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<'a> SubtitleIterator<'a> {
+    /// Creates an iterator over the subtitles in `input[offset..]`, whose
+    /// spans are relative to the start of the `input`.
+    pub(crate) const fn new_at(input: &'a str, offset: usize) -> Self {
+        Self {
+            parser: EventParser::new_at(input, offset),
+        }
+    }
+
+    /// Returns the byte offset in the input, at which the next subtitle
+    /// starts, if parsing it does not depend on anything in front of it.
+    pub(crate) const fn sync_point(&self) -> Option<usize> {
+        if self.parser.expects_counter() {
+            Some(self.parser.offset())
+        } else {
+            None
+        }
+    }
+}
+
 impl<'a> Iterator for SubtitleIterator<'a> {
     type Item = Result<Subtitle<'a>, SubtitleError>;
 
@@ -22,7 +62,7 @@ impl<'a> Iterator for SubtitleIterator<'a> {
         let mut duration = None;
         let mut lines = None;
 
-        while let Some(event) = self.parser.next() {
+        for event in self.parser.by_ref() {
             // TODO: replace with something smarter
             let event = {
                 match event {
@@ -50,24 +90,22 @@ impl<'a> Iterator for SubtitleIterator<'a> {
                         let subtitle = SubtitleInit {
                             counter,
                             start,
-                            duration: end - start,
-                            text: text.into(),
+                            // a subtitle, that ends before it starts, has no duration
+                            duration: end.saturating_sub(start),
+                            text,
                             ..SubtitleInit::default()
                         }
                         .init();
 
                         // TODO: simplify
-                        return subtitle
-                            .map(|value| Some(value))
-                            .map_err(|error| SubtitleError::Init(error))
-                            .transpose();
+                        return subtitle.map(Some).map_err(SubtitleError::Init).transpose();
                     } else if counter.is_some() {
                         return Some(Err(SubtitleError::MissingCounter));
                     } else if duration.is_some() {
                         return Some(Err(SubtitleError::MissingDuration));
-                    } else {
-                        return Some(Err(SubtitleError::MissingText));
                     }
+
+                    return Some(Err(SubtitleError::MissingText));
                 }
             }
         }
@@ -2,7 +2,7 @@ use core::convert::TryFrom;
 
 use super::ParseAttributeError;
 use crate::utils::Spanned;
-use crate::{Buffer, Serialize};
+use crate::{Buffer, ExactSerializedLength, Serialize};
 
 // https://www.w3.org/community/webed/wiki/HTML/Training/Tag_syntax
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -13,7 +13,6 @@ pub struct Attribute<'a> {
 
 impl<'a> Attribute<'a> {
     // TODO: do not ignore doc examples
-    // TODO: must_use attribute
     /// Creates a new [`Attribute`] from the provided `name` and its associated
     /// `value`.
     ///
@@ -24,7 +23,8 @@ impl<'a> Attribute<'a> {
     ///
     /// let attribute = Attribute::new("color", Some("rgb(12, 12, 22)"));
     /// ```
-    pub fn new(name: &'a str, value: Option<&'a str>) -> Self { Self { name, value } }
+    #[must_use]
+    pub const fn new(name: &'a str, value: Option<&'a str>) -> Self { Self { name, value } }
 
     /// Returns the `name` of the `Attribute`.
     ///
@@ -38,7 +38,7 @@ impl<'a> Attribute<'a> {
     /// assert_eq!(attribute.name(), "color");
     /// ```
     #[must_use]
-    pub fn name(&self) -> &str { &self.name }
+    pub const fn name(&self) -> &str { self.name }
 
     /// Returns the `value` of the `Attribute`.
     ///
@@ -52,7 +52,7 @@ impl<'a> Attribute<'a> {
     /// assert_eq!(attribute.value(), Some("#AABBCC"));
     /// ```
     #[must_use]
-    pub fn value(&self) -> Option<&str> { self.value.as_deref() }
+    pub const fn value(&self) -> Option<&str> { self.value }
 
     // This function is for `Attributes::try_from(&str)`
     pub(crate) fn parse(
@@ -64,8 +64,8 @@ impl<'a> Attribute<'a> {
             Ok((
                 name,
                 Attribute {
-                    name: name.into_inner().into(),
-                    value: Some(remove_quotes(value)?.into_inner().into()),
+                    name: name.into_inner(),
+                    value: Some(remove_quotes(value)?.into_inner()),
                 },
             ))
         } else {
@@ -74,7 +74,7 @@ impl<'a> Attribute<'a> {
             Ok((
                 name,
                 Attribute {
-                    name: name.into_inner().into(),
+                    name: name.into_inner(),
                     value: None,
                 },
             ))
@@ -128,45 +128,58 @@ impl<'a> From<(&'a str, Option<&'a str>)> for Attribute<'a> {
     fn from(value: (&'a str, Option<&'a str>)) -> Self { Self::new(value.0, value.1) }
 }
 
-impl<'a, B: Buffer> Serialize<B> for Attribute<'a> {
+/// Returns the quotes, that are needed around the value, if any.
+fn quotes(value: &str) -> Option<char> {
+    // > In HTML, the attribute value can remain unquoted
+    // > if it doesn't contain spaces or any of the following
+    // > characters: " ' ` = < or >. Otherwise, it has to be
+    // > quoted using either single or double quotes.
+    //
+    // https://www.w3.org/community/webed/wiki/HTML/Training/Tag_syntax
+    if value.contains(' ')
+        || value.contains('\'')
+        || value.contains('`')
+        || value.contains('=')
+        || value.contains('<')
+        || value.contains('>')
+    {
+        if value.contains('"') {
+            Some('\'')
+        } else {
+            Some('"')
+        }
+    } else if value.contains('"') {
+        // if the value contains a double quote,
+        // it has to be escaped with single quotes
+        Some('\'')
+    } else {
+        None
+    }
+}
+
+impl ExactSerializedLength for Attribute<'_> {
+    fn serialized_len(&self) -> usize {
+        self.name.len()
+            + self.value.map_or(0, |value| {
+                let quotes = quotes(value).map_or(0, |c| c.len_utf8() * 2);
+
+                '='.len_utf8() + quotes + value.len()
+            })
+    }
+}
+
+impl<B: Buffer> Serialize<B> for Attribute<'_> {
     type Error = B::Error;
 
     fn serialize(&self, mut buffer: B) -> Result<(), Self::Error> {
-        buffer.write_str(&self.name)?;
-
-        if let Some(value) = &self.value {
-            let mut needs_quotes = None;
-
-            // > In HTML, the attribute value can remain unquoted
-            // > if it doesn't contain spaces or any of the following
-            // > characters: " ' ` = < or >. Otherwise, it has to be
-            // > quoted using either single or double quotes.
-            //
-            // https://www.w3.org/community/webed/wiki/HTML/Training/Tag_syntax
-            if value.contains(' ')
-                || value.contains('\'')
-                || value.contains('`')
-                || value.contains('=')
-                || value.contains('<')
-                || value.contains('>')
-            {
-                if value.contains('"') {
-                    needs_quotes = Some('\'');
-                } else {
-                    needs_quotes = Some('"');
-                }
-            } else if value.contains('"') {
-                // if the value contains a double quote,
-                // it has to be escaped with single quotes
-                needs_quotes = Some('\'');
-            }
+        buffer.reserve(self.serialized_len())?;
+        buffer.write_str(self.name)?;
 
-            if let Some(c) = needs_quotes {
-                buffer.reserve('='.len_utf8() + c.len_utf8() * 2 + value.len())?;
-                write!(buffer, "={}{}{}", c, value, c)?;
+        if let Some(value) = self.value {
+            if let Some(c) = quotes(value) {
+                write!(buffer, "={c}{value}{c}")?;
             } else {
-                buffer.reserve('='.len_utf8() + value.len())?;
-                write!(buffer, "={}", value)?;
+                write!(buffer, "={value}")?;
             }
         }
 
@@ -229,4 +242,18 @@ mod tests {
             Ok("key=' \" ` = < or >'".to_string())
         );
     }
+
+    #[test]
+    fn test_serialized_len() {
+        for attribute in [
+            Attribute::new("key", None),
+            Attribute::new("key", Some("value")),
+            Attribute::new("key", Some("välue")),
+            Attribute::new("key", Some(" ' ` = < or >")),
+            Attribute::new("key", Some(" \" ` = < or >")),
+            Attribute::new("key", Some("\"")),
+        ] {
+            crate::serialize::tests::assert_exact_length(&attribute);
+        }
+    }
 }
@@ -1,7 +1,5 @@
-use core::convert::TryFrom;
-
 use super::{Attribute, ParseAttributeError};
-use crate::utils::{Spanned, SplitIter};
+use crate::utils::Spanned;
 
 // what it could look like:
 // Attributes<Spanned<&'a str>>
@@ -14,27 +12,115 @@ use crate::utils::{Spanned, SplitIter};
 pub struct Attributes<T>(T);
 
 impl<'a> Attributes<Spanned<&'a str>> {
+    #[must_use]
     pub fn new_spanned(string: &'a str, start: usize) -> Self {
         Self(Spanned::new(string).with_span(start..start + string.len()))
     }
 
-    pub fn attributes<'b>(&'b self) -> LazyAttributesIter<'b> {
-        //
-        LazyAttributesIter::new(self.0)
-    }
+    /// Returns an iterator over the attributes, like `color` and `face` in
+    /// `color=red face="Times New Roman"`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::text::{Attribute, Attributes};
+    ///
+    /// let attributes = Attributes::new_spanned("color=red face=\"Times New Roman\" bold", 6);
+    ///
+    /// assert_eq!(
+    ///     attributes.attributes().collect::<Result<Vec<_>, _>>(),
+    ///     Ok(vec![
+    ///         Attribute::new("color", Some("red")),
+    ///         Attribute::new("face", Some("Times New Roman")),
+    ///         Attribute::new("bold", None),
+    ///     ])
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn attributes(&self) -> LazyAttributesIter<'a> { LazyAttributesIter::new(self.0) }
 }
 
 impl<T> From<T> for Attributes<T> {
     fn from(value: T) -> Self { Self(value) }
 }
 
-#[derive(Debug)]
-pub struct LazyAttributesIter<'a>(SplitIter<'a, char>);
+/// An iterator over the attributes of a tag, which are separated by
+/// whitespace.
+///
+/// The value of an attribute is either quoted with single or double quotes,
+/// which allows it to contain whitespace, or it ends at the next whitespace.
+/// Whitespace around the `=` is ignored.
+#[derive(Debug, Clone)]
+pub struct LazyAttributesIter<'a> {
+    /// The part of the attributes, that has not been parsed yet.
+    remaining: Spanned<&'a str>,
+}
+
+/// The name and the value of an attribute.
+type SpannedAttribute<'a> = (Spanned<&'a str>, Option<Spanned<&'a str>>);
 
 impl<'a> LazyAttributesIter<'a> {
-    pub(crate) fn new(value: Spanned<&'a str>) -> Self {
-        // TODO: shouldnt you split at a ,?
-        Self(value.split('='))
+    pub(crate) const fn new(value: Spanned<&'a str>) -> Self { Self { remaining: value } }
+
+    /// Stops the iterator after an error, from which it can not recover.
+    fn finish(&mut self) {
+        self.remaining = self
+            .remaining
+            .get(self.remaining.len()..)
+            .unwrap_or(self.remaining);
+    }
+
+    /// Returns the name and the value of the next attribute, whose spans are
+    /// the spans of the string, the iterator has been created with.
+    pub(crate) fn next_spanned(
+        &mut self,
+    ) -> Option<Result<SpannedAttribute<'a>, ParseAttributeError>> {
+        let whitespace = char::is_whitespace as fn(char) -> bool;
+        let remaining = self.remaining.trim_start_matches(whitespace);
+
+        if remaining.is_empty() {
+            self.remaining = remaining;
+            return None;
+        }
+
+        let name_end = remaining
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(remaining.len());
+        let name = remaining.get(..name_end)?;
+        let rest = remaining.get(name_end..)?.trim_start_matches(whitespace);
+
+        if !rest.starts_with('=') {
+            // the value, along with the `=`, can be omitted
+            self.remaining = rest;
+            return Some(Ok((name, None)));
+        }
+
+        let value_start = rest.get(1..)?.trim_start_matches(whitespace);
+        let (value, rest) = if let Some(quote @ ('"' | '\'')) = value_start.chars().next() {
+            // a quoted value ends at the next quote of the same kind
+            let Some(end) = value_start[1..].find(quote) else {
+                self.finish();
+                return Some(Err(ParseAttributeError::invalid_quote(value_start.range())));
+            };
+
+            (value_start.get(1..=end)?, value_start.get(end + 2..)?)
+        } else {
+            let end = value_start
+                .find(char::is_whitespace)
+                .unwrap_or(value_start.len());
+
+            (value_start.get(..end)?, value_start.get(end..)?)
+        };
+
+        self.remaining = rest;
+
+        if name.is_empty() {
+            return Some(Err(ParseAttributeError::missing_name(
+                remaining.get(..1)?.range(),
+            )));
+        }
+
+        Some(Ok((name, Some(value))))
     }
 }
 
@@ -42,8 +128,126 @@ impl<'a> Iterator for LazyAttributesIter<'a> {
     type Item = Result<Attribute<'a>, ParseAttributeError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let string = self.0.next()?;
+        let attribute = self
+            .next_spanned()?
+            .map(|(name, value)| Attribute::new(name.into_inner(), value.map(Spanned::into_inner)));
+
+        Some(attribute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::vec;
+    #[cfg(feature = "alloc")]
+    use alloc::vec::Vec;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(feature = "alloc")]
+    fn attributes(string: &str) -> Vec<Result<Attribute<'_>, ParseAttributeError>> {
+        Attributes::new_spanned(string, 0).attributes().collect()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn spans(string: &str) -> Vec<(&str, Option<&str>)> {
+        let mut iterator = Attributes::new_spanned(string, 0).attributes();
+        let mut result = Vec::new();
+
+        while let Some(Ok((name, value))) = iterator.next_spanned() {
+            result.push((
+                &string[name.range()],
+                value.map(|value| &string[value.range()]),
+            ));
+        }
+
+        result
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_attributes() {
+        assert_eq!(
+            attributes("color=red face=\"Arial\""),
+            vec![
+                Ok(Attribute::new("color", Some("red"))),
+                Ok(Attribute::new("face", Some("Arial"))),
+            ]
+        );
+
+        assert_eq!(
+            attributes("  face = 'Times New Roman'\tsize=\"24\"color=#FFF  "),
+            vec![
+                Ok(Attribute::new("face", Some("Times New Roman"))),
+                Ok(Attribute::new("size", Some("24"))),
+                Ok(Attribute::new("color", Some("#FFF"))),
+            ]
+        );
+
+        assert_eq!(
+            attributes("bold italic color='a \"b\" = c' face=\"\""),
+            vec![
+                Ok(Attribute::new("bold", None)),
+                Ok(Attribute::new("italic", None)),
+                Ok(Attribute::new("color", Some("a \"b\" = c"))),
+                Ok(Attribute::new("face", Some(""))),
+            ]
+        );
+
+        assert_eq!(
+            attributes("color=rgb(1,2,3) face=Ärial"),
+            vec![
+                Ok(Attribute::new("color", Some("rgb(1,2,3)"))),
+                Ok(Attribute::new("face", Some("Ärial"))),
+            ]
+        );
+
+        assert_eq!(attributes(""), vec![]);
+        assert_eq!(attributes("   "), vec![]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_attributes_spans() {
+        assert_eq!(
+            spans("color = red face=\"Times New Roman\" bold"),
+            vec![
+                ("color", Some("red")),
+                ("face", Some("Times New Roman")),
+                ("bold", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attributes_offset() {
+        let mut iterator = Attributes::new_spanned("a b='c'", 6).attributes();
+        let (name, value) = iterator.next_spanned().unwrap().unwrap();
+        assert_eq!((name.range(), value), (6..7, None));
+
+        let (name, value) = iterator.next_spanned().unwrap().unwrap();
+        assert_eq!((name.range(), value.unwrap().range()), (8..9, 11..12));
+        assert!(iterator.next_spanned().is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_attributes_errors() {
+        assert_eq!(
+            attributes("color=red face=\"Arial size=24"),
+            vec![
+                Ok(Attribute::new("color", Some("red"))),
+                Err(ParseAttributeError::invalid_quote(15..29)),
+            ]
+        );
 
-        Some(Attribute::try_from(string))
+        assert_eq!(
+            attributes("='red' face=Arial"),
+            vec![
+                Err(ParseAttributeError::missing_name(0..1)),
+                Ok(Attribute::new("face", Some("Arial"))),
+            ]
+        );
     }
 }
@@ -1,12 +1,27 @@
 use core::convert::TryFrom;
+use core::fmt;
 
+use super::named_colors::{named_color, NAMED_COLORS};
 use super::ColorError;
+use crate::serialize::decimal_digits;
 use crate::utils::Spanned;
-use crate::{Buffer, Serialize, SerializeWithConfig};
+use crate::{Buffer, ExactSerializedLength, Serialize, SerializeWithConfig};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Color<'a> {
-    Rgb { red: u8, green: u8, blue: u8 },
+    Rgb {
+        red: u8,
+        green: u8,
+        blue: u8,
+    },
+    /// A color with an alpha channel, where an `alpha` of `0` is fully
+    /// transparent and `255` is opaque.
+    Rgba {
+        red: u8,
+        green: u8,
+        blue: u8,
+        alpha: u8,
+    },
     Name(&'a str),
 }
 
@@ -15,36 +30,251 @@ impl<'a> TryFrom<Spanned<&'a str>> for Color<'a> {
 
     fn try_from(value: Spanned<&'a str>) -> Result<Self, Self::Error> {
         if value.starts_with('#') {
-            if let [Some(red), Some(green), Some(blue)] = value.sub_ranges([1..3, 3..5, 5..7]) {
-                let red = red.parse_radix_u8(16)?;
-                let green = green.parse_radix_u8(16)?;
-                let blue = blue.parse_radix_u8(16)?;
-
-                Ok(Self::Rgb { red, green, blue })
+            Self::parse_hex(&value)
+        } else if ["rgb(", "rgba(", "hsl(", "hsla("]
+            .iter()
+            .any(|function| value.starts_with(function))
+        {
+            Self::parse_function(&value)
+        } else if value.starts_with("&H") {
+            // parses an ASS color of the format "&HBBGGRR&" or "&HAABBGGRR&", the
+            // trailing "&" is optional and like libass leading zeros can be omitted
+            // (`&HFF&` is red)
+            let end = if value.len() > 2 && value.ends_with('&') {
+                value.len() - 1
             } else {
-                Err(ColorError::invalid_format(value.range()))
-            }
-        } else if value.starts_with("rgb") {
-            // parses a string of the following format: "rgb({red}, {green}, {blue})"
-            // (the {color} is a decimal number of type u8)
-
-            // remove "rgb" and the round braces
-            if let Some(value) = &value.get(3..).and_then(|s| s.remove_start_end('(', ')')) {
-                if let [Some(red), Some(green), Some(blue)] = value.split_at_most::<_, 3>(',') {
-                    let red = red.trim().parse_radix_u8(10)?;
-                    let green = green.trim().parse_radix_u8(10)?;
-                    let blue = blue.trim().parse_radix_u8(10)?;
-
-                    Ok(Self::Rgb { red, green, blue })
-                } else {
-                    Err(ColorError::invalid_format(value.range()))
+                value.len()
+            };
+
+            Self::parse_bgr(&value, value.get(2..end))
+        } else if value.starts_with('$') {
+            // parses a MicroDVD color of the format "$BBGGRR"
+            Self::parse_bgr(&value, value.get(1..).filter(|digits| digits.len() == 6))
+        } else {
+            Ok(Self::Name(&value))
+        }
+    }
+}
+
+impl Color<'_> {
+    /// Returns `Rgb` or, if there is an `alpha`, `Rgba`.
+    const fn from_channels([red, green, blue]: [u8; 3], alpha: Option<u8>) -> Self {
+        match alpha {
+            Some(alpha) => {
+                Self::Rgba {
+                    red,
+                    green,
+                    blue,
+                    alpha,
                 }
+            }
+            None => Self::Rgb { red, green, blue },
+        }
+    }
+
+    /// Parses a color of the format `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA`.
+    fn parse_hex(value: &Spanned<&str>) -> Result<Self, ColorError> {
+        let width = match value.len() {
+            4 | 5 => 1,
+            7 | 9 => 2,
+            _ => return Err(ColorError::invalid_format(value.range())),
+        };
+
+        let mut channels = [0; 4];
+
+        for (index, channel) in channels
+            .iter_mut()
+            .enumerate()
+            .take((value.len() - 1) / width)
+        {
+            let start = 1 + index * width;
+            let digits = value
+                .get(start..start + width)
+                .ok_or_else(|| ColorError::invalid_format(value.range()))?;
+
+            // a single digit is repeated, so `#F00` is the same as `#FF0000`
+            *channel = digits.parse_radix_u8(16)? * if width == 1 { 0x11 } else { 1 };
+        }
+
+        let [red, green, blue, alpha] = channels;
+        let has_alpha = value.len() == 5 || value.len() == 9;
+
+        Ok(Self::from_channels(
+            [red, green, blue],
+            Some(alpha).filter(|_| has_alpha),
+        ))
+    }
+
+    /// Parses a color of the format `rgb(r, g, b)`, `rgba(r, g, b, a)`,
+    /// `hsl(h, s, l)` or `hsla(h, s, l, a)`.
+    ///
+    /// The red, green and blue channel are either a number from `0` to `255`
+    /// or a percentage, the hue is in degrees, the saturation and the
+    /// lightness are percentages and the alpha is either a number from `0` to
+    /// `1` or a percentage. Like in CSS, the alpha is optional for all of them.
+    fn parse_function(value: &Spanned<&str>) -> Result<Self, ColorError> {
+        let is_rgb = value.starts_with("rgb");
+        let invalid_string = |value: &Spanned<&str>| {
+            if is_rgb {
+                ColorError::invalid_rgb_string(value.range())
             } else {
-                Err(ColorError::invalid_rgb_string(value.range()))
+                ColorError::invalid_hsl_string(value.range())
             }
+        };
+
+        // remove the name of the function and the round braces
+        let start = if value.as_bytes().get(3) == Some(&b'a') {
+            4
         } else {
-            Ok(Self::Name(&value))
+            3
+        };
+        let arguments = value
+            .get(start..)
+            .and_then(|s| s.remove_start_end('(', ')'))
+            .ok_or_else(|| invalid_string(value))?;
+
+        let [Some(first), Some(second), Some(third), alpha] = arguments.split_at_most::<_, 4>(',')
+        else {
+            return Err(ColorError::invalid_format(arguments.range()));
+        };
+        let [first, second, third] = [first.trim(), second.trim(), third.trim()];
+
+        let channels = if is_rgb {
+            [
+                parse_channel(&first)?,
+                parse_channel(&second)?,
+                parse_channel(&third)?,
+            ]
+        } else {
+            hsl_to_rgb(
+                parse_hue(&first)?,
+                parse_percentage(&second)?,
+                parse_percentage(&third)?,
+            )
+        };
+        let alpha = alpha.map(|alpha| parse_alpha(&alpha.trim())).transpose()?;
+
+        Ok(Self::from_channels(channels, alpha))
+    }
+
+    /// Parses the `digits` of the format `BBGGRR` or `AABBGGRR`, which are part
+    /// of the `value`. Missing leading digits are zero, so `FF` is red.
+    ///
+    /// The alpha is inverted, so `00` is opaque and `FF` is fully transparent.
+    fn parse_bgr(value: &Spanned<&str>, digits: Option<Spanned<&str>>) -> Result<Self, ColorError> {
+        let digits = digits
+            .filter(|digits| (1..=8).contains(&digits.len()))
+            .ok_or_else(|| ColorError::invalid_format(value.range()))?;
+
+        // the channels are read from the end, so the missing leading digits are zero
+        let mut channels = [0; 4];
+        let mut end = digits.len();
+
+        for channel in channels.iter_mut().take(digits.len().div_ceil(2)) {
+            let start = end.saturating_sub(2);
+
+            *channel = digits
+                .get(start..end)
+                .ok_or_else(|| ColorError::invalid_format(value.range()))?
+                .parse_radix_u8(16)?;
+            end = start;
         }
+
+        let [red, green, blue, alpha] = channels;
+
+        Ok(Self::from_channels(
+            [red, green, blue],
+            Some(u8::MAX - alpha).filter(|_| digits.len() > 6),
+        ))
+    }
+
+    /// Returns the red, green and blue channel and the alpha of the color or
+    /// `None` for a [`Color::Name`].
+    const fn channels(&self) -> Option<([u8; 3], Option<u8>)> {
+        match self {
+            Self::Rgb { red, green, blue } => Some(([*red, *green, *blue], None)),
+            Self::Rgba {
+                red,
+                green,
+                blue,
+                alpha,
+            } => Some(([*red, *green, *blue], Some(*alpha))),
+            Self::Name(_) => None,
+        }
+    }
+
+    /// Returns the red, green and blue component of the color, a
+    /// [`Color::Name`] is looked up in the table of CSS named colors.
+    fn components(&self) -> Option<[u8; 3]> {
+        match self {
+            Self::Name(name) => named_color(name),
+            _ => self.channels().map(|(components, _)| components),
+        }
+    }
+
+    /// Resolves a [`Color::Name`] to a [`Color::Rgb`], any other color is
+    /// returned as it is.
+    ///
+    /// A name is looked up in the table of the 148 named colors of CSS and
+    /// HTML regardless of its ASCII case. Returns `None` if the name is
+    /// unknown.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use libsrt::text::Color;
+    ///
+    /// let name = Color::try_from("Red")?;
+    /// let hex = Color::try_from("#ff0000")?;
+    ///
+    /// assert_ne!(name, hex);
+    /// assert_eq!(name.resolve(), hex.resolve());
+    /// assert_eq!(Color::Name("reddish").resolve(), None);
+    /// # Ok::<(), libsrt::text::ColorError>(())
+    /// ```
+    #[must_use]
+    pub fn resolve(&self) -> Option<Color<'static>> {
+        let alpha = self.channels().and_then(|(_, alpha)| alpha);
+
+        Some(Color::from_channels(self.components()?, alpha))
+    }
+
+    /// Returns the name of the CSS named color, that is closest to this color.
+    ///
+    /// The distance is the euclidean distance in the RGB color space. If
+    /// several names have the same color, like `aqua` and `cyan`, the first
+    /// one in alphabetical order is returned. The alpha is ignored. Returns
+    /// `None` for an unknown [`Color::Name`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::text::Color;
+    ///
+    /// let color = Color::Rgb {
+    ///     red: 0xFE,
+    ///     green: 0x01,
+    ///     blue: 0x02,
+    /// };
+    ///
+    /// assert_eq!(color.nearest_name(), Some("red"));
+    /// assert_eq!(Color::Name("CYAN").nearest_name(), Some("aqua"));
+    /// ```
+    #[must_use]
+    pub fn nearest_name(&self) -> Option<&'static str> {
+        let components = self.components()?;
+
+        NAMED_COLORS
+            .iter()
+            .min_by_key(|(_, other)| {
+                components
+                    .iter()
+                    .zip(other)
+                    .map(|(a, b)| u32::from(a.abs_diff(*b)).pow(2))
+                    .sum::<u32>()
+            })
+            .map(|(name, _)| *name)
     }
 }
 
@@ -61,6 +291,24 @@ pub enum SerializeColorConfig {
     UpperHex,
     LowerHex,
     Rgb,
+    /// The `hsl(h, s%, l%)` format of CSS, which is rounded to whole degrees
+    /// and percentages.
+    Hsl,
+    /// The `&HBBGGRR&` format of `.ass` and `.ssa` files, which is
+    /// `&HAABBGGRR&` with an inverted alpha for a [`Color::Rgba`].
+    ///
+    /// Named colors are resolved, unknown names are written as they are.
+    Ass,
+    /// The `$BBGGRR` format of `.sub` files, which can not represent the alpha
+    /// of a [`Color::Rgba`].
+    ///
+    /// Named colors are resolved, unknown names are written as they are.
+    MicroDvd,
+}
+
+impl SerializeColorConfig {
+    /// Returns `true` if the format can not represent a [`Color::Name`].
+    const fn resolves_names(&self) -> bool { matches!(self, Self::Ass | Self::MicroDvd) }
 }
 
 impl Default for SerializeColorConfig {
@@ -68,7 +316,207 @@ impl Default for SerializeColorConfig {
     fn default() -> Self { Self::LowerHex }
 }
 
-impl<'a, B: Buffer> Serialize<B> for Color<'a> {
+/// Parses a decimal number like `12`, `-1.5` or `.25` into thousandths.
+fn parse_decimal(value: &str) -> Option<i64> {
+    let (sign, value) = value
+        .strip_prefix('-')
+        .map_or((1, value), |value| (-1, value));
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let is_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+
+    if (integer.is_empty() && fraction.is_empty())
+        || integer.len() > 9
+        || !is_digits(integer)
+        || !is_digits(fraction)
+    {
+        return None;
+    }
+
+    // the fraction is truncated to three digits
+    let thousandths = integer
+        .bytes()
+        .chain(fraction.bytes().chain(core::iter::repeat(b'0')).take(3))
+        .fold(0, |result, digit| result * 10 + i64::from(digit - b'0'));
+
+    Some(sign * thousandths)
+}
+
+/// Scales a `value` from `0` to `max` to a channel from `0` to `255`.
+fn scale(value: i64, max: i64) -> u8 {
+    u8::try_from((value * i64::from(u8::MAX) + max / 2) / max).unwrap_or(u8::MAX)
+}
+
+/// Parses a percentage like `50%` or `12.5%` into thousandths of a percent.
+fn parse_percentage(value: &Spanned<&str>) -> Result<i64, ColorError> {
+    value
+        .strip_suffix('%')
+        .and_then(parse_decimal)
+        .filter(|percentage| (0..=100_000).contains(percentage))
+        .ok_or_else(|| ColorError::invalid_component(value.range()))
+}
+
+/// Parses a red, green or blue channel, which is either a number from `0` to
+/// `255` or a percentage.
+fn parse_channel(value: &Spanned<&str>) -> Result<u8, ColorError> {
+    if value.ends_with('%') {
+        Ok(scale(parse_percentage(value)?, 100_000))
+    } else {
+        Ok(value.parse_radix_u8(10)?)
+    }
+}
+
+/// Parses an alpha, which is either a number from `0` to `1` or a percentage.
+fn parse_alpha(value: &Spanned<&str>) -> Result<u8, ColorError> {
+    if value.ends_with('%') {
+        Ok(scale(parse_percentage(value)?, 100_000))
+    } else {
+        parse_decimal(value)
+            .filter(|alpha| (0..=1_000).contains(alpha))
+            .map(|alpha| scale(alpha, 1_000))
+            .ok_or_else(|| ColorError::invalid_component(value.range()))
+    }
+}
+
+/// Parses a hue in degrees, like `120` or `-60deg`, into thousandths of a
+/// degree.
+fn parse_hue(value: &Spanned<&str>) -> Result<i64, ColorError> {
+    parse_decimal(value.strip_suffix("deg").unwrap_or(value))
+        .ok_or_else(|| ColorError::invalid_component(value.range()))
+}
+
+/// Converts a color from the HSL to the RGB color space, see
+/// <https://www.w3.org/TR/css-color-4/#hsl-to-rgb>.
+///
+/// The `hue` is in thousandths of a degree, the `saturation` and the
+/// `lightness` are in thousandths of a percent.
+fn hsl_to_rgb(hue: i64, saturation: i64, lightness: i64) -> [u8; 3] {
+    const PERCENT: i64 = 100_000;
+    // 30 degrees, which is one twelfth of the color wheel
+    const SECTOR: i64 = 30_000;
+
+    let hue = hue.rem_euclid(12 * SECTOR);
+    let chroma = saturation * lightness.min(PERCENT - lightness) / PERCENT;
+
+    [0, 8, 4].map(|n| {
+        let k = (n * SECTOR + hue) % (12 * SECTOR);
+        let factor = (k - 3 * SECTOR).min(9 * SECTOR - k).clamp(-SECTOR, SECTOR);
+
+        scale(lightness - chroma * factor / SECTOR, PERCENT)
+    })
+}
+
+/// Converts a color from the RGB to the HSL color space, the hue is rounded to
+/// whole degrees, the saturation and the lightness to whole percents.
+fn rgb_to_hsl(components: [u8; 3]) -> [u16; 3] {
+    let [red, green, blue] = components.map(i32::from);
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let delta = max - min;
+
+    // rounds the quotient to the nearest integer
+    let divide = |numerator: i32, denominator: i32| {
+        (2 * numerator + denominator).div_euclid(2 * denominator)
+    };
+
+    let lightness = divide(100 * (max + min), 2 * 255);
+    let (hue, saturation) = if delta == 0 {
+        (0, 0)
+    } else {
+        let hue = if max == red {
+            divide(60 * (green - blue), delta)
+        } else if max == green {
+            divide(60 * (blue - red), delta) + 120
+        } else {
+            divide(60 * (red - green), delta) + 240
+        };
+
+        (
+            hue.rem_euclid(360),
+            divide(100 * delta, 255 - (max + min - 255).abs()),
+        )
+    };
+
+    [hue, saturation, lightness].map(|value| u16::try_from(value).unwrap_or(u16::MAX))
+}
+
+/// Formats an alpha as a number from `0` to `1` with at most three decimal
+/// places, which is precise enough to parse the same alpha again.
+struct DecimalAlpha(u8);
+
+impl DecimalAlpha {
+    fn thousandths(&self) -> u32 { (u32::from(self.0) * 1_000 + 127) / 255 }
+
+    fn len(&self) -> usize {
+        match self.thousandths() {
+            thousandths if thousandths % 1_000 == 0 => "1".len(),
+            thousandths if thousandths % 100 == 0 => "0.5".len(),
+            thousandths if thousandths % 10 == 0 => "0.25".len(),
+            _ => "0.125".len(),
+        }
+    }
+}
+
+impl fmt::Display for DecimalAlpha {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.thousandths() {
+            thousandths if thousandths % 1_000 == 0 => write!(f, "{}", thousandths / 1_000),
+            thousandths if thousandths % 100 == 0 => write!(f, "0.{}", thousandths / 100),
+            thousandths if thousandths % 10 == 0 => write!(f, "0.{:02}", thousandths / 10),
+            thousandths => write!(f, "0.{thousandths:03}"),
+        }
+    }
+}
+
+impl Color<'_> {
+    /// Returns the length of the output of [`SerializeWithConfig`].
+    fn len_with_config(&self, config: &SerializeColorConfig) -> usize {
+        if let (true, Self::Name(_), Some(color)) = (config.resolves_names(), self, self.resolve())
+        {
+            return color.len_with_config(config);
+        }
+
+        let Some((components, alpha)) = self.channels() else {
+            return if let Self::Name(name) = self {
+                name.len()
+            } else {
+                0
+            };
+        };
+        let alpha_len = alpha.map_or(0, |alpha| ", ".len() + DecimalAlpha(alpha).len());
+        let digits = |values: &[u16]| {
+            values
+                .iter()
+                .map(|value| decimal_digits(u128::from(*value)))
+                .sum::<usize>()
+        };
+
+        match config {
+            SerializeColorConfig::UpperHex | SerializeColorConfig::LowerHex => {
+                "#RRGGBB".len() + alpha.map_or(0, |_| "AA".len())
+            }
+            SerializeColorConfig::Rgb => {
+                "rgb(, , )".len()
+                    + digits(&components.map(u16::from))
+                    + alpha.map_or(0, |_| "a".len())
+                    + alpha_len
+            }
+            SerializeColorConfig::Hsl => {
+                "hsl(, %, %)".len()
+                    + digits(&rgb_to_hsl(components))
+                    + alpha.map_or(0, |_| "a".len())
+                    + alpha_len
+            }
+            SerializeColorConfig::Ass => "&HBBGGRR&".len() + alpha.map_or(0, |_| "AA".len()),
+            SerializeColorConfig::MicroDvd => "$BBGGRR".len(),
+        }
+    }
+}
+
+impl ExactSerializedLength for Color<'_> {
+    fn serialized_len(&self) -> usize { self.len_with_config(&SerializeColorConfig::default()) }
+}
+
+impl<B: Buffer> Serialize<B> for Color<'_> {
     type Error = B::Error;
 
     fn serialize(&self, buffer: B) -> Result<(), Self::Error> {
@@ -76,7 +524,7 @@ impl<'a, B: Buffer> Serialize<B> for Color<'a> {
     }
 }
 
-impl<'a, B: Buffer> SerializeWithConfig<B> for Color<'a> {
+impl<B: Buffer> SerializeWithConfig<B> for Color<'_> {
     type Config = SerializeColorConfig;
     type Error = B::Error;
 
@@ -85,20 +533,74 @@ impl<'a, B: Buffer> SerializeWithConfig<B> for Color<'a> {
         mut buffer: B,
         config: &Self::Config,
     ) -> Result<(), Self::Error> {
+        if let (true, Self::Name(_), Some(color)) = (config.resolves_names(), self, self.resolve())
+        {
+            return color.serialize_with_config(buffer, config);
+        }
+
+        buffer.reserve(self.len_with_config(config))?;
+
         match self {
             Self::Rgb { red, green, blue } => {
                 match config {
                     Self::Config::UpperHex => {
-                        buffer.reserve(3 * 2)?;
-                        write!(buffer, "#{:02X?}{:02X?}{:02X?}", red, green, blue)?;
+                        write!(buffer, "#{red:02X?}{green:02X?}{blue:02X?}")?;
+                    }
+                    Self::Config::LowerHex => {
+                        write!(buffer, "#{red:02x?}{green:02x?}{blue:02x?}")?;
+                    }
+                    Self::Config::Rgb => {
+                        write!(buffer, "rgb({red}, {green}, {blue})")?;
+                    }
+                    Self::Config::Hsl => {
+                        let [hue, saturation, lightness] = rgb_to_hsl([*red, *green, *blue]);
+
+                        write!(buffer, "hsl({hue}, {saturation}%, {lightness}%)")?;
+                    }
+                    Self::Config::Ass => {
+                        write!(buffer, "&H{blue:02X?}{green:02X?}{red:02X?}&")?;
+                    }
+                    Self::Config::MicroDvd => {
+                        write!(buffer, "${blue:02X?}{green:02X?}{red:02X?}")?;
+                    }
+                }
+            }
+            Self::Rgba {
+                red,
+                green,
+                blue,
+                alpha,
+            } => {
+                match config {
+                    Self::Config::UpperHex => {
+                        write!(buffer, "#{red:02X?}{green:02X?}{blue:02X?}{alpha:02X?}")?;
                     }
                     Self::Config::LowerHex => {
-                        buffer.reserve(3 * 2)?;
-                        write!(buffer, "#{:02x?}{:02x?}{:02x?}", red, green, blue)?;
+                        write!(buffer, "#{red:02x?}{green:02x?}{blue:02x?}{alpha:02x?}")?;
                     }
                     Self::Config::Rgb => {
-                        buffer.reserve(4 + 3 + 2 + 3 + 2 + 3 + 1)?;
-                        write!(buffer, "rgb({}, {}, {})", red, green, blue)?;
+                        write!(
+                            buffer,
+                            "rgba({red}, {green}, {blue}, {})",
+                            DecimalAlpha(*alpha)
+                        )?;
+                    }
+                    Self::Config::Hsl => {
+                        let [hue, saturation, lightness] = rgb_to_hsl([*red, *green, *blue]);
+
+                        write!(
+                            buffer,
+                            "hsla({hue}, {saturation}%, {lightness}%, {})",
+                            DecimalAlpha(*alpha)
+                        )?;
+                    }
+                    Self::Config::Ass => {
+                        let alpha = u8::MAX - alpha;
+
+                        write!(buffer, "&H{alpha:02X?}{blue:02X?}{green:02X?}{red:02X?}&")?;
+                    }
+                    Self::Config::MicroDvd => {
+                        write!(buffer, "${blue:02X?}{green:02X?}{red:02X?}")?;
                     }
                 }
             }
@@ -116,6 +618,7 @@ mod tests {
     use super::*;
     #[cfg(feature = "alloc")]
     use crate::serialize::{SerializeToString, SerializeWithConfigToString};
+    use crate::text::attributes::named_colors::named_color;
     #[cfg(feature = "alloc")]
     use alloc::format;
     #[cfg(feature = "alloc")]
@@ -205,6 +708,362 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_color_from_str_short_hex_and_alpha() {
+        assert_eq!(
+            Color::try_from("#F0a"),
+            Ok(Color::Rgb {
+                red: 0xFF,
+                green: 0x00,
+                blue: 0xAA,
+            })
+        );
+
+        assert_eq!(
+            Color::try_from("#F0a8"),
+            Ok(Color::Rgba {
+                red: 0xFF,
+                green: 0x00,
+                blue: 0xAA,
+                alpha: 0x88,
+            })
+        );
+
+        assert_eq!(
+            Color::try_from("#AABBCC80"),
+            Ok(Color::Rgba {
+                red: 0xAA,
+                green: 0xBB,
+                blue: 0xCC,
+                alpha: 0x80,
+            })
+        );
+
+        assert_eq!(
+            Color::try_from("#FFG"),
+            Err(ColorError::from(
+                Spanned::new(u8::from_str_radix("G", 16).unwrap_err()).with_span(3..4)
+            ))
+        );
+
+        assert_eq!(
+            Color::try_from("#AABBCCGG"),
+            Err(ColorError::from(
+                Spanned::new(u8::from_str_radix("GG", 16).unwrap_err()).with_span(7..9)
+            ))
+        );
+
+        for input in ["#", "#F", "#FF", "#FFFFF", "#FFFFFFF", "#FFFFFFFFF", "#ÄÄ"] {
+            assert_eq!(
+                Color::try_from(input),
+                Err(ColorError::invalid_format(0..input.len())),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_color_from_str_rgba() {
+        assert_eq!(
+            Color::try_from("rgba(255, 0, 0, 0.5)"),
+            Ok(Color::Rgba {
+                red: 255,
+                green: 0,
+                blue: 0,
+                alpha: 128,
+            })
+        );
+
+        assert_eq!(
+            Color::try_from("rgb(1,2,3,1)"),
+            Ok(Color::Rgba {
+                red: 1,
+                green: 2,
+                blue: 3,
+                alpha: 255,
+            })
+        );
+
+        assert_eq!(
+            Color::try_from("rgba(1, 2, 3, 20%)"),
+            Ok(Color::Rgba {
+                red: 1,
+                green: 2,
+                blue: 3,
+                alpha: 51,
+            })
+        );
+
+        assert_eq!(
+            Color::try_from("rgb(100%, 50%, 0%)"),
+            Ok(Color::Rgb {
+                red: 255,
+                green: 128,
+                blue: 0,
+            })
+        );
+
+        assert_eq!(
+            Color::try_from("rgb(12.5%, 0, 255)"),
+            Ok(Color::Rgb {
+                red: 32,
+                green: 0,
+                blue: 255,
+            })
+        );
+    }
+
+    #[test]
+    fn test_color_from_str_hsl() {
+        let cases = [
+            ("hsl(0, 100%, 50%)", [255, 0, 0]),
+            ("hsl(120, 100%, 50%)", [0, 255, 0]),
+            ("hsl(240deg, 100%, 50%)", [0, 0, 255]),
+            ("hsl(-120, 100%, 50%)", [0, 0, 255]),
+            ("hsl(480, 100%, 50%)", [0, 255, 0]),
+            ("hsl(0, 0%, 0%)", [0, 0, 0]),
+            ("hsl(0, 0%, 100%)", [255, 255, 255]),
+            ("hsl(0, 0%, 50%)", [128, 128, 128]),
+            ("hsl(39, 100%, 50%)", [255, 166, 0]),
+            ("hsl(270, 50%, 40%)", [102, 51, 153]),
+            ("hsl(197.4, 71.4%, 72.5%)", [135, 206, 235]),
+        ];
+
+        for (input, [red, green, blue]) in cases {
+            assert_eq!(
+                Color::try_from(input),
+                Ok(Color::Rgb { red, green, blue }),
+                "{:?}",
+                input
+            );
+        }
+
+        assert_eq!(
+            Color::try_from("hsla(0, 100%, 50%, .25)"),
+            Ok(Color::Rgba {
+                red: 255,
+                green: 0,
+                blue: 0,
+                alpha: 64,
+            })
+        );
+    }
+
+    #[test]
+    fn test_color_from_str_component_error() {
+        let cases = [
+            ("rgb(101%, 0, 0)", 4..8),
+            ("rgb(0, 5.0.0%, 0)", 7..13),
+            ("rgb(0, 0, %)", 10..11),
+            ("rgba(0, 0, 0, 1.5)", 14..17),
+            ("rgba(0, 0, 0,  half )", 15..19),
+            ("rgba(0, 0, 0, -1%)", 14..17),
+            ("rgba(0, 0, 0, 1, 2)", 14..18),
+            ("hsl(red, 100%, 50%)", 4..7),
+            ("hsl(0, 100, 50%)", 7..10),
+            ("hsl(0, 100%, 50)", 13..15),
+            ("hsla(0, 100%, 50%, 2)", 19..20),
+        ];
+
+        for (input, span) in cases {
+            assert_eq!(
+                Color::try_from(input),
+                Err(ColorError::invalid_component(span)),
+                "{:?}",
+                input
+            );
+        }
+
+        assert_eq!(
+            Color::try_from("rgb(0, 256, 0)"),
+            Err(ColorError::from(
+                Spanned::new("256".parse::<u8>().unwrap_err()).with_span(7..10)
+            ))
+        );
+
+        assert_eq!(
+            Color::try_from("hsl(0, 0%, 0%"),
+            Err(ColorError::invalid_hsl_string(0..13))
+        );
+        assert_eq!(
+            Color::try_from("rgba(0, 0, 0"),
+            Err(ColorError::invalid_rgb_string(0..12))
+        );
+
+        // only the name of a function followed by a brace is a function
+        for input in ["rgbfoo", "hsl 0, 0%, 0%", "rgba"] {
+            assert_eq!(Color::try_from(input), Ok(Color::Name(input)));
+        }
+        assert_eq!(
+            Color::try_from("hsl(0, 0%)"),
+            Err(ColorError::invalid_format(4..9))
+        );
+    }
+
+    #[test]
+    fn test_decimal_alpha() {
+        for alpha in 0..=u8::MAX {
+            let mut buffer = crate::ArrayBuffer::<8>::new();
+            write!(buffer, "{}", DecimalAlpha(alpha)).unwrap();
+
+            assert_eq!(buffer.len(), DecimalAlpha(alpha).len());
+            // the alpha can be parsed again
+            assert_eq!(
+                parse_alpha(&Spanned::new(buffer.as_str())),
+                Ok(alpha),
+                "{:?}",
+                buffer.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rgb_to_hsl() {
+        assert_eq!(rgb_to_hsl([255, 0, 0]), [0, 100, 50]);
+        assert_eq!(rgb_to_hsl([0, 0, 255]), [240, 100, 50]);
+        assert_eq!(rgb_to_hsl([255, 0, 128]), [330, 100, 50]);
+        assert_eq!(rgb_to_hsl([128, 128, 128]), [0, 0, 50]);
+        assert_eq!(rgb_to_hsl([255, 255, 255]), [0, 0, 100]);
+        assert_eq!(rgb_to_hsl([102, 51, 153]), [270, 50, 40]);
+    }
+
+    #[test]
+    fn test_color_from_str_bgr() {
+        let expected = Ok(Color::Rgb {
+            red: 0xAA,
+            green: 0xBB,
+            blue: 0xCC,
+        });
+
+        assert_eq!(Color::try_from("&HCCBBAA&"), expected);
+        assert_eq!(Color::try_from("&HCCBBAA"), expected);
+        assert_eq!(Color::try_from("$CCBBAA"), expected);
+        assert_eq!(Color::try_from("$ccbbaa"), expected);
+
+        assert_eq!(
+            Color::try_from("&HCCGGAA&"),
+            Err(ColorError::from(
+                Spanned::new(u8::from_str_radix("GG", 16).unwrap_err()).with_span(4..6)
+            ))
+        );
+        assert_eq!(
+            Color::try_from("$CCBBGG"),
+            Err(ColorError::from(
+                Spanned::new(u8::from_str_radix("GG", 16).unwrap_err()).with_span(5..7)
+            ))
+        );
+
+        assert_eq!(
+            Color::try_from("&H80CCBBAA&"),
+            Ok(Color::Rgba {
+                red: 0xAA,
+                green: 0xBB,
+                blue: 0xCC,
+                alpha: 0x7F,
+            })
+        );
+
+        // like libass, the leading zeros of an ASS color can be omitted
+        assert_eq!(
+            Color::try_from("&HFF&"),
+            Ok(Color::Rgb {
+                red: 0xFF,
+                green: 0,
+                blue: 0,
+            })
+        );
+        assert_eq!(
+            Color::try_from("&HCBBAA"),
+            Ok(Color::Rgb {
+                red: 0xAA,
+                green: 0xBB,
+                blue: 0x0C,
+            })
+        );
+        assert_eq!(
+            Color::try_from("&H8CCBBAA&"),
+            Ok(Color::Rgba {
+                red: 0xAA,
+                green: 0xBB,
+                blue: 0xCC,
+                alpha: 0xF7,
+            })
+        );
+
+        for input in [
+            "&H",
+            "&H&",
+            "&H1CCBBAA00&",
+            "$CCBBA",
+            "$CCBBAAA",
+            "$00CCBBAA",
+        ] {
+            assert_eq!(
+                Color::try_from(input),
+                Err(ColorError::invalid_format(0..input.len())),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let red = Some(Color::Rgb {
+            red: 0xFF,
+            green: 0,
+            blue: 0,
+        });
+
+        assert_eq!(Color::Name("red").resolve(), red);
+        assert_eq!(Color::Name("RED").resolve(), red);
+        assert_eq!(Color::try_from("#FF0000").unwrap().resolve(), red);
+        assert_eq!(
+            Color::Name("DarkSlateGrey").resolve(),
+            Some(Color::Rgb {
+                red: 0x2F,
+                green: 0x4F,
+                blue: 0x4F,
+            })
+        );
+        assert_eq!(Color::Name("").resolve(), None);
+        assert_eq!(Color::Name("red ").resolve(), None);
+    }
+
+    #[test]
+    fn test_nearest_name() {
+        for (name, rgb) in NAMED_COLORS {
+            let [red, green, blue] = *rgb;
+            let nearest = Color::Rgb { red, green, blue }.nearest_name().unwrap();
+
+            // the name or an alias with the same color
+            assert_eq!(named_color(nearest), Some(*rgb), "{:?}", name);
+        }
+
+        assert_eq!(Color::Name("fuchsia").nearest_name(), Some("fuchsia"));
+        assert_eq!(Color::Name("magenta").nearest_name(), Some("fuchsia"));
+        assert_eq!(
+            Color::Rgb {
+                red: 0x80,
+                green: 0x80,
+                blue: 0x81,
+            }
+            .nearest_name(),
+            Some("gray")
+        );
+        assert_eq!(
+            Color::Rgb {
+                red: 0x01,
+                green: 0x02,
+                blue: 0x03,
+            }
+            .nearest_name(),
+            Some("black")
+        );
+        assert_eq!(Color::Name("unknown").nearest_name(), None);
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn test_serialize() {
@@ -220,7 +1079,7 @@ mod tests {
         );
 
         assert_eq!(
-            Color::Name("red".into()).serialize_to_string().unwrap(),
+            Color::Name("red").serialize_to_string().unwrap(),
             "red".to_string()
         );
     }
@@ -277,5 +1136,146 @@ mod tests {
             .serialize_with_config_to_string(&SerializeColorConfig::LowerHex),
             Ok("#0a0b0c".to_string())
         );
+
+        assert_eq!(
+            Color::Rgb {
+                red: 0x0A,
+                green: 0xBC,
+                blue: 0xDE,
+            }
+            .serialize_with_config_to_string(&SerializeColorConfig::Ass),
+            Ok("&HDEBC0A&".to_string())
+        );
+
+        assert_eq!(
+            Color::Rgb {
+                red: 0x0A,
+                green: 0xBC,
+                blue: 0xDE,
+            }
+            .serialize_with_config_to_string(&SerializeColorConfig::MicroDvd),
+            Ok("$DEBC0A".to_string())
+        );
+
+        assert_eq!(
+            Color::Rgb {
+                red: 0xFF,
+                green: 0x00,
+                blue: 0x80,
+            }
+            .serialize_with_config_to_string(&SerializeColorConfig::Hsl),
+            Ok("hsl(330, 100%, 50%)".to_string())
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_serialize_rgba_with_config() {
+        let color = Color::Rgba {
+            red: 0xFF,
+            green: 0x00,
+            blue: 0x80,
+            alpha: 0x80,
+        };
+        let cases = [
+            (SerializeColorConfig::UpperHex, "#FF008080"),
+            (SerializeColorConfig::LowerHex, "#ff008080"),
+            (SerializeColorConfig::Rgb, "rgba(255, 0, 128, 0.502)"),
+            (SerializeColorConfig::Hsl, "hsla(330, 100%, 50%, 0.502)"),
+            (SerializeColorConfig::Ass, "&H7F8000FF&"),
+            (SerializeColorConfig::MicroDvd, "$8000FF"),
+        ];
+
+        for (config, expected) in &cases {
+            let output = color.serialize_with_config_to_string(config).unwrap();
+            assert_eq!(&output, expected);
+
+            // all formats, that have an alpha, can be parsed again
+            if *config != SerializeColorConfig::MicroDvd {
+                let parsed = Color::try_from(output.as_str()).unwrap();
+
+                if *config == SerializeColorConfig::Hsl {
+                    assert_eq!(parsed.serialize_with_config_to_string(config), Ok(output));
+                } else {
+                    assert_eq!(parsed, color, "{:?}", config);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_serialize_name_with_config() {
+        let cases = [
+            (SerializeColorConfig::UpperHex, "Orange"),
+            (SerializeColorConfig::LowerHex, "Orange"),
+            (SerializeColorConfig::Rgb, "Orange"),
+            (SerializeColorConfig::Ass, "&H00A5FF&"),
+            (SerializeColorConfig::MicroDvd, "$00A5FF"),
+        ];
+
+        for (config, expected) in &cases {
+            assert_eq!(
+                Color::Name("Orange").serialize_with_config_to_string(config),
+                Ok(expected.to_string())
+            );
+        }
+
+        // an unknown name can not be converted
+        assert_eq!(
+            Color::Name("orangish").serialize_with_config_to_string(&SerializeColorConfig::Ass),
+            Ok("orangish".to_string())
+        );
+    }
+
+    #[test]
+    fn test_serialized_len() {
+        use crate::serialize::tests::CountingBuffer;
+
+        let colors = [
+            Color::Rgb {
+                red: 0xAB,
+                green: 0xCD,
+                blue: 0xEF,
+            },
+            Color::Rgb {
+                red: 0,
+                green: 10,
+                blue: 100,
+            },
+            Color::Rgba {
+                red: 0,
+                green: 10,
+                blue: 100,
+                alpha: 0,
+            },
+            Color::Rgba {
+                red: 255,
+                green: 255,
+                blue: 255,
+                alpha: 51,
+            },
+            Color::Name("red"),
+            Color::Name("unknown"),
+        ];
+        let configs = [
+            SerializeColorConfig::UpperHex,
+            SerializeColorConfig::LowerHex,
+            SerializeColorConfig::Rgb,
+            SerializeColorConfig::Hsl,
+            SerializeColorConfig::Ass,
+            SerializeColorConfig::MicroDvd,
+        ];
+
+        for color in &colors {
+            crate::serialize::tests::assert_exact_length(color);
+
+            for config in &configs {
+                let mut buffer = CountingBuffer::default();
+
+                assert!(color.serialize_with_config(&mut buffer, config).is_ok());
+                assert_eq!(buffer.written(), color.len_with_config(config));
+            }
+        }
     }
 }
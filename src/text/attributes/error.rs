@@ -1,7 +1,11 @@
 use crate::utils::{Span, Spanned};
 use core::num::ParseIntError;
+use core::ops::Range;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Error)]
+#[error("{0}")]
 pub struct ParseAttributeError(Spanned<ParseAttributeErrorKind>);
 
 impl ParseAttributeError {
@@ -14,20 +18,35 @@ impl ParseAttributeError {
     pub(crate) fn invalid_quote(range: impl Into<Span>) -> Self {
         Self::new(ParseAttributeErrorKind::InvalidQuote, range)
     }
+
+    #[must_use]
+    pub(crate) fn missing_name(range: impl Into<Span>) -> Self {
+        Self::new(ParseAttributeErrorKind::MissingName, range)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Error)]
 #[non_exhaustive]
 pub enum ParseAttributeErrorKind {
+    #[error("mismatched quotes around the attribute value")]
     InvalidQuote,
+    #[error("the attribute is missing its name")]
+    MissingName,
 }
 
 #[doc(hidden)]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
 #[non_exhaustive]
 pub enum ColorErrorKind {
+    #[error("{0}")]
     ParseIntError(ParseIntError),
+    #[error("invalid `rgb(r, g, b)` color")]
     InvalidRgbString,
+    #[error("invalid `hsl(h, s, l)` color")]
+    InvalidHslString,
+    #[error("invalid color component")]
+    InvalidComponent,
+    #[error("invalid color")]
     InvalidFormat,
 }
 
@@ -35,7 +54,8 @@ impl From<ParseIntError> for ColorErrorKind {
     fn from(value: ParseIntError) -> Self { Self::ParseIntError(value) }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{0}")]
 pub struct ColorError(Spanned<ColorErrorKind>);
 
 impl ColorError {
@@ -51,6 +71,18 @@ impl ColorError {
         Self::new(ColorErrorKind::InvalidRgbString, range)
     }
 
+    #[inline]
+    #[must_use]
+    pub(crate) fn invalid_hsl_string(range: impl Into<Span>) -> Self {
+        Self::new(ColorErrorKind::InvalidHslString, range)
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) fn invalid_component(range: impl Into<Span>) -> Self {
+        Self::new(ColorErrorKind::InvalidComponent, range)
+    }
+
     #[inline]
     #[must_use]
     pub(crate) fn invalid_format(range: impl Into<Span>) -> Self {
@@ -63,5 +95,62 @@ impl<E> From<Spanned<E>> for ColorError
 where
     ColorErrorKind: From<E>,
 {
-    fn from(value: Spanned<E>) -> Self { Self(value.map(|v| v.into())) }
+    fn from(value: Spanned<E>) -> Self { Self(value.map(Into::into)) }
+}
+
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum FontAttributesErrorKind {
+    #[error("{0}")]
+    Attribute(ParseAttributeErrorKind),
+    #[error("invalid `color` attribute: {0}")]
+    Color(ColorErrorKind),
+    #[error("invalid `size` attribute: {0}")]
+    Size(ParseIntError),
+    #[error("the `{0}` attribute is missing its value")]
+    MissingValue(&'static str),
+}
+
+/// The error of [`FontAttributes`], whose span points at the attribute or the
+/// part of its value, that is invalid.
+///
+/// [`FontAttributes`]: super::FontAttributes
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{0}")]
+pub struct FontAttributesError(Spanned<FontAttributesErrorKind>);
+
+impl FontAttributesError {
+    #[inline]
+    #[must_use]
+    fn new(kind: FontAttributesErrorKind, range: impl Into<Span>) -> Self {
+        Self(Spanned::new(kind).with_span(range))
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) fn invalid_size(source: ParseIntError, range: impl Into<Span>) -> Self {
+        Self::new(FontAttributesErrorKind::Size(source), range)
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) fn missing_value(name: &'static str, range: impl Into<Span>) -> Self {
+        Self::new(FontAttributesErrorKind::MissingValue(name), range)
+    }
+
+    /// Returns the byte range in the text, at which the error occurred.
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.0.span().map(|span| span.start()..span.end())
+    }
+}
+
+impl From<ParseAttributeError> for FontAttributesError {
+    fn from(value: ParseAttributeError) -> Self {
+        Self(value.0.map(FontAttributesErrorKind::Attribute))
+    }
+}
+
+impl From<ColorError> for FontAttributesError {
+    fn from(value: ColorError) -> Self { Self(value.0.map(FontAttributesErrorKind::Color)) }
 }
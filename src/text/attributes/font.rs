@@ -0,0 +1,143 @@
+use core::convert::TryFrom;
+
+use super::{Attributes, Color, FontAttributesError};
+use crate::utils::Spanned;
+
+/// The attributes of a `<font>` tag, like
+/// `<font color="red" face="Arial" size="24">`.
+///
+/// Attributes, that are not known, are ignored and if an attribute occurs more
+/// than once, the first one is used, like in HTML. The names of the attributes
+/// are compared regardless of their ASCII case.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct FontAttributes<'a> {
+    /// The color of the text.
+    pub color: Option<Color<'a>>,
+    /// The name of the font family, or a comma separated list of them.
+    pub face: Option<&'a str>,
+    /// The size of the font, which most players interpret as pixels.
+    pub size: Option<u16>,
+}
+
+impl<'a> FontAttributes<'a> {
+    /// Returns the value of an attribute, that is required to have one.
+    fn value(
+        name: &'static str,
+        span: &Spanned<&'a str>,
+        value: Option<Spanned<&'a str>>,
+    ) -> Result<Spanned<&'a str>, FontAttributesError> {
+        value.ok_or_else(|| FontAttributesError::missing_value(name, span.range()))
+    }
+}
+
+impl<'a> TryFrom<&Attributes<Spanned<&'a str>>> for FontAttributes<'a> {
+    type Error = FontAttributesError;
+
+    fn try_from(attributes: &Attributes<Spanned<&'a str>>) -> Result<Self, Self::Error> {
+        let mut result = Self::default();
+        let mut iterator = attributes.attributes();
+
+        while let Some(attribute) = iterator.next_spanned() {
+            let (name, value) = attribute?;
+
+            if name.eq_ignore_ascii_case("color") && result.color.is_none() {
+                let value = Self::value("color", &name, value)?;
+
+                result.color = Some(Color::try_from(value)?);
+            } else if name.eq_ignore_ascii_case("face") && result.face.is_none() {
+                result.face = Some(Self::value("face", &name, value)?.into_inner());
+            } else if name.eq_ignore_ascii_case("size") && result.size.is_none() {
+                let value = Self::value("size", &name, value)?;
+                let size = value
+                    .parse()
+                    .map_err(|error| FontAttributesError::invalid_size(error, value.range()))?;
+
+                result.size = Some(size);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    use crate::text::ColorError;
+    use crate::Buffer;
+
+    fn font(string: &str) -> Result<FontAttributes<'_>, FontAttributesError> {
+        FontAttributes::try_from(&Attributes::new_spanned(string, 6))
+    }
+
+    #[test]
+    fn test_font_attributes() {
+        assert_eq!(
+            font("color=red face=\"Times New Roman\" size='24'"),
+            Ok(FontAttributes {
+                color: Some(Color::Name("red")),
+                face: Some("Times New Roman"),
+                size: Some(24),
+            })
+        );
+
+        assert_eq!(
+            font("SIZE=12 unknown=\"?\" Color=#F00 color=blue size=x"),
+            Ok(FontAttributes {
+                color: Some(Color::Rgb {
+                    red: 0xFF,
+                    green: 0,
+                    blue: 0,
+                }),
+                face: None,
+                size: Some(12),
+            })
+        );
+
+        assert_eq!(font(""), Ok(FontAttributes::default()));
+    }
+
+    #[test]
+    fn test_font_attributes_errors() {
+        // the spans are relative to the start of the tag
+        assert_eq!(
+            font("face=Arial color=\"rgb(0, 300, 0)\""),
+            Err(FontAttributesError::from(ColorError::from(
+                Spanned::new("300".parse::<u8>().unwrap_err()).with_span(31..34)
+            )))
+        );
+
+        let error = font("color=red size=big").unwrap_err();
+        assert_eq!(error.span(), Some(21..24));
+        assert_eq!(
+            error,
+            FontAttributesError::invalid_size("big".parse::<u16>().unwrap_err(), 21..24)
+        );
+
+        assert_eq!(
+            font("face color=red"),
+            Err(FontAttributesError::missing_value("face", 6..10))
+        );
+
+        let error = font("color='red").unwrap_err();
+        assert_eq!(error.span(), Some(12..16));
+    }
+
+    #[test]
+    fn test_font_attributes_error_display() {
+        let mut buffer = crate::ArrayBuffer::<64>::new();
+
+        write!(buffer, "{}", font("color=\"#GG0000\"").unwrap_err()).unwrap();
+        assert_eq!(
+            buffer.as_str(),
+            "invalid `color` attribute: invalid digit found in string"
+        );
+
+        buffer.clear();
+        write!(buffer, "{}", font("size").unwrap_err()).unwrap();
+        assert_eq!(buffer.as_str(), "the `size` attribute is missing its value");
+    }
+}
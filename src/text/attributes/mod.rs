@@ -2,8 +2,11 @@ mod attribute;
 mod attributes;
 mod color;
 mod error;
+mod font;
+mod named_colors;
 
 pub use attribute::Attribute;
-pub use attributes::Attributes;
-pub use color::Color;
-pub use error::{ColorError, ParseAttributeError};
+pub use attributes::{Attributes, LazyAttributesIter};
+pub use color::{Color, SerializeColorConfig};
+pub use error::{ColorError, FontAttributesError, ParseAttributeError};
+pub use font::FontAttributes;
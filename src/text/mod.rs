@@ -1,7 +1,15 @@
 mod attributes;
+#[cfg(feature = "alloc")]
+mod reflow;
+#[cfg(feature = "alloc")]
+mod sdh;
 mod tags;
 mod text;
 
 pub use attributes::*;
-pub use tags::{ParsedTag, TagKind};
-pub use text::Text;
+#[cfg(feature = "alloc")]
+pub use reflow::ReflowOptions;
+#[cfg(feature = "alloc")]
+pub use sdh::SdhOptions;
+pub use tags::{ParseTagError, ParsedTag, TagKind};
+pub use text::{Text, TextEvent, TextIter};
@@ -0,0 +1,633 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem;
+
+use unicode_width::UnicodeWidthChar;
+
+use super::{TagKind, Text, TextEvent};
+
+/// Conjunctions in front of which a line is preferably broken.
+const ENGLISH_CLAUSE_WORDS: &[&str] = &[
+    "and", "but", "or", "nor", "so", "yet", "because", "although", "though", "if", "unless",
+    "when", "while", "where", "which", "who", "that",
+];
+
+/// Characters at the end of a sentence, after which a line is preferably
+/// broken.
+const SENTENCE_END: &[char] = &['.', '!', '?', '…', '。', '！', '？'];
+
+/// Characters at the end of a clause, after which a line is preferably broken.
+const CLAUSE_END: &[char] = &[',', ';', ':', '，', '；', '：', '、'];
+
+/// Punctuation that must not be placed at the start of a line.
+const CLOSING_PUNCTUATION: &[char] = &[
+    '，', '。', '！', '？', '、', '；', '：', '」', '』', '）', '》', '〉', '…', ')', ']', '"',
+];
+
+/// Punctuation that must not be placed at the end of a line.
+const OPENING_PUNCTUATION: &[char] = &['「', '『', '（', '《', '〈', '(', '['];
+
+/// Configures how [`Text::reflow`] breaks the text into lines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReflowOptions<'a> {
+    /// The maximum width of a line, measured in the number of columns a
+    /// terminal would use to display it (most CJK characters take up two
+    /// columns).
+    pub max_width: usize,
+    /// The maximum number of lines. If the text does not fit into this many
+    /// lines of [`ReflowOptions::max_width`], the lines are made wider
+    /// instead. Lines of a dialogue are always kept on separate lines, even if
+    /// there are more of them, and a value of `0` is treated like `1`.
+    pub max_lines: usize,
+    /// Words in front of which a line is preferably broken, because they start
+    /// a new clause. The comparison ignores the case of the words.
+    pub clause_words: &'a [&'a str],
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for ReflowOptions<'_> {
+    fn default() -> Self {
+        Self {
+            max_width: 42,
+            max_lines: 2,
+            clause_words: ENGLISH_CLAUSE_WORDS,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// A part of the text that can not be broken.
+#[derive(Debug, Clone, Default)]
+struct Word {
+    /// The text of the word, including all tags.
+    raw: String,
+    /// The text of the word without any tags.
+    visible: String,
+    width: usize,
+}
+
+/// The space in between two words, where the line might be broken.
+#[derive(Debug, Copy, Clone)]
+struct Gap {
+    /// Whether the words are separated by a space, if the line is not broken.
+    space: bool,
+    /// Whether there has been a line break in the original text.
+    newline: bool,
+    /// The number of tag pairs that are open at this point.
+    depth: usize,
+}
+
+#[derive(Debug, Default)]
+struct Tokenizer<'t> {
+    words: Vec<Word>,
+    gaps: Vec<Gap>,
+    current: Word,
+    pending: Option<Gap>,
+    open_tags: Vec<&'t str>,
+}
+
+/// Returns `true` for characters of languages, that do not separate words with
+/// spaces and can therefore be broken after any character.
+const fn is_ideographic(c: char) -> bool {
+    matches!(c,
+        '\u{3000}'..='\u{30FF}' // CJK punctuation, Hiragana and Katakana
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FFEF}' // fullwidth forms
+        | '\u{20000}'..='\u{2FFFF}'
+    )
+}
+
+impl<'t> Tokenizer<'t> {
+    fn finish_word(&mut self) {
+        if !self.current.raw.is_empty() {
+            self.words.push(mem::take(&mut self.current));
+        }
+    }
+
+    fn push_whitespace(&mut self, c: char) {
+        let depth = self.open_tags.len();
+
+        if !self.current.raw.is_empty() {
+            self.finish_word();
+            self.pending = Some(Gap {
+                space: true,
+                newline: c == '\n',
+                depth,
+            });
+        } else if let Some(gap) = &mut self.pending {
+            gap.newline |= c == '\n';
+            gap.depth = depth;
+        }
+    }
+
+    /// Called in front of anything that belongs to a word.
+    fn start_content(&mut self) {
+        if let Some(gap) = self.pending.take() {
+            self.gaps.push(gap);
+        }
+    }
+
+    fn push_char(&mut self, c: char) {
+        if c.is_whitespace() {
+            self.push_whitespace(c);
+            return;
+        }
+
+        self.start_content();
+
+        if let Some(previous) = self.current.visible.chars().last() {
+            if (is_ideographic(previous) || is_ideographic(c))
+                && !CLOSING_PUNCTUATION.contains(&c)
+                && !OPENING_PUNCTUATION.contains(&previous)
+            {
+                self.finish_word();
+                self.gaps.push(Gap {
+                    space: false,
+                    newline: false,
+                    depth: self.open_tags.len(),
+                });
+            }
+        }
+
+        self.current.raw.push(c);
+        self.current.visible.push(c);
+        self.current.width += c.width().unwrap_or(0);
+    }
+
+    /// Adds an override tag in braces, like `{\an8}`, which does not come in
+    /// pairs and is attached to the following word.
+    fn push_override(&mut self, raw: &str) {
+        self.start_content();
+        self.current.raw.push_str(raw);
+    }
+
+    fn push_tag(&mut self, raw: &str, name: &'t str, is_open: bool) {
+        if is_open {
+            self.start_content();
+            self.open_tags.push(name);
+            self.current.raw.push_str(raw);
+            return;
+        }
+
+        if let Some(position) = self
+            .open_tags
+            .iter()
+            .rposition(|open| open.eq_ignore_ascii_case(name))
+        {
+            self.open_tags.truncate(position);
+        }
+
+        // a closing tag is attached to the previous word, so it does not end
+        // up alone at the start of a line
+        if let (Some(gap), true, Some(word)) = (
+            &mut self.pending,
+            self.current.raw.is_empty(),
+            self.words.last_mut(),
+        ) {
+            gap.depth = self.open_tags.len();
+            word.raw.push_str(raw);
+        } else {
+            self.start_content();
+            self.current.raw.push_str(raw);
+        }
+    }
+}
+
+/// The cost of a layout, where a smaller value is better.
+///
+/// The fields are compared in order, so a layout with less overflowing text
+/// is always better than one with fewer lines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Cost {
+    overflow: usize,
+    lines: usize,
+    score: i64,
+}
+
+struct Layout<'w> {
+    words: &'w [Word],
+    gaps: &'w [Gap],
+    /// The gaps at which a line may be broken.
+    allowed: Vec<bool>,
+    /// The gaps at which a line must be broken.
+    forced: Vec<bool>,
+    max_width: usize,
+    clause_words: &'w [&'w str],
+}
+
+#[allow(clippy::cast_possible_wrap)]
+impl Layout<'_> {
+    fn width(&self, start: usize, end: usize) -> usize {
+        let words = self.words[start..end]
+            .iter()
+            .map(|word| word.width)
+            .sum::<usize>();
+        let spaces = self.gaps[start..end - 1]
+            .iter()
+            .filter(|gap| gap.space)
+            .count();
+
+        words + spaces
+    }
+
+    /// Returns the (negative) cost of breaking the line at the gap in front of
+    /// the word with the index `next`.
+    fn break_score(&self, next: usize) -> i64 {
+        let max_width = self.max_width as i64;
+        let last = self.words[next - 1].visible.chars().last();
+
+        if let Some(last) = last {
+            if SENTENCE_END.contains(&last) {
+                return -(max_width * max_width / 4);
+            } else if CLAUSE_END.contains(&last) {
+                return -(max_width * max_width / 5);
+            }
+        }
+
+        let starts_clause = self.clause_words.iter().any(|clause_word| {
+            self.words[next]
+                .visible
+                .trim_end_matches(|c: char| !c.is_alphanumeric())
+                .eq_ignore_ascii_case(clause_word)
+        });
+
+        if starts_clause {
+            -(max_width * max_width / 8)
+        } else {
+            0
+        }
+    }
+
+    /// Returns the best layout for the words and the indices at which the
+    /// lines start.
+    #[allow(clippy::needless_range_loop)]
+    fn solve(&self) -> Vec<usize> {
+        let count = self.words.len();
+        let max_width = self.max_width as i64;
+
+        // best[start][end] is the cost of the best layout for `words[start..]`,
+        // where the first line consists of `words[start..end]`
+        let mut best: Vec<Vec<Option<(Cost, usize)>>> = vec![vec![None; count + 1]; count + 1];
+
+        for start in (0..count).rev() {
+            for end in start + 1..=count {
+                // a line must not span over a forced break
+                if end - start > 1 && self.forced[end - 2] {
+                    break;
+                }
+
+                let width = self.width(start, end);
+
+                if width > self.max_width && end - start > 1 {
+                    break;
+                }
+
+                let line = Cost {
+                    overflow: width.saturating_sub(self.max_width),
+                    lines: 1,
+                    score: (width as i64) * (width as i64),
+                };
+
+                if end == count {
+                    best[start][end] = Some((line, count));
+                    continue;
+                }
+
+                if !self.allowed[end - 1] && !self.forced[end - 1] {
+                    continue;
+                }
+
+                let break_score = self.break_score(end);
+
+                let rest = (end + 1..=count)
+                    .filter_map(|next_end| {
+                        let (cost, _) = best[end][next_end]?;
+                        let next_width = self.width(end, next_end);
+
+                        // a bottom-heavy pyramid is preferred
+                        let pyramid = {
+                            if width > next_width {
+                                (width - next_width) as i64 * max_width / 2
+                            } else {
+                                0
+                            }
+                        };
+
+                        Some((
+                            Cost {
+                                overflow: cost.overflow,
+                                lines: cost.lines,
+                                score: cost.score + pyramid,
+                            },
+                            next_end,
+                        ))
+                    })
+                    .min();
+
+                if let Some((rest, next_end)) = rest {
+                    best[start][end] = Some((
+                        Cost {
+                            overflow: line.overflow + rest.overflow,
+                            lines: line.lines + rest.lines,
+                            score: line.score + rest.score + break_score,
+                        },
+                        next_end,
+                    ));
+                }
+            }
+        }
+
+        let mut result = vec![0];
+        let mut start = 0;
+        let mut end = (1..=count)
+            .filter_map(|end| best[0][end].map(|(cost, _)| (cost, end)))
+            .min()
+            .map_or(count, |(_, end)| end);
+
+        while end < count {
+            result.push(end);
+
+            let next_end = best[start][end].map_or(count, |(_, next_end)| next_end);
+            start = end;
+            end = next_end;
+        }
+
+        result
+    }
+}
+
+impl Text<'_> {
+    /// Breaks the text into at most [`ReflowOptions::max_lines`] lines, which
+    /// are at most [`ReflowOptions::max_width`] wide.
+    ///
+    /// The text is broken into as few lines as possible, which are balanced, so
+    /// that they have a similar width. An upper line, that is wider than the
+    /// line below it, is penalized in proportion to the difference, so of two
+    /// similarly balanced layouts the bottom-heavy one is chosen, but lines
+    /// that differ by only a few columns are preferred over a bottom-heavy
+    /// layout with a large difference. A line is preferably broken after
+    /// punctuation or in front of a word of [`ReflowOptions::clause_words`].
+    ///
+    /// A line is never broken inside of a tag or in between an opening and a
+    /// closing tag, unless the tags enclose all of the text. Lines of a
+    /// dialogue, which start with a `-`, are kept on separate lines.
+    ///
+    /// If a tag can not be parsed, the text is returned unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::text::ReflowOptions;
+    /// use libsrt::Text;
+    ///
+    /// let text = Text::from("I told you already, we are not going back there tonight.");
+    ///
+    /// assert_eq!(
+    ///     text.reflow(&ReflowOptions::default()),
+    ///     "I told you already,\nwe are not going back there tonight."
+    /// );
+    /// ```
+    #[must_use]
+    pub fn reflow(&self, options: &ReflowOptions<'_>) -> Text<'static> {
+        let raw = self.as_raw();
+        let mut tokenizer = Tokenizer::default();
+        let mut iterator = self.iter();
+
+        loop {
+            let start = iterator.offset();
+
+            match iterator.next() {
+                Some(Ok(TextEvent::Text(text))) => {
+                    for c in text.chars() {
+                        tokenizer.push_char(c);
+                    }
+                }
+                Some(Ok(TextEvent::Tag(tag))) if tag.kind() == TagKind::Braced => {
+                    tokenizer.push_override(&raw[start..iterator.offset()]);
+                }
+                Some(Ok(TextEvent::Tag(tag))) => {
+                    let end = iterator.offset();
+                    tokenizer.push_tag(&raw[start..end], tag.name(), tag.is_open());
+                }
+                Some(Err(_)) => return self.clone().into_owned(),
+                None => break,
+            }
+        }
+
+        tokenizer.finish_word();
+
+        let (words, gaps) = (tokenizer.words, tokenizer.gaps);
+
+        if words.is_empty() {
+            return self.clone().into_owned();
+        }
+
+        // tag pairs that enclose all of the text must not prevent line breaks
+        let min_depth = gaps.iter().map(|gap| gap.depth).min().unwrap_or_default();
+
+        let mut layout = Layout {
+            words: &words,
+            gaps: &gaps,
+            allowed: gaps.iter().map(|gap| gap.depth == min_depth).collect(),
+            forced: gaps
+                .iter()
+                .zip(&words[1..])
+                .map(|(gap, next)| gap.newline && next.visible.starts_with('-'))
+                .collect(),
+            max_width: options.max_width,
+            clause_words: options.clause_words,
+        };
+
+        let mut breaks = layout.solve();
+
+        // a forced break can not be removed, so the text needs at least one
+        // line more than there are forced breaks
+        let forced = layout.forced.iter().filter(|forced| **forced).count();
+        let max_lines = options.max_lines.max(forced + 1);
+
+        if breaks.len() > max_lines {
+            // the number of lines never increases with the width, so the
+            // smallest width at which the text fits is found with a binary
+            // search
+            let (mut low, mut high) = (options.max_width + 1, layout.width(0, words.len()));
+
+            while low < high {
+                layout.max_width = low + (high - low) / 2;
+
+                if layout.solve().len() <= max_lines {
+                    high = layout.max_width;
+                } else {
+                    low = layout.max_width + 1;
+                }
+            }
+
+            layout.max_width = low;
+            breaks = layout.solve();
+        }
+        let mut result = String::with_capacity(raw.len());
+
+        for (index, word) in words.iter().enumerate() {
+            if index > 0 {
+                if breaks.contains(&index) {
+                    result.push('\n');
+                } else if gaps[index - 1].space {
+                    result.push(' ');
+                }
+            }
+
+            result.push_str(&word.raw);
+        }
+
+        Text::from(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn reflow(text: &str) -> Text<'static> { Text::from(text).reflow(&ReflowOptions::default()) }
+
+    #[test]
+    fn test_reflow_short() {
+        assert_eq!(reflow("Hello world"), "Hello world");
+        assert_eq!(reflow("Hello\nworld"), "Hello world");
+        assert_eq!(reflow("  Hello  \n  world  "), "Hello world");
+        assert_eq!(reflow(""), "");
+    }
+
+    #[test]
+    fn test_reflow_balanced() {
+        assert_eq!(
+            reflow("This is a rather long subtitle line that needs to be wrapped somewhere"),
+            "This is a rather long subtitle line\nthat needs to be wrapped somewhere"
+        );
+        assert_eq!(
+            reflow("This is\na long piece of text broken up badly by somebody"),
+            "This is a long piece of text\nbroken up badly by somebody"
+        );
+    }
+
+    #[test]
+    fn test_reflow_bottom_heavy() {
+        let options = ReflowOptions {
+            max_width: 10,
+            ..ReflowOptions::default()
+        };
+
+        assert_eq!(
+            Text::from("Wait just here").reflow(&options),
+            "Wait\njust here"
+        );
+        assert_eq!(
+            Text::from("Wait just\nhere").reflow(&options),
+            "Wait\njust here"
+        );
+    }
+
+    #[test]
+    fn test_reflow_max_lines() {
+        let text = Text::from(
+            "This is a very long subtitle, that does not fit into two lines of the usual \
+             width, so both of its lines have to be wider",
+        );
+
+        assert_eq!(
+            text.reflow(&ReflowOptions::default()),
+            "This is a very long subtitle, that does not fit into two lines\nof the usual width, so both of its lines have to be wider"
+        );
+        assert_eq!(
+            text.reflow(&ReflowOptions {
+                max_lines: 3,
+                ..ReflowOptions::default()
+            }),
+            "This is a very long subtitle, that does\nnot fit into two lines of the usual width,\nso both of its lines have to be wider"
+        );
+        assert_eq!(
+            Text::from("one two three").reflow(&ReflowOptions {
+                max_width: 3,
+                max_lines: 0,
+                ..ReflowOptions::default()
+            }),
+            "one two three"
+        );
+        assert_eq!(
+            reflow("- Are you coming?\n- Yes, wait for me.\n- Me too."),
+            "- Are you coming?\n- Yes, wait for me.\n- Me too."
+        );
+    }
+
+    #[test]
+    fn test_reflow_punctuation() {
+        assert_eq!(
+            reflow("I told you already, we are not going back there tonight."),
+            "I told you already,\nwe are not going back there tonight."
+        );
+        assert_eq!(
+            reflow("We should leave the city right now because the storm is coming"),
+            "We should leave the city right now\nbecause the storm is coming"
+        );
+    }
+
+    #[test]
+    fn test_reflow_tags() {
+        assert_eq!(
+            reflow("<i>This is a rather long subtitle</i> line that needs to be wrapped"),
+            "<i>This is a rather long subtitle</i>\nline that needs to be wrapped"
+        );
+        assert_eq!(
+            reflow("<i>This is a rather long subtitle line that needs to be wrapped somewhere</i>"),
+            "<i>This is a rather long subtitle line\nthat needs to be wrapped somewhere</i>"
+        );
+        assert_eq!(
+            reflow("<font color=\"#ff0000\">Hello</font>   <b>world </b>"),
+            "<font color=\"#ff0000\">Hello</font> <b>world</b>"
+        );
+    }
+
+    #[test]
+    fn test_reflow_override_tags() {
+        assert_eq!(
+            reflow("{\\an8}This is a rather long subtitle line that needs to be wrapped somewhere"),
+            "{\\an8}This is a rather long subtitle line\nthat needs to be wrapped somewhere"
+        );
+        assert_eq!(
+            reflow("This is a rather long {\\i1}subtitle line that needs to be wrapped{\\i0}"),
+            "This is a rather long {\\i1}subtitle\nline that needs to be wrapped{\\i0}"
+        );
+    }
+
+    #[test]
+    fn test_reflow_dialogue() {
+        assert_eq!(
+            reflow("- Are you coming?\n- Yes, wait for me."),
+            "- Are you coming?\n- Yes, wait for me."
+        );
+    }
+
+    #[test]
+    fn test_reflow_width() {
+        assert_eq!(
+            reflow("作为第三首曲子，是至今为止最让我措手不及的一首"),
+            "作为第三首曲子，\n是至今为止最让我措手不及的一首"
+        );
+        assert_eq!(
+            reflow("今天录制了专辑的第三首歌「Fluegel」"),
+            "今天录制了专辑的第三首歌「Fluegel」"
+        );
+        assert_eq!(
+            Text::from("one two three").reflow(&ReflowOptions {
+                max_width: 3,
+                max_lines: 3,
+                ..ReflowOptions::default()
+            }),
+            "one\ntwo\nthree"
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{Text, TextEvent};
+
+const MUSIC_NOTES: &[char] = &['♪', '♫'];
+const DASHES: &[char] = &['-', '–', '—'];
+
+/// Configures which annotations [`Text::remove_sdh`] removes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct SdhOptions {
+    /// Removes descriptions in square brackets like `[DOOR SLAMS]`.
+    pub brackets: bool,
+    /// Removes descriptions in parentheses like `(laughs)`.
+    pub parentheses: bool,
+    /// Removes upper case speaker labels at the start of a line like `JOHN:`.
+    pub speaker_labels: bool,
+    /// Removes lyrics in between music notes like `♪ la la la ♪`.
+    pub music: bool,
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for SdhOptions {
+    fn default() -> Self {
+        Self {
+            brackets: true,
+            parentheses: true,
+            speaker_labels: true,
+            music: true,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Piece<'t> {
+    Tag {
+        raw: &'t str,
+        name: &'t str,
+        is_open: bool,
+    },
+    /// The index of the char in [`Annotated::chars`].
+    Char(usize),
+}
+
+/// The text split into tags and chars, where each char can be removed.
+#[derive(Debug, Default)]
+struct Annotated<'t> {
+    pieces: Vec<Piece<'t>>,
+    chars: Vec<char>,
+    removed: Vec<bool>,
+}
+
+impl Annotated<'_> {
+    /// Returns the ranges of the chars of each line, excluding the `'\n'`.
+    fn lines(&self) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        let mut start = 0;
+
+        for (index, c) in self.chars.iter().enumerate() {
+            if *c == '\n' {
+                result.push((start, index));
+                start = index + 1;
+            }
+        }
+
+        result.push((start, self.chars.len()));
+        result
+    }
+
+    fn remove(&mut self, start: usize, end: usize) {
+        for removed in &mut self.removed[start..end] {
+            *removed = true;
+        }
+    }
+
+    /// Removes everything from `open` up to and including the next `close`.
+    fn remove_enclosed(&mut self, open: char, close: char) {
+        let mut index = 0;
+
+        while index < self.chars.len() {
+            if self.chars[index] == open {
+                let end = self.chars[index + 1..]
+                    .iter()
+                    .position(|c| *c == close || *c == open)
+                    .map(|position| index + 1 + position);
+
+                if let Some(end) = end.filter(|end| self.chars[*end] == close) {
+                    self.remove(index, end + 1);
+                    index = end;
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    fn remove_music(&mut self) {
+        for (start, end) in self.lines() {
+            let notes = (start..end)
+                .filter(|index| MUSIC_NOTES.contains(&self.chars[*index]))
+                .collect::<Vec<_>>();
+
+            match notes.as_slice() {
+                [] => {}
+                // the lyrics start or end on another line
+                [note] => {
+                    let is_start = (start..*note).all(|index| {
+                        self.removed[index]
+                            || self.chars[index].is_whitespace()
+                            || DASHES.contains(&self.chars[index])
+                    });
+
+                    if is_start {
+                        self.remove(*note, end);
+                    } else {
+                        self.remove(start, *note + 1);
+                    }
+                }
+                [first, .., last] => self.remove(*first, *last + 1),
+            }
+        }
+    }
+
+    fn remove_speaker_labels(&mut self) {
+        for (start, end) in self.lines() {
+            let mut kept = (start..end)
+                .filter(|index| !self.removed[*index])
+                .peekable();
+
+            // skip the dash of a dialogue
+            while kept
+                .next_if(|index| {
+                    self.chars[*index].is_whitespace() || DASHES.contains(&self.chars[*index])
+                })
+                .is_some()
+            {}
+
+            let label_start = if let Some(index) = kept.peek() {
+                *index
+            } else {
+                continue;
+            };
+            let mut has_letters = false;
+
+            while let Some(index) = kept.next_if(|index| {
+                let c = self.chars[*index];
+                c.is_uppercase() || c.is_ascii_digit() || matches!(c, ' ' | '.' | '\'' | '-')
+            }) {
+                has_letters |= self.chars[index].is_uppercase();
+            }
+
+            let colon = kept.next_if(|index| self.chars[*index] == ':');
+
+            if let (Some(colon), true) = (colon, has_letters) {
+                let is_followed_by_space = kept
+                    .peek()
+                    .is_none_or(|index| self.chars[*index].is_whitespace());
+
+                if is_followed_by_space {
+                    self.remove(label_start, colon + 1);
+                }
+            }
+        }
+    }
+
+    /// Removes superfluous whitespace, empty lines and dashes, that are left
+    /// behind.
+    fn clean_up(&mut self) {
+        let lines = self.lines();
+        let mut non_empty = Vec::new();
+
+        for (start, end) in lines.iter().copied() {
+            let mut previous_is_whitespace = true;
+
+            for index in start..end {
+                if self.removed[index] {
+                    continue;
+                }
+
+                let is_whitespace = self.chars[index].is_whitespace();
+
+                if is_whitespace && previous_is_whitespace {
+                    self.removed[index] = true;
+                }
+
+                previous_is_whitespace = is_whitespace;
+            }
+
+            // trailing whitespace
+            if let Some(last) = (start..end).rev().find(|index| !self.removed[*index]) {
+                if self.chars[last].is_whitespace() {
+                    self.removed[last] = true;
+                }
+            }
+
+            let is_empty = (start..end)
+                .filter(|index| !self.removed[*index])
+                .all(|index| DASHES.contains(&self.chars[index]) || self.chars[index] == ':');
+
+            if is_empty {
+                self.remove(start, end);
+            } else {
+                non_empty.push((start, end));
+            }
+        }
+
+        // only keep the line breaks in front of non-empty lines
+        for (start, _) in lines.iter().skip(1) {
+            self.removed[start - 1] = true;
+        }
+
+        for (start, _) in non_empty.iter().skip(1) {
+            self.removed[start - 1] = false;
+        }
+
+        // a single line does not need a dialogue dash
+        if let [(start, end)] = non_empty.as_slice() {
+            let kept = (*start..*end)
+                .filter(|index| !self.removed[*index])
+                .take(2)
+                .collect::<Vec<_>>();
+
+            if let Some(dash) = kept
+                .first()
+                .filter(|index| DASHES.contains(&self.chars[**index]))
+            {
+                self.removed[*dash] = true;
+
+                if let Some(space) = kept
+                    .get(1)
+                    .filter(|index| self.chars[**index].is_whitespace())
+                {
+                    self.removed[*space] = true;
+                }
+            }
+        }
+    }
+
+    fn to_text(&self) -> Text<'static> {
+        let mut result: Vec<Piece<'_>> = Vec::with_capacity(self.pieces.len());
+
+        for piece in self.pieces.iter().copied() {
+            match piece {
+                Piece::Char(index) if self.removed[index] => {}
+                Piece::Tag {
+                    name,
+                    is_open: false,
+                    ..
+                } => {
+                    // drop tag pairs, which no longer contain any text
+                    if let Some(Piece::Tag {
+                        name: open_name,
+                        is_open: true,
+                        ..
+                    }) = result.last()
+                    {
+                        if open_name.eq_ignore_ascii_case(name) {
+                            result.pop();
+                            continue;
+                        }
+                    }
+
+                    result.push(piece);
+                }
+                _ => result.push(piece),
+            }
+        }
+
+        let mut text = String::new();
+
+        for piece in result {
+            match piece {
+                Piece::Tag { raw, .. } => text.push_str(raw),
+                Piece::Char(index) => text.push(self.chars[index]),
+            }
+        }
+
+        Text::from(text)
+    }
+}
+
+impl Text<'_> {
+    /// Removes annotations for the hearing impaired (SDH) from the text.
+    ///
+    /// Which annotations are removed can be configured with [`SdhOptions`].
+    /// The tags of the text are preserved, unless they no longer contain any
+    /// text. Afterwards, lines that are empty or only contain a dash are
+    /// removed and if only a single line of a dialogue remains, its dash is
+    /// removed too.
+    ///
+    /// If a tag can not be parsed, the text is returned unchanged.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::text::SdhOptions;
+    /// use libsrt::Text;
+    ///
+    /// let text = Text::from("- [DOOR SLAMS]\n- <i>JOHN: Who's there?</i>");
+    ///
+    /// assert_eq!(
+    ///     text.remove_sdh(&SdhOptions::default()),
+    ///     "<i>Who's there?</i>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn remove_sdh(&self, options: &SdhOptions) -> Text<'static> {
+        let raw = self.as_raw();
+        let mut annotated = Annotated::default();
+        let mut iterator = self.iter();
+
+        loop {
+            let start = iterator.offset();
+
+            match iterator.next() {
+                Some(Ok(TextEvent::Text(text))) => {
+                    for c in text.chars() {
+                        annotated.pieces.push(Piece::Char(annotated.chars.len()));
+                        annotated.chars.push(c);
+                        annotated.removed.push(false);
+                    }
+                }
+                Some(Ok(TextEvent::Tag(tag))) => {
+                    annotated.pieces.push(Piece::Tag {
+                        raw: &raw[start..iterator.offset()],
+                        name: tag.name(),
+                        is_open: tag.is_open(),
+                    });
+                }
+                Some(Err(_)) => return self.clone().into_owned(),
+                None => break,
+            }
+        }
+
+        if options.brackets {
+            annotated.remove_enclosed('[', ']');
+        }
+
+        if options.parentheses {
+            annotated.remove_enclosed('(', ')');
+        }
+
+        if options.music {
+            annotated.remove_music();
+        }
+
+        if options.speaker_labels {
+            annotated.remove_speaker_labels();
+        }
+
+        annotated.clean_up();
+        annotated.to_text()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn remove_sdh(text: &str) -> Text<'static> {
+        Text::from(text).remove_sdh(&SdhOptions::default())
+    }
+
+    #[test]
+    fn test_remove_descriptions() {
+        assert_eq!(remove_sdh("[DOOR SLAMS]"), "");
+        assert_eq!(remove_sdh("(laughs) That's funny."), "That's funny.");
+        assert_eq!(remove_sdh("That's (laughs) funny."), "That's funny.");
+        assert_eq!(remove_sdh("[thunder\nrumbling]\nRun!"), "Run!");
+        assert_eq!(remove_sdh("Hello (unclosed"), "Hello (unclosed");
+    }
+
+    #[test]
+    fn test_remove_speaker_labels() {
+        assert_eq!(remove_sdh("JOHN: Who's there?"), "Who's there?");
+        assert_eq!(remove_sdh("MAN 2: Over here!"), "Over here!");
+        assert_eq!(
+            remove_sdh("JOHN (whispering): Who's there?"),
+            "Who's there?"
+        );
+        assert_eq!(remove_sdh("Time: 10 o'clock"), "Time: 10 o'clock");
+        assert_eq!(remove_sdh("NOTE:no space"), "NOTE:no space");
+    }
+
+    #[test]
+    fn test_remove_music() {
+        assert_eq!(remove_sdh("♪ la la la ♪"), "");
+        assert_eq!(remove_sdh("♪ Never gonna\ngive you up ♪"), "");
+        assert_eq!(
+            remove_sdh("♪ humming ♪\nWhat a nice song."),
+            "What a nice song."
+        );
+    }
+
+    #[test]
+    fn test_remove_dashes() {
+        assert_eq!(remove_sdh("- [GASPS]\n- What was that?"), "What was that?");
+        assert_eq!(
+            remove_sdh("- [GASPS] Hey!\n- What was that?"),
+            "- Hey!\n- What was that?"
+        );
+        assert_eq!(remove_sdh("- JOHN:\n- Yes?"), "Yes?");
+    }
+
+    #[test]
+    fn test_preserve_markup() {
+        assert_eq!(remove_sdh("<i>[sighs]</i> <b>Fine.</b>"), "<b>Fine.</b>");
+        assert_eq!(
+            remove_sdh("<i>[sighs] Fine,\n(groans) I'll do it.</i>"),
+            "<i>Fine,\nI'll do it.</i>"
+        );
+        assert_eq!(
+            remove_sdh("<font color=\"#ffff00\">JOHN:</font> Hello"),
+            "Hello"
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let options = SdhOptions {
+            brackets: false,
+            speaker_labels: false,
+            ..SdhOptions::default()
+        };
+
+        assert_eq!(
+            Text::from("[DOOR SLAMS] JOHN: Hello (laughs)").remove_sdh(&options),
+            "[DOOR SLAMS] JOHN: Hello"
+        );
+    }
+}
@@ -1,15 +1,24 @@
+use thiserror::Error;
+
 use crate::utils::{Span, Spanned};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
 enum ParseTagErrorKind {
+    #[error("the tag is missing its brackets")]
     MissingBrackets,
     /// expected open tag, found close tag
+    // TODO: not reported by the parser yet
+    #[allow(dead_code)]
+    #[error("expected an opening tag, found a closing tag")]
     ExpectedOpenTag,
     /// expected close tag, found open tag
+    #[allow(dead_code)]
+    #[error("expected a closing tag, found an opening tag")]
     ExpectedCloseTag,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{0}")]
 pub struct ParseTagError(Spanned<ParseTagErrorKind>);
 
 impl ParseTagError {
@@ -27,12 +36,14 @@ impl ParseTagError {
 
     #[inline]
     #[must_use]
+    #[allow(dead_code)]
     pub(crate) fn expected_open_tag(range: impl Into<Span>) -> Self {
         Self::new(ParseTagErrorKind::ExpectedOpenTag, range)
     }
 
     #[inline]
     #[must_use]
+    #[allow(dead_code)]
     pub(crate) fn expected_close_tag(range: impl Into<Span>) -> Self {
         Self::new(ParseTagErrorKind::ExpectedCloseTag, range)
     }
@@ -1,6 +1,6 @@
 use core::convert::TryFrom;
 
-use super::super::Attributes;
+use super::super::{Attributes, FontAttributes, FontAttributesError};
 use super::ParseTagError;
 use crate::utils::Spanned;
 
@@ -19,7 +19,11 @@ pub struct ParsedTag<'name, T> {
 }
 
 impl<'name, T> ParsedTag<'name, T> {
-    pub fn new_open(name: &'name str, kind: TagKind, attributes: Option<Attributes<T>>) -> Self {
+    pub const fn new_open(
+        name: &'name str,
+        kind: TagKind,
+        attributes: Option<Attributes<T>>,
+    ) -> Self {
         Self {
             kind,
             name,
@@ -28,7 +32,11 @@ impl<'name, T> ParsedTag<'name, T> {
         }
     }
 
-    pub fn new_closed(name: &'name str, kind: TagKind, attributes: Option<Attributes<T>>) -> Self {
+    pub const fn new_closed(
+        name: &'name str,
+        kind: TagKind,
+        attributes: Option<Attributes<T>>,
+    ) -> Self {
         Self {
             kind,
             name,
@@ -37,7 +45,59 @@ impl<'name, T> ParsedTag<'name, T> {
         }
     }
 
-    pub fn kind(&self) -> TagKind { self.kind }
+    pub const fn kind(&self) -> TagKind { self.kind }
+
+    /// Returns the name of the tag, like `b` for `<b>` and `</b>`.
+    #[must_use]
+    pub const fn name(&self) -> &'name str { self.name }
+
+    /// Returns `true` if this is an opening tag like `<b>` and `false` for a
+    /// closing tag like `</b>`.
+    #[must_use]
+    pub const fn is_open(&self) -> bool { self.is_open }
+
+    /// Returns the attributes of the tag, like `color="red"` for
+    /// `<font color="red">`.
+    #[must_use]
+    pub const fn attributes(&self) -> Option<&Attributes<T>> { self.attributes.as_ref() }
+}
+
+impl<'a> ParsedTag<'a, Spanned<&'a str>> {
+    /// Parses the `color`, `face` and `size` attributes of a `<font>` tag.
+    ///
+    /// The name of the tag is not checked, so this should only be called for a
+    /// tag named `font`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, if the attributes can not be separated or if the
+    /// value of a known attribute is invalid. The span of the error points at
+    /// the invalid part of the attribute.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::text::{Color, TextEvent};
+    /// use libsrt::Text;
+    ///
+    /// let text = Text::from("<font color=red face=\"Times New Roman\">Hello</font>");
+    ///
+    /// let tag = match text.iter().next() {
+    ///     Some(Ok(TextEvent::Tag(tag))) => tag,
+    ///     other => panic!("expected a tag, found {:?}", other),
+    /// };
+    /// let font = tag.font_attributes()?;
+    ///
+    /// assert_eq!(font.color, Some(Color::Name("red")));
+    /// assert_eq!(font.face, Some("Times New Roman"));
+    /// assert_eq!(font.size, None);
+    /// # Ok::<(), libsrt::text::FontAttributesError>(())
+    /// ```
+    pub fn font_attributes(&self) -> Result<FontAttributes<'a>, FontAttributesError> {
+        self.attributes
+            .as_ref()
+            .map_or_else(|| Ok(FontAttributes::default()), FontAttributes::try_from)
+    }
 }
 
 macro_rules! implement_default_tags {
@@ -51,11 +111,11 @@ macro_rules! implement_default_tags {
     ),+ $(,)*) => {
         impl<'name, T> ParsedTag<'name, T> {
             $(
-                pub fn $open (kind: TagKind) -> Self {
+                pub const fn $open (kind: TagKind) -> Self {
                     Self::new_open($string, kind, None)
                 }
 
-                pub fn $closed (kind: TagKind) -> Self {
+                pub const fn $closed (kind: TagKind) -> Self {
                     Self::new_closed($string, kind, None)
                 }
             )*
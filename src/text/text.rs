@@ -1,19 +1,84 @@
 use core::convert::TryFrom;
 use core::fmt;
-use core::iter::Peekable;
-use core::ops::{Deref, RangeFrom};
-use core::str::CharIndices;
+use core::ops::{Deref, Range, RangeFrom};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
 
 use super::tags::{ParseTagError, ParsedTag};
-use crate::utils::Spanned;
+use crate::utils::{find_any_byte, find_byte, find_either_byte, Spanned};
+use crate::{Buffer, ExactSerializedLength, Serialize};
+
+/// The storage of a [`Text`], which can only be owned if the `alloc` feature
+/// is enabled.
+#[cfg(feature = "alloc")]
+type Inner<'a> = Cow<'a, str>;
+#[cfg(not(feature = "alloc"))]
+type Inner<'a> = &'a str;
 
 /// `Text` of a subtitle.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Text<'a> {
-    text: Spanned<&'a str>,
+    text: Spanned<Inner<'a>>,
 }
 
-impl<'a> Text<'a> {
+impl Text<'_> {
+    /// Returns an iterator over the tags and the text in between them.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::text::TextEvent;
+    /// use libsrt::Text;
+    ///
+    /// let text = Text::from("<b>example</b> text");
+    /// let mut iterator = text.iter().skip(1);
+    ///
+    /// assert_eq!(iterator.next(), Some(Ok(TextEvent::Text("example"))));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> TextIter<'_> {
+        TextIter::new(Spanned::new(self.as_raw()).map_span(|_| self.text.span()))
+    }
+
+    /// Converts the `Text` into one that does not borrow from the input.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_owned(self) -> Text<'static> {
+        Text {
+            text: self.text.map(|text| Cow::Owned(text.into_owned())),
+        }
+    }
+
+    /// Returns the text without any tags.
+    ///
+    /// Tags that can not be parsed are removed as well.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::Text;
+    ///
+    /// let text = Text::from("<b>example</b> <font color=red>text</font>");
+    ///
+    /// assert_eq!(text.plain_text(), "example text");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn plain_text(&self) -> String {
+        let mut result = String::with_capacity(self.len());
+
+        for event in self {
+            if let Ok(TextEvent::Text(text)) = event {
+                result.push_str(text);
+            }
+        }
+
+        result
+    }
+
     /// Returns the underlying string.
     ///
     /// ## Example
@@ -28,11 +93,31 @@ impl<'a> Text<'a> {
     #[inline]
     #[must_use]
     pub fn as_raw(&self) -> &str { &self.text }
+
+    /// Returns the byte range of the text in the input it has been parsed
+    /// from, if it is known.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::SubtitleIterator;
+    ///
+    /// let input = "1\n00:00:01,000 --> 00:00:02,000\nexample\n";
+    /// let subtitle = SubtitleIterator::from(input).next().unwrap()?;
+    ///
+    /// assert_eq!(subtitle.text().span(), Some(32..39));
+    /// # Ok::<(), libsrt::SubtitleError>(())
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.text.span().map(|span| span.start()..span.end())
+    }
 }
 
 impl PartialEq<str> for Text<'_> {
     #[inline]
-    fn eq(&self, other: &str) -> bool { *self.text == other }
+    fn eq(&self, other: &str) -> bool { self.as_raw() == other }
 }
 
 impl PartialEq<&str> for Text<'_> {
@@ -40,104 +125,160 @@ impl PartialEq<&str> for Text<'_> {
     fn eq(&self, other: &&str) -> bool { self == *other }
 }
 
-impl<'a> Deref for Text<'a> {
+impl Deref for Text<'_> {
     type Target = str;
 
-    fn deref(&self) -> &Self::Target { &*self.text }
+    fn deref(&self) -> &Self::Target { self.as_raw() }
 }
 
 impl<'a> From<(&'a str, RangeFrom<usize>)> for Text<'a> {
-    fn from(value: (&'a str, RangeFrom<usize>)) -> Self { Self { text: value.into() } }
+    fn from(value: (&'a str, RangeFrom<usize>)) -> Self {
+        Self {
+            text: Spanned::from(value).map(Into::into),
+        }
+    }
 }
 
 // TODO: remove?
 impl<'a> From<&'a str> for Text<'a> {
-    fn from(value: &'a str) -> Self {
+    fn from(value: &'a str) -> Self { Self::from((value, 0..)) }
+}
+
+#[cfg(feature = "alloc")]
+impl From<String> for Text<'_> {
+    fn from(value: String) -> Self {
         Self {
-            text: (value, 0..).into(),
+            text: Spanned::new(Cow::Owned(value)),
         }
     }
 }
 
 impl fmt::Display for Text<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.text) }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_raw()) }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl ExactSerializedLength for Text<'_> {
+    #[inline]
+    fn serialized_len(&self) -> usize { self.as_raw().len() }
+}
+
+impl<B: Buffer> Serialize<B> for Text<'_> {
+    type Error = B::Error;
+
+    fn serialize(&self, mut buffer: B) -> Result<(), Self::Error> {
+        buffer.reserve(self.serialized_len())?;
+        buffer.write_str(self.as_raw())?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TextEvent<'a> {
     Tag(ParsedTag<'a, Spanned<&'a str>>),
     Text(&'a str),
 }
 
-impl<'a> IntoIterator for Text<'a> {
-    type IntoIter = TextIter<'a>;
-    type Item = Result<TextEvent<'a>, ParseTagError>;
+impl<'t> IntoIterator for &'t Text<'_> {
+    type IntoIter = TextIter<'t>;
+    type Item = Result<TextEvent<'t>, ParseTagError>;
 
-    fn into_iter(self) -> Self::IntoIter { TextIter::new(self.text) }
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[must_use]
 pub struct TextIter<'a> {
     text: Spanned<&'a str>,
-    char_indices: Peekable<CharIndices<'a>>,
+    /// The byte offset in the text, at which the next event starts.
+    position: usize,
 }
 
 impl<'a> TextIter<'a> {
-    pub(crate) fn new(text: Spanned<&'a str>) -> Self {
-        Self {
-            text,
-            char_indices: text.char_indices().peekable(),
+    pub(crate) const fn new(text: Spanned<&'a str>) -> Self { Self { text, position: 0 } }
+
+    /// Returns the index of the `>`, that closes the tag starting at `start`,
+    /// ignoring every `>` inside of quotes.
+    fn find_tag_end(&self, start: usize) -> Option<usize> {
+        let mut outside_double_quotes = true;
+        let mut outside_single_quotes = true;
+        let mut position = start + 1;
+
+        while let Some(index) = find_any_byte([b'>', b'"', b'\''], &self.text[position..]) {
+            let index = position + index;
+
+            match self.text.as_bytes()[index] {
+                b'>' if outside_double_quotes && outside_single_quotes => return Some(index),
+                b'"' => outside_double_quotes = !outside_double_quotes,
+                b'\'' => outside_single_quotes = !outside_single_quotes,
+                _ => {}
+            }
+
+            position = index + 1;
         }
+
+        None
     }
 }
 
+impl TextIter<'_> {
+    /// Returns the byte offset in the text, at which the next event starts.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::Text;
+    ///
+    /// let text = Text::from("<b>example</b> text");
+    /// let mut iterator = text.iter();
+    ///
+    /// assert_eq!(iterator.offset(), 0);
+    /// iterator.next();
+    /// assert_eq!(iterator.offset(), 3);
+    /// ```
+    #[must_use]
+    pub const fn offset(&self) -> usize { self.position }
+}
+
 impl<'a> Iterator for TextIter<'a> {
     type Item = Result<TextEvent<'a>, ParseTagError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let iterator = self.char_indices.by_ref();
-
-        let (start_index, start_char) = *iterator.peek()?;
+        let start_index = self.position;
 
-        // TODO: braces should be possible too!
+        if start_index >= self.text.len() {
+            return None;
+        }
 
         // check if the next event is a tag
-        if start_char == '<' {
-            iterator.next();
-
-            let mut outside_double_quotes = true;
-            let mut outside_single_quotes = true;
-
-            for (next_index, next_char) in iterator {
-                if next_char == '>' && outside_double_quotes && outside_single_quotes {
-                    let tag = self.text.get(start_index..=next_index)?;
-
-                    return Some(ParsedTag::try_from(tag).map(TextEvent::Tag));
-                } else if next_char == '"' {
-                    outside_double_quotes = !outside_double_quotes;
-                } else if next_char == '\'' {
-                    outside_single_quotes = !outside_single_quotes;
-                }
+        let bracket = self.text.as_bytes()[start_index];
+
+        if bracket == b'<' || bracket == b'{' {
+            let end_index = if bracket == b'<' {
+                self.find_tag_end(start_index)
+            } else {
+                // the override tags of ASS, like `{\an8}`, end at the first `}`
+                find_byte(b'}', &self.text[start_index..]).map(|index| start_index + index)
+            };
+
+            if let Some(end_index) = end_index {
+                self.position = end_index + 1;
+                let tag = self.text.get(start_index..=end_index)?;
+
+                return Some(ParsedTag::try_from(tag).map(TextEvent::Tag));
             }
 
-            // could not find a closing '>', therefore the rest of self.text
-            // is simple text that starts with a '<'
+            // could not find the closing bracket, therefore the rest of
+            // self.text is simple text that starts with the opening one
+            self.position = self.text.len();
             return Some(Ok(TextEvent::Text(&self.text[start_index..])));
-        } else {
-            // this is text until the next char would be '<'
-            loop {
-                if let Some((next_index, next_char)) = iterator.peek().copied() {
-                    if next_char == '<' {
-                        return Some(Ok(TextEvent::Text(&self.text[start_index..next_index])));
-                    } else {
-                        iterator.next();
-                    }
-                } else {
-                    return Some(Ok(TextEvent::Text(&self.text[start_index..])));
-                }
-            }
         }
+
+        // this is text until the next char would be '<' or '{'
+        self.position = find_either_byte([b'<', b'{'], &self.text[start_index..])
+            .map_or(self.text.len(), |index| start_index + index);
+
+        Some(Ok(TextEvent::Text(&self.text[start_index..self.position])))
     }
 }
 
@@ -150,11 +291,11 @@ mod tests {
 
     #[test]
     fn test_text_iter() {
-        let mut iterator = Text::from(concat!(
+        let text = Text::from(concat!(
             "<font color=#AABBCC>text with color #AABBCC</font>\n",
             "<b>fat text</b><i><u>underlined and italic text</i>underlined text</u>"
-        ))
-        .into_iter();
+        ));
+        let mut iterator = text.iter();
 
         assert_eq!(
             iterator.next(),
@@ -227,11 +368,11 @@ mod tests {
 
     #[test]
     fn test_text_iter_ignore() {
-        let mut iterator = Text::from(concat!(
+        let text = Text::from(concat!(
             "<font color=\"<\">text></font>\n",
             "<<b>fat> text</<b>"
-        ))
-        .into_iter();
+        ));
+        let mut iterator = text.iter();
 
         assert_eq!(
             iterator.next(),
@@ -275,7 +416,8 @@ mod tests {
 
     #[test]
     fn test_text_iter_quoted_string() {
-        let mut iterator = Text::from("<font color=\">\">text></font>").into_iter();
+        let text = Text::from("<font color=\">\">text></font>");
+        let mut iterator = text.iter();
 
         assert_eq!(
             iterator.next(),
@@ -297,4 +439,51 @@ mod tests {
 
         assert_eq!(iterator.next(), None);
     }
+
+    #[test]
+    fn test_text_iter_braced() {
+        let text = Text::from("{\\an8}top <i>line</i>{ unclosed");
+        let mut iterator = text.iter();
+
+        assert_eq!(
+            iterator.next(),
+            Some(Ok(TextEvent::Tag(ParsedTag::new_open(
+                "\\an8",
+                TagKind::Braced,
+                None
+            ))))
+        );
+        assert_eq!(iterator.next(), Some(Ok(TextEvent::Text("top "))));
+        assert!(iterator.next().is_some());
+        assert_eq!(iterator.next(), Some(Ok(TextEvent::Text("line"))));
+        assert!(iterator.next().is_some());
+        assert_eq!(iterator.next(), Some(Ok(TextEvent::Text("{ unclosed"))));
+        assert_eq!(iterator.next(), None);
+    }
+
+    #[test]
+    fn test_text_iter_unclosed_tag() {
+        let text = Text::from("ä<i>ö</i><b 'x>' ü");
+        let mut iterator = text.iter();
+
+        assert_eq!(iterator.next(), Some(Ok(TextEvent::Text("ä"))));
+        assert_eq!(iterator.offset(), 2);
+        assert!(iterator.next().is_some());
+        assert_eq!(iterator.offset(), 5);
+        assert_eq!(iterator.next(), Some(Ok(TextEvent::Text("ö"))));
+        assert!(iterator.next().is_some());
+        assert_eq!(iterator.offset(), 11);
+
+        // the `>` inside of the quotes does not close the tag
+        assert_eq!(iterator.next(), Some(Ok(TextEvent::Text("<b 'x>' ü"))));
+        assert_eq!(iterator.offset(), text.as_raw().len());
+        assert_eq!(iterator.next(), None);
+    }
+
+    #[test]
+    fn test_serialized_len() {
+        for text in ["", "plain text", "<i>Ünïcödé</i>\n{\\an8}second line"] {
+            crate::serialize::tests::assert_exact_length(&Text::from(text));
+        }
+    }
 }
@@ -0,0 +1,104 @@
+use core::num::ParseIntError;
+
+use thiserror::Error;
+
+use crate::utils::{Span, Spanned};
+
+/// The error of parsing a [`Timecode`] or converting it with a
+/// [`TimecodeConverter`], whose span points at the invalid part of the input
+/// (if there is one).
+///
+/// [`Timecode`]: super::Timecode
+/// [`TimecodeConverter`]: super::TimecodeConverter
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("{0}")]
+pub struct TimecodeError(Spanned<TimecodeErrorKind>);
+
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum TimecodeErrorKind {
+    /// A component of the timecode is not a number.
+    #[error("{0}")]
+    ParseIntError(ParseIntError),
+    /// The timecode is not of the form `HH:MM:SS:FF` or `HH:MM:SS;FF`.
+    #[error("invalid timecode")]
+    InvalidFormat,
+    /// The minutes, seconds or frames are too large.
+    #[error("value out of range")]
+    OutOfRange,
+    /// The frame is skipped by drop-frame timecode, like `00:01:00;00`.
+    #[error("the frame does not exist in drop-frame timecode")]
+    DroppedFrame,
+    /// Drop-frame timecode is not defined for the frame rate.
+    #[error("drop-frame timecode is only supported for 29.97 and 59.94 fps")]
+    UnsupportedDropFrame,
+    /// The timecode is drop-frame, but the converter is not (or vice versa).
+    #[error("the timecode does not match the drop-frame setting of the converter")]
+    DropFrameMismatch,
+    /// The timecode is before the start of the programme or `00:00:00:00`.
+    #[error("the timecode is before the start of the programme")]
+    BeforeStart,
+}
+
+impl From<ParseIntError> for TimecodeErrorKind {
+    fn from(value: ParseIntError) -> Self { Self::ParseIntError(value) }
+}
+
+impl TimecodeError {
+    #[inline]
+    #[must_use]
+    fn new(kind: TimecodeErrorKind, range: impl Into<Span>) -> Self {
+        Self(Spanned::new(kind).with_span(range))
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) fn invalid_format(range: impl Into<Span>) -> Self {
+        Self::new(TimecodeErrorKind::InvalidFormat, range)
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) fn out_of_range(range: impl Into<Span>) -> Self {
+        Self::new(TimecodeErrorKind::OutOfRange, range)
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) const fn frames_out_of_range() -> Self {
+        Self(Spanned::new(TimecodeErrorKind::OutOfRange))
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) const fn dropped_frame() -> Self {
+        Self(Spanned::new(TimecodeErrorKind::DroppedFrame))
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) const fn unsupported_drop_frame() -> Self {
+        Self(Spanned::new(TimecodeErrorKind::UnsupportedDropFrame))
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) const fn drop_frame_mismatch() -> Self {
+        Self(Spanned::new(TimecodeErrorKind::DropFrameMismatch))
+    }
+
+    #[inline]
+    #[must_use]
+    pub(crate) const fn before_start() -> Self {
+        Self(Spanned::new(TimecodeErrorKind::BeforeStart))
+    }
+}
+
+#[doc(hidden)]
+impl<E> From<Spanned<E>> for TimecodeError
+where
+    TimecodeErrorKind: From<E>,
+{
+    fn from(value: Spanned<E>) -> Self { Self(value.map(Into::into)) }
+}
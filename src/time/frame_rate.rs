@@ -0,0 +1,94 @@
+/// The frame rate of a video in frames per second, which is stored as a
+/// fraction to represent rates like `29.97` (`30000/1001`) exactly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FrameRate {
+    numerator: u32,
+    denominator: u32,
+}
+
+impl FrameRate {
+    /// `24000/1001` frames per second (film transferred to NTSC).
+    pub const FPS_23_976: Self = Self::new_unchecked(24_000, 1001);
+    /// `24` frames per second (film).
+    pub const FPS_24: Self = Self::new_unchecked(24, 1);
+    /// `25` frames per second (PAL).
+    pub const FPS_25: Self = Self::new_unchecked(25, 1);
+    /// `30000/1001` frames per second (NTSC).
+    pub const FPS_29_97: Self = Self::new_unchecked(30_000, 1001);
+    /// `30` frames per second.
+    pub const FPS_30: Self = Self::new_unchecked(30, 1);
+    /// `50` frames per second.
+    pub const FPS_50: Self = Self::new_unchecked(50, 1);
+    /// `60000/1001` frames per second.
+    pub const FPS_59_94: Self = Self::new_unchecked(60_000, 1001);
+    /// `60` frames per second.
+    pub const FPS_60: Self = Self::new_unchecked(60, 1);
+
+    #[must_use]
+    const fn new_unchecked(numerator: u32, denominator: u32) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Creates a frame rate of `numerator / denominator` frames per second.
+    ///
+    /// Returns `None` if either the `numerator` or the `denominator` is zero.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::time::FrameRate;
+    ///
+    /// assert_eq!(FrameRate::new(30_000, 1001), Some(FrameRate::FPS_29_97));
+    /// assert_eq!(FrameRate::new(25, 0), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new(numerator: u32, denominator: u32) -> Option<Self> {
+        if numerator == 0 || denominator == 0 {
+            None
+        } else {
+            Some(Self::new_unchecked(numerator, denominator))
+        }
+    }
+
+    /// Returns the numerator of the frame rate.
+    #[inline]
+    #[must_use]
+    pub const fn numerator(self) -> u32 { self.numerator }
+
+    /// Returns the denominator of the frame rate.
+    #[inline]
+    #[must_use]
+    pub const fn denominator(self) -> u32 { self.denominator }
+
+    /// Returns the number of frames per second rounded to the nearest integer,
+    /// which is the number of frames that are counted per second in a
+    /// timecode (for example `30` for `29.97`).
+    #[inline]
+    #[must_use]
+    pub const fn nominal(self) -> u32 { (self.numerator + self.denominator / 2) / self.denominator }
+
+    /// Returns the number of frames, that are dropped at the start of each
+    /// minute (except every tenth minute) in drop-frame timecode or `None` if
+    /// drop-frame timecode is not defined for the frame rate.
+    ///
+    /// Drop-frame timecode only exists for `29.97` and `59.94` frames per
+    /// second.
+    #[inline]
+    #[must_use]
+    pub const fn dropped_frames(self) -> Option<u32> {
+        if self.denominator == 1001 && self.nominal().is_multiple_of(30) {
+            Some(self.nominal() / 15)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the (possibly inexact) number of frames per second.
+    #[inline]
+    #[must_use]
+    pub fn as_f64(self) -> f64 { f64::from(self.numerator) / f64::from(self.denominator) }
+}
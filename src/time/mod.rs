@@ -0,0 +1,9 @@
+mod error;
+mod frame_rate;
+mod timecode;
+mod timestamp;
+
+pub use error::TimecodeError;
+pub use frame_rate::FrameRate;
+pub use timecode::{Timecode, TimecodeConverter};
+pub use timestamp::{ParseMode, SerializeTimestampConfig, Timestamp};
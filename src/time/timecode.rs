@@ -0,0 +1,513 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
+
+use super::{FrameRate, TimecodeError, Timestamp};
+use crate::utils::Spanned;
+
+/// A SMPTE timecode of the form `HH:MM:SS:FF` or `HH:MM:SS;FF` for
+/// drop-frame timecode.
+///
+/// A `Timecode` only counts frames, to convert it into a [`Duration`] a
+/// [`TimecodeConverter`] is needed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timecode {
+    hours: u32,
+    minutes: u8,
+    seconds: u8,
+    frames: u8,
+    drop_frame: bool,
+}
+
+impl Timecode {
+    #[must_use]
+    const fn new_(
+        hours: u32,
+        minutes: u8,
+        seconds: u8,
+        frames: u8,
+        drop_frame: bool,
+    ) -> Option<Self> {
+        if minutes >= 60 || seconds >= 60 {
+            return None;
+        }
+
+        Some(Self {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            drop_frame,
+        })
+    }
+
+    /// Creates a non-drop-frame `Timecode`.
+    ///
+    /// Returns `None` if the `minutes` or `seconds` are not less than `60`.
+    #[inline]
+    #[must_use]
+    pub const fn new(hours: u32, minutes: u8, seconds: u8, frames: u8) -> Option<Self> {
+        Self::new_(hours, minutes, seconds, frames, false)
+    }
+
+    /// Creates a drop-frame `Timecode`.
+    ///
+    /// Returns `None` if the `minutes` or `seconds` are not less than `60`.
+    #[inline]
+    #[must_use]
+    pub const fn new_drop_frame(hours: u32, minutes: u8, seconds: u8, frames: u8) -> Option<Self> {
+        Self::new_(hours, minutes, seconds, frames, true)
+    }
+
+    /// Returns the hours of the `Timecode`.
+    #[inline]
+    #[must_use]
+    pub const fn hours(self) -> u32 { self.hours }
+
+    /// Returns the minutes of the `Timecode`.
+    #[inline]
+    #[must_use]
+    pub const fn minutes(self) -> u8 { self.minutes }
+
+    /// Returns the seconds of the `Timecode`.
+    #[inline]
+    #[must_use]
+    pub const fn seconds(self) -> u8 { self.seconds }
+
+    /// Returns the frames of the `Timecode`.
+    #[inline]
+    #[must_use]
+    pub const fn frames(self) -> u8 { self.frames }
+
+    /// Returns `true` if this is a drop-frame `Timecode`.
+    #[inline]
+    #[must_use]
+    pub const fn is_drop_frame(self) -> bool { self.drop_frame }
+
+    fn parse(input: Spanned<&str>) -> Result<Self, TimecodeError> {
+        if let [Some(hours), Some(minutes), Some(seconds_frames)] = input.split_at_most::<_, 3>(':')
+        {
+            let (seconds, frames, drop_frame) = {
+                match seconds_frames.split_once(';') {
+                    (seconds, Some(frames)) => (seconds, frames, true),
+                    (_, None) => {
+                        if let (seconds, Some(frames)) = seconds_frames.split_once(':') {
+                            (seconds, frames, false)
+                        } else {
+                            return Err(TimecodeError::invalid_format(input.range()));
+                        }
+                    }
+                }
+            };
+
+            let hours = hours
+                .parse::<u32>()
+                .map_err(|error| Spanned::new(error).with_span(hours.range()))?;
+            let minutes_value = minutes.parse_radix_u8(10)?;
+            let seconds_value = seconds.parse_radix_u8(10)?;
+            let frames = frames.parse_radix_u8(10)?;
+
+            if minutes_value >= 60 {
+                return Err(TimecodeError::out_of_range(minutes.range()));
+            } else if seconds_value >= 60 {
+                return Err(TimecodeError::out_of_range(seconds.range()));
+            }
+
+            return Ok(Self {
+                hours,
+                minutes: minutes_value,
+                seconds: seconds_value,
+                frames,
+                drop_frame,
+            });
+        }
+
+        Err(TimecodeError::invalid_format(input.range()))
+    }
+}
+
+impl FromStr for Timecode {
+    type Err = TimecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::parse(Spanned::new(s)) }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, separator, self.frames
+        )
+    }
+}
+
+/// Converts between [`Timecode`]s and the [`Duration`]s used by subtitles.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::time::{FrameRate, TimecodeConverter};
+/// use std::time::Duration;
+///
+/// // the programme starts at 10:00:00:00 on the broadcast master
+/// let converter = TimecodeConverter::new(FrameRate::FPS_25).with_start("10:00:00:00".parse()?);
+///
+/// assert_eq!(
+///     converter.to_duration("10:00:01:05".parse()?)?,
+///     Duration::from_millis(1_200)
+/// );
+/// assert_eq!(
+///     converter.timecode_from_duration(Duration::from_millis(1_200))?,
+///     "10:00:01:05".parse()?
+/// );
+/// # Ok::<(), libsrt::time::TimecodeError>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimecodeConverter {
+    rate: FrameRate,
+    drop_frame: bool,
+    start: Option<Timecode>,
+}
+
+impl TimecodeConverter {
+    /// Creates a converter for non-drop-frame timecode, which starts at
+    /// `00:00:00:00`.
+    #[inline]
+    #[must_use]
+    pub const fn new(rate: FrameRate) -> Self {
+        Self {
+            rate,
+            drop_frame: false,
+            start: None,
+        }
+    }
+
+    /// Creates a converter for drop-frame timecode, which starts at
+    /// `00:00:00;00`.
+    ///
+    /// Returns `None` if drop-frame timecode is not defined for the `rate`
+    /// (see [`FrameRate::dropped_frames`]).
+    #[inline]
+    #[must_use]
+    pub const fn new_drop_frame(rate: FrameRate) -> Option<Self> {
+        if rate.dropped_frames().is_none() {
+            return None;
+        }
+
+        Some(Self {
+            rate,
+            drop_frame: true,
+            start: None,
+        })
+    }
+
+    /// Sets the `Timecode` of the start of the programme (for example
+    /// `10:00:00:00`), which corresponds to a [`Duration`] of zero.
+    #[inline]
+    #[must_use]
+    pub const fn with_start(mut self, start: Timecode) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Returns the [`FrameRate`] of the converter.
+    #[inline]
+    #[must_use]
+    pub const fn rate(self) -> FrameRate { self.rate }
+
+    /// Returns the number of frames since `00:00:00:00`.
+    fn frame_number(self, timecode: Timecode) -> Result<i128, TimecodeError> {
+        let nominal = self.rate.nominal();
+
+        if u32::from(timecode.frames) >= nominal {
+            return Err(TimecodeError::frames_out_of_range());
+        }
+
+        let total_minutes = i128::from(timecode.hours) * 60 + i128::from(timecode.minutes);
+        let mut result = (total_minutes * 60 + i128::from(timecode.seconds)) * i128::from(nominal)
+            + i128::from(timecode.frames);
+
+        if timecode.drop_frame != self.drop_frame {
+            return Err(if self.rate.dropped_frames().is_none() {
+                TimecodeError::unsupported_drop_frame()
+            } else {
+                TimecodeError::drop_frame_mismatch()
+            });
+        }
+
+        if self.drop_frame {
+            let dropped = self
+                .rate
+                .dropped_frames()
+                .ok_or_else(TimecodeError::unsupported_drop_frame)?;
+
+            // the first frames of each minute are skipped, except for every tenth minute
+            if timecode.seconds == 0
+                && !timecode.minutes.is_multiple_of(10)
+                && u32::from(timecode.frames) < dropped
+            {
+                return Err(TimecodeError::dropped_frame());
+            }
+
+            result -= i128::from(dropped) * (total_minutes - total_minutes / 10);
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the `Timecode` of the frame with the provided number.
+    fn timecode(self, frame_number: i128) -> Result<Timecode, TimecodeError> {
+        if frame_number < 0 {
+            return Err(TimecodeError::before_start());
+        }
+
+        let nominal = i128::from(self.rate.nominal());
+        let mut frames = frame_number;
+
+        if self.drop_frame {
+            let dropped = i128::from(
+                self.rate
+                    .dropped_frames()
+                    .ok_or_else(TimecodeError::unsupported_drop_frame)?,
+            );
+            let frames_per_minute = nominal * 60 - dropped;
+            let frames_per_ten_minutes = nominal * 60 * 10 - dropped * 9;
+
+            let tens = frames / frames_per_ten_minutes;
+            let remainder = frames % frames_per_ten_minutes;
+
+            frames += dropped * 9 * tens;
+
+            if remainder > dropped {
+                frames += dropped * ((remainder - dropped) / frames_per_minute);
+            }
+        }
+
+        let component =
+            |value: i128| u8::try_from(value).map_err(|_| TimecodeError::frames_out_of_range());
+
+        Ok(Timecode {
+            hours: u32::try_from(frames / nominal / 3600)
+                .map_err(|_| TimecodeError::frames_out_of_range())?,
+            minutes: component(frames / nominal / 60 % 60)?,
+            seconds: component(frames / nominal % 60)?,
+            frames: component(frames % nominal)?,
+            drop_frame: self.drop_frame,
+        })
+    }
+
+    fn start_frame_number(self) -> Result<i128, TimecodeError> {
+        self.start.map_or(Ok(0), |start| self.frame_number(start))
+    }
+
+    /// Converts the `timecode` into a [`Timestamp`] relative to the start of
+    /// the programme, which is negative if the `timecode` is before the start.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the `timecode` (or the start) does not exist with
+    /// the [`FrameRate`] of the converter or if it is drop-frame timecode,
+    /// but the converter is not (or vice versa).
+    pub fn to_timestamp(self, timecode: Timecode) -> Result<Timestamp, TimecodeError> {
+        let frames = self.frame_number(timecode)? - self.start_frame_number()?;
+
+        Ok(Timestamp::from_frames(frames, self.rate))
+    }
+
+    /// Converts the `timecode` into a [`Duration`] relative to the start of
+    /// the programme.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the `timecode` is before the start of the programme,
+    /// if it does not exist with the [`FrameRate`] of the converter or if its
+    /// drop-frame setting does not match the converter.
+    pub fn to_duration(self, timecode: Timecode) -> Result<Duration, TimecodeError> {
+        self.to_timestamp(timecode)?
+            .to_duration()
+            .ok_or_else(TimecodeError::before_start)
+    }
+
+    /// Converts a [`Timestamp`] relative to the start of the programme into
+    /// the `Timecode` of the nearest frame.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the resulting `Timecode` would be before
+    /// `00:00:00:00` or if the start of the programme does not exist with the
+    /// [`FrameRate`] of the converter.
+    pub fn timecode_from_timestamp(self, timestamp: Timestamp) -> Result<Timecode, TimecodeError> {
+        let frames = timestamp.round_to_frames(self.rate).to_frames(self.rate);
+
+        self.timecode(frames + self.start_frame_number()?)
+    }
+
+    /// Converts a [`Duration`] relative to the start of the programme into the
+    /// `Timecode` of the nearest frame.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the start of the programme does not exist with the
+    /// [`FrameRate`] of the converter.
+    #[inline]
+    pub fn timecode_from_duration(self, duration: Duration) -> Result<Timecode, TimecodeError> {
+        self.timecode_from_timestamp(Timestamp::from(duration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    fn timecode(string: &str) -> Timecode { string.parse().unwrap() }
+
+    #[test]
+    fn test_parse_timecode() {
+        assert_eq!(
+            "01:02:03:04".parse(),
+            Ok(Timecode::new(1, 2, 3, 4).unwrap())
+        );
+        assert_eq!(
+            "01:02:03;04".parse(),
+            Ok(Timecode::new_drop_frame(1, 2, 3, 4).unwrap())
+        );
+        assert_eq!(
+            "01:02:60:04".parse::<Timecode>(),
+            Err(TimecodeError::out_of_range(6..8))
+        );
+        assert_eq!(
+            "01:02:03".parse::<Timecode>(),
+            Err(TimecodeError::invalid_format(0..8))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_display_timecode() {
+        assert_eq!(timecode("1:02:03:04").to_string(), "01:02:03:04");
+        assert_eq!(timecode("10:00:00;02").to_string(), "10:00:00;02");
+    }
+
+    #[test]
+    fn test_non_drop_frame() {
+        let converter = TimecodeConverter::new(FrameRate::FPS_25);
+
+        assert_eq!(
+            converter.to_duration(timecode("00:01:00:12")),
+            Ok(Duration::from_millis(60_480))
+        );
+        assert_eq!(
+            converter.timecode_from_duration(Duration::from_millis(60_480)),
+            Ok(timecode("00:01:00:12"))
+        );
+        assert_eq!(
+            converter.to_duration(timecode("00:00:00:25")),
+            Err(TimecodeError::frames_out_of_range())
+        );
+        assert_eq!(
+            converter.to_duration(timecode("00:00:00;02")),
+            Err(TimecodeError::unsupported_drop_frame())
+        );
+    }
+
+    #[test]
+    fn test_drop_frame() {
+        let converter = TimecodeConverter::new_drop_frame(FrameRate::FPS_29_97).unwrap();
+
+        // frame 1800 at 30000/1001 fps
+        assert_eq!(
+            converter.to_duration(timecode("00:01:00;02")),
+            Ok(Duration::from_millis(60_060))
+        );
+        assert_eq!(
+            converter.timecode_from_duration(Duration::from_millis(60_060)),
+            Ok(timecode("00:01:00;02"))
+        );
+        assert_eq!(
+            converter.timecode_from_duration(Duration::from_millis(60_027)),
+            Ok(timecode("00:00:59;29"))
+        );
+        assert_eq!(
+            converter.to_duration(timecode("00:01:00;00")),
+            Err(TimecodeError::dropped_frame())
+        );
+
+        // frame 17982 is 00:10:00;00, because no frames are dropped every tenth minute
+        assert_eq!(
+            converter.to_timestamp(timecode("00:10:00;00")),
+            Ok(Timestamp::from_frames(17_982, FrameRate::FPS_29_97))
+        );
+        assert_eq!(
+            converter.timecode_from_timestamp(Timestamp::from_frames(17_982, FrameRate::FPS_29_97)),
+            Ok(timecode("00:10:00;00"))
+        );
+
+        // an hour of drop-frame timecode is (almost) an hour of real time
+        assert_eq!(
+            converter
+                .to_timestamp(timecode("01:00:00;00"))
+                .map(Timestamp::round_to_millis),
+            Ok(Timestamp::from_millis(3_599_996))
+        );
+
+        for frames in 0..20_000 {
+            let timecode = converter
+                .timecode_from_timestamp(Timestamp::from_frames(frames, FrameRate::FPS_29_97))
+                .unwrap();
+
+            assert_eq!(
+                converter.to_timestamp(timecode),
+                Ok(Timestamp::from_frames(frames, FrameRate::FPS_29_97))
+            );
+        }
+
+        assert_eq!(TimecodeConverter::new_drop_frame(FrameRate::FPS_25), None);
+
+        // a round trip must not change whether the timecode is drop-frame
+        assert_eq!(
+            TimecodeConverter::new(FrameRate::FPS_29_97).to_timestamp(timecode("00:01:00;02")),
+            Err(TimecodeError::drop_frame_mismatch())
+        );
+        assert_eq!(
+            converter.to_timestamp(timecode("00:01:00:02")),
+            Err(TimecodeError::drop_frame_mismatch())
+        );
+        assert_eq!(
+            converter
+                .with_start(timecode("10:00:00:00"))
+                .timecode_from_duration(Duration::ZERO),
+            Err(TimecodeError::drop_frame_mismatch())
+        );
+    }
+
+    #[test]
+    fn test_programme_start() {
+        let converter = TimecodeConverter::new_drop_frame(FrameRate::FPS_29_97)
+            .unwrap()
+            .with_start(timecode("10:00:00;00"));
+
+        assert_eq!(
+            converter.to_duration(timecode("10:01:00;02")),
+            Ok(Duration::from_millis(60_060))
+        );
+        assert_eq!(
+            converter.timecode_from_duration(Duration::from_millis(60_060)),
+            Ok(timecode("10:01:00;02"))
+        );
+        assert_eq!(
+            converter.to_timestamp(timecode("09:59:59;29")),
+            Ok(Timestamp::from_frames(-1, FrameRate::FPS_29_97))
+        );
+        assert_eq!(
+            converter.to_duration(timecode("09:59:59;29")),
+            Err(TimecodeError::before_start())
+        );
+    }
+}
@@ -0,0 +1,679 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use core::str::FromStr;
+use core::time::Duration;
+
+use super::FrameRate;
+use crate::buffer::FmtWriter;
+use crate::parser::{parse_timestamp, Notation, ParserError};
+use crate::serialize::decimal_digits;
+use crate::utils::Spanned;
+use crate::{Buffer, ExactSerializedLength, Serialize, SerializeWithConfig};
+
+const NANOS_PER_MILLI: i128 = 1_000_000;
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// Divides `value` by `divisor` and rounds half away from zero.
+///
+/// The `divisor` must be positive.
+#[must_use]
+const fn div_round(value: i128, divisor: i128) -> i128 {
+    let quotient = value / divisor;
+
+    if (value % divisor).abs() * 2 >= divisor {
+        quotient + value.signum()
+    } else {
+        quotient
+    }
+}
+
+/// Decides how strictly a [`Timestamp`] is parsed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ParseMode {
+    /// Accepts everything that can be interpreted as a timestamp, like
+    /// `999:99:99,999` or `00:00:11,00`.
+    Lenient,
+    /// Requires minutes and seconds with two digits, which must be less than
+    /// `60`, milliseconds with three digits and hours with at least two
+    /// digits.
+    Strict,
+}
+
+impl Default for ParseMode {
+    #[inline]
+    fn default() -> Self { Self::Lenient }
+}
+
+/// The notation in which a [`Timestamp`] is serialized.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum SerializeTimestampConfig {
+    /// `HH:MM:SS,mmm`
+    Srt,
+    /// `HH:MM:SS.mmm`
+    WebVtt,
+}
+
+impl Default for SerializeTimestampConfig {
+    #[inline]
+    fn default() -> Self { Self::Srt }
+}
+
+/// A signed point in time with nanosecond precision.
+///
+/// In contrast to [`Duration`], a `Timestamp` can be negative, which makes it
+/// suitable to represent offsets, that should be applied to subtitles.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::time::Timestamp;
+/// use std::time::Duration;
+///
+/// let start = Timestamp::from(Duration::from_secs(2));
+/// let offset = Timestamp::parse_srt("-00:00:01,500", Default::default())?;
+///
+/// assert_eq!((start + offset).to_string(), "00:00:00,500");
+/// assert_eq!(start + offset + offset, Timestamp::from_millis(-1_000));
+/// # Ok::<(), libsrt::ParserError>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Timestamp {
+    nanos: i128,
+}
+
+impl Timestamp {
+    /// A `Timestamp` of zero.
+    pub const ZERO: Self = Self { nanos: 0 };
+
+    /// Creates a `Timestamp` from the provided number of nanoseconds.
+    #[inline]
+    #[must_use]
+    pub const fn from_nanos(nanos: i128) -> Self { Self { nanos } }
+
+    /// Creates a `Timestamp` from the provided number of milliseconds.
+    #[inline]
+    #[must_use]
+    pub const fn from_millis(millis: i64) -> Self {
+        Self {
+            nanos: millis as i128 * NANOS_PER_MILLI,
+        }
+    }
+
+    /// Creates a `Timestamp` from the provided number of seconds.
+    #[inline]
+    #[must_use]
+    pub const fn from_secs(secs: i64) -> Self {
+        Self {
+            nanos: secs as i128 * NANOS_PER_SEC,
+        }
+    }
+
+    /// Returns the total number of nanoseconds.
+    #[inline]
+    #[must_use]
+    pub const fn as_nanos(self) -> i128 { self.nanos }
+
+    /// Returns the total number of whole milliseconds, truncated towards zero.
+    #[inline]
+    #[must_use]
+    pub const fn as_millis(self) -> i128 { self.nanos / NANOS_PER_MILLI }
+
+    /// Adds two `Timestamp`s or returns `None` if the result overflows.
+    #[inline]
+    #[must_use]
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.nanos.checked_add(other.nanos) {
+            Some(nanos) => Some(Self { nanos }),
+            None => None,
+        }
+    }
+
+    /// Subtracts two `Timestamp`s or returns `None` if the result overflows.
+    #[inline]
+    #[must_use]
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.nanos.checked_sub(other.nanos) {
+            Some(nanos) => Some(Self { nanos }),
+            None => None,
+        }
+    }
+
+    /// Negates the `Timestamp` or returns `None` if the result overflows.
+    #[inline]
+    #[must_use]
+    pub const fn checked_neg(self) -> Option<Self> {
+        match self.nanos.checked_neg() {
+            Some(nanos) => Some(Self { nanos }),
+            None => None,
+        }
+    }
+
+    /// Returns `true` if the `Timestamp` is less than zero.
+    #[inline]
+    #[must_use]
+    pub const fn is_negative(self) -> bool { self.nanos < 0 }
+
+    /// Returns the absolute value as a [`Duration`].
+    #[must_use]
+    pub fn unsigned_abs(self) -> Duration {
+        let nanos = self.nanos.unsigned_abs();
+        // a `Timestamp` that does not fit into a `Duration` is saturated
+        let secs = u64::try_from(nanos / NANOS_PER_SEC.unsigned_abs()).unwrap_or(u64::MAX);
+        let subsec_nanos = u32::try_from(nanos % NANOS_PER_SEC.unsigned_abs()).unwrap_or(0);
+
+        Duration::new(secs, subsec_nanos)
+    }
+
+    /// Converts the `Timestamp` into a [`Duration`] or returns `None` if it is
+    /// negative.
+    #[inline]
+    #[must_use]
+    pub fn to_duration(self) -> Option<Duration> {
+        if self.is_negative() {
+            None
+        } else {
+            Some(self.unsigned_abs())
+        }
+    }
+
+    /// Converts the `Timestamp` into a [`Duration`], where negative values are
+    /// clamped to zero.
+    #[inline]
+    #[must_use]
+    pub fn to_duration_saturating(self) -> Duration { self.to_duration().unwrap_or_default() }
+
+    /// Truncates the `Timestamp` to whole milliseconds (towards zero).
+    #[inline]
+    #[must_use]
+    pub const fn truncate_to_millis(self) -> Self {
+        Self {
+            nanos: self.nanos - self.nanos % NANOS_PER_MILLI,
+        }
+    }
+
+    /// Rounds the `Timestamp` to the nearest millisecond (half away from zero).
+    #[inline]
+    #[must_use]
+    pub const fn round_to_millis(self) -> Self {
+        Self {
+            nanos: div_round(self.nanos, NANOS_PER_MILLI) * NANOS_PER_MILLI,
+        }
+    }
+
+    /// Returns the number of the frame, that is displayed at this `Timestamp`.
+    ///
+    /// The number of frames is rounded down, so a negative `Timestamp` within
+    /// the first frame before zero (like -0.5 frames) returns `-1`.
+    #[must_use]
+    pub fn to_frames(self, rate: FrameRate) -> i128 {
+        (self.nanos * i128::from(rate.numerator()))
+            .div_euclid(i128::from(rate.denominator()) * NANOS_PER_SEC)
+    }
+
+    /// Returns the `Timestamp` at which the frame with the provided number
+    /// starts.
+    ///
+    /// The start is rounded up to the next nanosecond, so converting it back
+    /// with [`Timestamp::to_frames`] returns the same frame.
+    #[must_use]
+    pub fn from_frames(frames: i128, rate: FrameRate) -> Self {
+        let dividend = frames * i128::from(rate.denominator()) * NANOS_PER_SEC;
+        let divisor = i128::from(rate.numerator());
+
+        Self {
+            nanos: -(-dividend).div_euclid(divisor),
+        }
+    }
+
+    /// Truncates the `Timestamp` to the start of the frame, that is displayed
+    /// at this `Timestamp`.
+    #[inline]
+    #[must_use]
+    pub fn truncate_to_frames(self, rate: FrameRate) -> Self {
+        Self::from_frames(self.to_frames(rate), rate)
+    }
+
+    /// Rounds the `Timestamp` to the start of the nearest frame.
+    #[must_use]
+    pub fn round_to_frames(self, rate: FrameRate) -> Self {
+        let frames = div_round(
+            self.nanos * i128::from(rate.numerator()),
+            i128::from(rate.denominator()) * NANOS_PER_SEC,
+        );
+
+        Self::from_frames(frames, rate)
+    }
+
+    fn parse(
+        input: Spanned<&str>,
+        notation: Notation,
+        mode: ParseMode,
+    ) -> Result<Self, ParserError> {
+        match input.get(1..) {
+            Some(remaining) if input.starts_with('-') => {
+                parse_timestamp(remaining, notation, mode).map(|duration| -Self::from(duration))
+            }
+            _ => parse_timestamp(input, notation, mode).map(Self::from),
+        }
+    }
+
+    /// Parses a `Timestamp` in the `HH:MM:SS,mmm` notation of `.srt` files,
+    /// which can be prefixed with a `-` to represent a negative offset.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::time::{ParseMode, Timestamp};
+    ///
+    /// assert_eq!(
+    ///     Timestamp::parse_srt("00:01:02,003", ParseMode::Strict),
+    ///     Ok(Timestamp::from_millis(62_003))
+    /// );
+    /// assert!(Timestamp::parse_srt("00:99:00,000", ParseMode::Lenient).is_ok());
+    /// assert!(Timestamp::parse_srt("00:99:00,000", ParseMode::Strict).is_err());
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ParserError`] if the `input` is not a valid timestamp for
+    /// the provided [`ParseMode`].
+    #[inline]
+    pub fn parse_srt(input: &str, mode: ParseMode) -> Result<Self, ParserError> {
+        Self::parse(Spanned::new(input), Notation::Srt, mode)
+    }
+
+    /// Parses a `Timestamp` in the `[HH:]MM:SS.mmm` notation of `WebVTT` files,
+    /// which can be prefixed with a `-` to represent a negative offset.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::time::{ParseMode, Timestamp};
+    ///
+    /// assert_eq!(
+    ///     Timestamp::parse_webvtt("01:02.003", ParseMode::Strict),
+    ///     Ok(Timestamp::from_millis(62_003))
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns a [`ParserError`] if the `input` is not a valid timestamp for
+    /// the provided [`ParseMode`].
+    #[inline]
+    pub fn parse_webvtt(input: &str, mode: ParseMode) -> Result<Self, ParserError> {
+        Self::parse(Spanned::new(input), Notation::WebVtt, mode)
+    }
+
+    fn write_to<W: fmt::Write>(self, f: &mut W, config: &SerializeTimestampConfig) -> fmt::Result {
+        let millis =
+            self.truncate_to_millis().nanos.unsigned_abs() / NANOS_PER_MILLI.unsigned_abs();
+
+        // a timestamp of `-0.1ms` would be written as `00:00:00,000`
+        if self.is_negative() && millis > 0 {
+            f.write_char('-')?;
+        }
+
+        let separator = {
+            match config {
+                SerializeTimestampConfig::Srt => ',',
+                SerializeTimestampConfig::WebVtt => '.',
+            }
+        };
+
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:03}",
+            millis / 3_600_000,
+            millis / 60_000 % 60,
+            millis / 1000 % 60,
+            separator,
+            millis % 1000
+        )
+    }
+}
+
+impl From<Duration> for Timestamp {
+    #[inline]
+    fn from(value: Duration) -> Self {
+        Self {
+            // a `Duration` has at most ~1.8e28 nanoseconds, which fits into an `i128`
+            nanos: i128::try_from(value.as_nanos()).unwrap_or(i128::MAX),
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = ParserError;
+
+    /// Parses a `Timestamp` with [`Timestamp::parse_srt`] in
+    /// [`ParseMode::Lenient`].
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::parse_srt(s, ParseMode::Lenient) }
+}
+
+/// Saturates at the bounds of a `Timestamp` instead of overflowing, use
+/// [`Timestamp::checked_neg`] to detect an overflow.
+impl Neg for Timestamp {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self {
+            nanos: self.nanos.saturating_neg(),
+        }
+    }
+}
+
+/// Saturates at the bounds of a `Timestamp` instead of overflowing, use
+/// [`Timestamp::checked_add`] to detect an overflow.
+impl Add for Timestamp {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            nanos: self.nanos.saturating_add(other.nanos),
+        }
+    }
+}
+
+/// Saturates at the bounds of a `Timestamp` instead of overflowing, use
+/// [`Timestamp::checked_sub`] to detect an overflow.
+impl Sub for Timestamp {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            nanos: self.nanos.saturating_sub(other.nanos),
+        }
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Duration) -> Self::Output { self + Self::from(other) }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Duration) -> Self::Output { self - Self::from(other) }
+}
+
+impl AddAssign for Timestamp {
+    #[inline]
+    fn add_assign(&mut self, other: Self) { *self = *self + other; }
+}
+
+impl SubAssign for Timestamp {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) { *self = *self - other; }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    #[inline]
+    fn add_assign(&mut self, other: Duration) { *self = *self + other; }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    #[inline]
+    fn sub_assign(&mut self, other: Duration) { *self = *self - other; }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f, &SerializeTimestampConfig::default())
+    }
+}
+
+impl ExactSerializedLength for Timestamp {
+    fn serialized_len(&self) -> usize {
+        let millis =
+            self.truncate_to_millis().nanos.unsigned_abs() / NANOS_PER_MILLI.unsigned_abs();
+        let sign = usize::from(self.is_negative() && millis > 0);
+
+        // "-HH:MM:SS,mmm", where the hours have at least two digits
+        sign + decimal_digits(millis / 3_600_000).max(2) + ":MM:SS,mmm".len()
+    }
+}
+
+impl<B: Buffer> Serialize<B> for Timestamp {
+    type Error = B::Error;
+
+    fn serialize(&self, buffer: B) -> Result<(), Self::Error> {
+        Self::serialize_with_config(self, buffer, &SerializeTimestampConfig::default())
+    }
+}
+
+impl<B: Buffer> SerializeWithConfig<B> for Timestamp {
+    type Config = SerializeTimestampConfig;
+    type Error = B::Error;
+
+    fn serialize_with_config(
+        &self,
+        mut buffer: B,
+        config: &Self::Config,
+    ) -> Result<(), Self::Error> {
+        // the separator of the milliseconds does not change the length
+        buffer.reserve(self.serialized_len())?;
+
+        let mut writer = FmtWriter::new(&mut buffer);
+        let result = self.write_to(&mut writer, config);
+
+        writer.finish(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::serialize::{SerializeToString, SerializeWithConfigToString};
+    #[cfg(feature = "alloc")]
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_srt() {
+        assert_eq!(
+            Timestamp::parse_srt("00:00:06,500", ParseMode::Strict),
+            Ok(Timestamp::from_millis(6_500))
+        );
+        assert_eq!(
+            Timestamp::parse_srt("-00:00:06,500", ParseMode::Strict),
+            Ok(Timestamp::from_millis(-6_500))
+        );
+        assert_eq!(
+            "999:99:99,999".parse::<Timestamp>(),
+            Ok(Timestamp::from_millis(
+                ((999 * 60 + 99) * 60 + 99) * 1000 + 999
+            ))
+        );
+        assert_eq!(
+            Timestamp::parse_srt("999:99:99,999", ParseMode::Strict),
+            Err(ParserError::out_of_range(4..6))
+        );
+        assert_eq!(
+            Timestamp::parse_srt("-00:00:60,000", ParseMode::Strict),
+            Err(ParserError::out_of_range(7..9))
+        );
+        assert_eq!(
+            Timestamp::parse_srt("99999999999999999:00:00,000", ParseMode::Lenient),
+            Err(ParserError::out_of_range(0..27))
+        );
+        assert_eq!(
+            Timestamp::parse_srt("-99999999999999999:00:00,000", ParseMode::Lenient),
+            Err(ParserError::out_of_range(1..28))
+        );
+    }
+
+    #[test]
+    fn test_parse_webvtt() {
+        assert_eq!(
+            Timestamp::parse_webvtt("100:00:06.500", ParseMode::Strict),
+            Ok(Timestamp::from_secs(100 * 3600) + Timestamp::from_millis(6_500))
+        );
+        assert_eq!(
+            Timestamp::parse_webvtt("00:06.500", ParseMode::Strict),
+            Ok(Timestamp::from_millis(6_500))
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let start = Timestamp::from(Duration::from_secs(1));
+
+        assert_eq!(start - Timestamp::from_secs(2), Timestamp::from_secs(-1));
+        assert_eq!(-start, Timestamp::from_secs(-1));
+        assert_eq!(
+            start + Duration::from_millis(500),
+            Timestamp::from_millis(1_500)
+        );
+        assert_eq!((start - Duration::from_secs(3)).to_duration(), None);
+        assert_eq!(
+            (start - Duration::from_secs(3)).to_duration_saturating(),
+            Duration::from_secs(0)
+        );
+        assert_eq!(
+            (start - Duration::from_secs(3)).unsigned_abs(),
+            Duration::from_secs(2)
+        );
+        assert!(Timestamp::from_secs(-2) < Timestamp::from_secs(-1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = Timestamp::from_nanos(i128::MAX);
+        let min = Timestamp::from_nanos(i128::MIN);
+        let one = Timestamp::from_nanos(1);
+
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(
+            max.checked_sub(one),
+            Some(Timestamp::from_nanos(i128::MAX - 1))
+        );
+        assert_eq!(max + one, max);
+        assert_eq!(min - one, min);
+        assert_eq!(-min, max);
+        assert_eq!(min + Duration::MAX - Duration::MAX, min);
+    }
+
+    #[test]
+    fn test_round_and_truncate_millis() {
+        assert_eq!(
+            Timestamp::from_nanos(1_499_999).round_to_millis(),
+            Timestamp::from_millis(1)
+        );
+        assert_eq!(
+            Timestamp::from_nanos(1_500_000).round_to_millis(),
+            Timestamp::from_millis(2)
+        );
+        assert_eq!(
+            Timestamp::from_nanos(-1_500_000).round_to_millis(),
+            Timestamp::from_millis(-2)
+        );
+        assert_eq!(
+            Timestamp::from_nanos(1_999_999).truncate_to_millis(),
+            Timestamp::from_millis(1)
+        );
+        assert_eq!(
+            Timestamp::from_nanos(-1_999_999).truncate_to_millis(),
+            Timestamp::from_millis(-1)
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let rate = FrameRate::FPS_25;
+
+        assert_eq!(Timestamp::from_millis(1_000).to_frames(rate), 25);
+        assert_eq!(Timestamp::from_millis(1_039).to_frames(rate), 25);
+        assert_eq!(
+            Timestamp::from_millis(1_039).truncate_to_frames(rate),
+            Timestamp::from_millis(1_000)
+        );
+        assert_eq!(
+            Timestamp::from_millis(1_021).round_to_frames(rate),
+            Timestamp::from_millis(1_040)
+        );
+
+        let rate = FrameRate::FPS_23_976;
+
+        for frame in -100..100 {
+            assert_eq!(Timestamp::from_frames(frame, rate).to_frames(rate), frame);
+        }
+
+        // half a frame before zero is displayed during the frame -1
+        assert_eq!(Timestamp::from_millis(-20).to_frames(FrameRate::FPS_25), -1);
+        assert_eq!(
+            Timestamp::from_millis(-20).truncate_to_frames(FrameRate::FPS_25),
+            Timestamp::from_millis(-40)
+        );
+        assert_eq!(
+            Timestamp::from_millis(-1_020).truncate_to_frames(rate),
+            Timestamp::from_frames(-25, rate)
+        );
+
+        // frame 24 starts at 24 * 1001 / 24000 = 1.001s
+        assert_eq!(
+            Timestamp::from_millis(1_020).truncate_to_frames(rate),
+            Timestamp::from_millis(1_001)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_serialize() {
+        assert_eq!(
+            Timestamp::from_millis(6_500).serialize_to_string(),
+            Ok("00:00:06,500".to_string())
+        );
+        assert_eq!(
+            Timestamp::from_millis(-6_500).serialize_to_string(),
+            Ok("-00:00:06,500".to_string())
+        );
+        assert_eq!(
+            Timestamp::from_millis(((999 * 60 + 59) * 60 + 59) * 1000 + 999).serialize_to_string(),
+            Ok("999:59:59,999".to_string())
+        );
+        assert_eq!(
+            Timestamp::from_nanos(-1).serialize_to_string(),
+            Ok("00:00:00,000".to_string())
+        );
+        assert_eq!(
+            Timestamp::from_millis(3_723_004)
+                .serialize_with_config_to_string(&SerializeTimestampConfig::WebVtt),
+            Ok("01:02:03.004".to_string())
+        );
+        assert_eq!(
+            Timestamp::from_millis(3_723_004).to_string(),
+            "01:02:03,004".to_string()
+        );
+    }
+
+    #[test]
+    fn test_serialized_len() {
+        for millis in [
+            0,
+            6_500,
+            -6_500,
+            3_723_004,
+            ((999 * 60 + 59) * 60 + 59) * 1000 + 999,
+        ] {
+            crate::serialize::tests::assert_exact_length(&Timestamp::from_millis(millis));
+        }
+
+        crate::serialize::tests::assert_exact_length(&Timestamp::from_nanos(-1));
+        assert_eq!(Timestamp::from_millis(-6_500).serialized_len(), 13);
+    }
+}
@@ -0,0 +1,519 @@
+use core::fmt;
+use core::ops::Range;
+use core::time::Duration;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::time::Timestamp;
+use crate::{Subtitle, Text};
+
+/// Configures how [`diff`] aligns the cues of both tracks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffOptions {
+    /// Cues, whose plain texts are at least this similar (from `0.0` to
+    /// `1.0`), are matched, even if they have been retimed.
+    pub min_similarity: f64,
+    /// Cues, whose starts are further apart than this, are never matched.
+    pub max_shift: Duration,
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            min_similarity: 0.6,
+            max_shift: Duration::from_secs(10),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// A cue of one of the compared tracks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffCue<'a> {
+    /// The index of the cue in the slice.
+    pub index: usize,
+    /// The counter of the cue.
+    pub counter: usize,
+    /// The start of the cue.
+    pub start: Duration,
+    /// The end of the cue.
+    pub end: Duration,
+    /// The text of the cue.
+    pub text: Text<'a>,
+    /// The byte range of the text in its source, see [`Text::span`].
+    pub span: Option<Range<usize>>,
+}
+
+impl<'a> DiffCue<'a> {
+    fn new(index: usize, subtitle: &Subtitle<'a>) -> Self {
+        Self {
+            index,
+            counter: subtitle.counter(),
+            start: subtitle.start(),
+            end: subtitle.end(),
+            text: subtitle.text().clone(),
+            span: subtitle.text().span(),
+        }
+    }
+}
+
+/// A difference between the two tracks compared by [`diff`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Change<'a> {
+    /// The cue only exists in the new track.
+    Inserted { new: DiffCue<'a> },
+    /// The cue only exists in the old track.
+    Deleted { old: DiffCue<'a> },
+    /// The cue exists in both tracks, but its timing or text has changed.
+    Modified {
+        old: DiffCue<'a>,
+        new: DiffCue<'a>,
+        /// How much the start has been moved, if the timing has changed.
+        start_delta: Option<Timestamp>,
+        /// How much the end has been moved, if the timing has changed.
+        end_delta: Option<Timestamp>,
+        /// Whether the text (including its tags) has changed.
+        retexted: bool,
+    },
+}
+
+impl Change<'_> {
+    /// Returns whether the start or end of the cue has been moved.
+    #[must_use]
+    pub const fn is_retimed(&self) -> bool {
+        matches!(
+            self,
+            Self::Modified { start_delta, end_delta, .. }
+                if start_delta.is_some() || end_delta.is_some()
+        )
+    }
+
+    /// Returns whether the text of the cue has changed.
+    #[must_use]
+    pub const fn is_retexted(&self) -> bool {
+        matches!(self, Self::Modified { retexted: true, .. })
+    }
+}
+
+/// The differences between two tracks, that are returned by [`diff`].
+///
+/// The [`fmt::Display`] implementation writes them in a form similar to a
+/// unified diff, where each hunk is a single cue.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Diff<'a> {
+    /// The changes ordered by their position in the tracks.
+    pub changes: Vec<Change<'a>>,
+}
+
+impl Diff<'_> {
+    /// Returns `true` if both tracks are identical, ignoring their counters.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool { self.changes.is_empty() }
+}
+
+fn write_lines(f: &mut fmt::Formatter<'_>, prefix: char, text: &Text<'_>) -> fmt::Result {
+    for line in text.lines() {
+        writeln!(f, "{prefix}{line}")?;
+    }
+
+    Ok(())
+}
+
+fn write_time(f: &mut fmt::Formatter<'_>, prefix: char, cue: &DiffCue<'_>) -> fmt::Result {
+    writeln!(
+        f,
+        "{}{} --> {}",
+        prefix,
+        Timestamp::from(cue.start),
+        Timestamp::from(cue.end)
+    )
+}
+
+impl fmt::Display for Change<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inserted { new } => {
+                writeln!(f, "@@ +{} @@ inserted", new.counter)?;
+                write_time(f, '+', new)?;
+                write_lines(f, '+', &new.text)
+            }
+            Self::Deleted { old } => {
+                writeln!(f, "@@ -{} @@ deleted", old.counter)?;
+                write_time(f, '-', old)?;
+                write_lines(f, '-', &old.text)
+            }
+            Self::Modified {
+                old,
+                new,
+                start_delta,
+                end_delta,
+                retexted,
+            } => {
+                write!(f, "@@ -{} +{} @@", old.counter, new.counter)?;
+
+                if start_delta.is_some() || end_delta.is_some() {
+                    let delta = |delta: &Option<Timestamp>| delta.map_or(0, Timestamp::as_millis);
+
+                    write!(
+                        f,
+                        " retimed (start {:+}ms, end {:+}ms)",
+                        delta(start_delta),
+                        delta(end_delta)
+                    )?;
+                }
+
+                if *retexted {
+                    f.write_str(" retexted")?;
+                }
+
+                f.write_str("\n")?;
+
+                if start_delta.is_some() || end_delta.is_some() {
+                    write_time(f, '-', old)?;
+                    write_time(f, '+', new)?;
+                } else {
+                    write_time(f, ' ', new)?;
+                }
+
+                if *retexted {
+                    write_lines(f, '-', &old.text)?;
+                    write_lines(f, '+', &new.text)
+                } else {
+                    write_lines(f, ' ', &new.text)
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            change.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns how similar both strings are based on their edit distance, from
+/// `0.0` (completely different) to `1.0` (identical).
+#[allow(clippy::cast_precision_loss)]
+fn similarity(a: &[char], b: &[char]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    // levenshtein distance with a single row
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    1.0 - row[b.len()] as f64 / a.len().max(b.len()) as f64
+}
+
+/// Returns how much both time ranges overlap (intersection over union).
+fn overlap(a: &Subtitle<'_>, b: &Subtitle<'_>) -> f64 {
+    let intersection = a
+        .end()
+        .min(b.end())
+        .saturating_sub(a.start().max(b.start()));
+    let union = a
+        .end()
+        .max(b.end())
+        .saturating_sub(a.start().min(b.start()));
+
+    if union.is_zero() {
+        0.0
+    } else {
+        intersection.as_secs_f64() / union.as_secs_f64()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Step {
+    SkipOld,
+    SkipNew,
+    Match,
+}
+
+/// Aligns the cues of both tracks, so that the sum of the scores of the
+/// matched pairs is maximal and returns the matched pairs of indices.
+fn align(old: &[Subtitle<'_>], new: &[Subtitle<'_>], options: &DiffOptions) -> Vec<(usize, usize)> {
+    let old_texts = old
+        .iter()
+        .map(|subtitle| subtitle.text().plain_text().chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let new_texts = new
+        .iter()
+        .map(|subtitle| subtitle.text().plain_text().chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let score = |i: usize, j: usize| {
+        if old[i].start().abs_diff(new[j].start()) > options.max_shift {
+            return None;
+        }
+
+        let similarity = similarity(&old_texts[i], &new_texts[j]);
+        let overlap = overlap(&old[i], &new[j]);
+
+        // a cue in the same place is a rewritten one
+        if similarity >= options.min_similarity || overlap >= 0.5 {
+            Some(similarity + overlap)
+        } else {
+            None
+        }
+    };
+
+    let width = new.len() + 1;
+    let mut scores = vec![0.0_f64; (old.len() + 1) * width];
+    let mut steps = vec![Step::SkipOld; (old.len() + 1) * width];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            let mut best = (scores[(i + 1) * width + j], Step::SkipOld);
+
+            if scores[i * width + j + 1] > best.0 {
+                best = (scores[i * width + j + 1], Step::SkipNew);
+            }
+
+            if let Some(score) = score(i, j) {
+                if score + scores[(i + 1) * width + j + 1] > best.0 {
+                    best = (score + scores[(i + 1) * width + j + 1], Step::Match);
+                }
+            }
+
+            scores[i * width + j] = best.0;
+            steps[i * width + j] = best.1;
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        match steps[i * width + j] {
+            Step::SkipOld => i += 1,
+            Step::SkipNew => j += 1,
+            Step::Match => {
+                result.push((i, j));
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// Compares two versions of a track, ignoring their counters, and returns the
+/// inserted, deleted, retimed and retexted cues.
+///
+/// The cues are aligned by the similarity of their text (without tags) and
+/// by how much they overlap in time, so a cue with a rewritten text in the
+/// same place or a retimed cue with a similar text are reported as a
+/// modification.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{diff, DiffOptions};
+/// use libsrt::SubtitleIterator;
+///
+/// let old = SubtitleIterator::from(
+///     "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n\
+///      2\n00:00:03,000 --> 00:00:04,000\nHow are you?\n",
+/// )
+/// .collect::<Result<Vec<_>, _>>()?;
+/// let new = SubtitleIterator::from(
+///     "1\n00:00:00,500 --> 00:00:01,000\nHey!\n\n\
+///      2\n00:00:01,000 --> 00:00:02,000\nHello\n\n\
+///      3\n00:00:03,500 --> 00:00:04,000\nHow are you?\n",
+/// )
+/// .collect::<Result<Vec<_>, _>>()?;
+///
+/// let diff = diff(&old, &new, &DiffOptions::default());
+///
+/// assert_eq!(
+///     diff.to_string(),
+///     concat!(
+///         "@@ +1 @@ inserted\n",
+///         "+00:00:00,500 --> 00:00:01,000\n",
+///         "+Hey!\n",
+///         "@@ -2 +3 @@ retimed (start +500ms, end +0ms)\n",
+///         "-00:00:03,000 --> 00:00:04,000\n",
+///         "+00:00:03,500 --> 00:00:04,000\n",
+///         " How are you?\n",
+///     )
+/// );
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+pub fn diff<'a>(old: &[Subtitle<'a>], new: &[Subtitle<'a>], options: &DiffOptions) -> Diff<'a> {
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    let delta = |old: Duration, new: Duration| {
+        Some(Timestamp::from(new) - Timestamp::from(old)).filter(|_| old != new)
+    };
+
+    for (matched_i, matched_j) in align(old, new, options)
+        .into_iter()
+        .chain(Some((old.len(), new.len())))
+    {
+        for (index, subtitle) in old.iter().enumerate().take(matched_i).skip(i) {
+            changes.push(Change::Deleted {
+                old: DiffCue::new(index, subtitle),
+            });
+        }
+
+        for (index, subtitle) in new.iter().enumerate().take(matched_j).skip(j) {
+            changes.push(Change::Inserted {
+                new: DiffCue::new(index, subtitle),
+            });
+        }
+
+        if let (Some(old), Some(new)) = (old.get(matched_i), new.get(matched_j)) {
+            let start_delta = delta(old.start(), new.start());
+            let end_delta = delta(old.end(), new.end());
+            let retexted = old.text().as_raw() != new.text().as_raw();
+
+            if start_delta.is_some() || end_delta.is_some() || retexted {
+                changes.push(Change::Modified {
+                    old: DiffCue::new(matched_i, old),
+                    new: DiffCue::new(matched_j, new),
+                    start_delta,
+                    end_delta,
+                    retexted,
+                });
+            }
+        }
+
+        i = matched_i + 1;
+        j = matched_j + 1;
+    }
+
+    Diff { changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::track::tests::parse;
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    const OLD: &str = concat!(
+        "1\n00:00:01,000 --> 00:00:02,000\nGood morning.\n\n",
+        "2\n00:00:03,000 --> 00:00:05,000\nHow did you sleep?\n\n",
+        "3\n00:00:06,000 --> 00:00:07,000\nNot well.\n\n",
+        "4\n00:00:08,000 --> 00:00:09,000\nWhy?\n",
+    );
+
+    #[test]
+    fn test_identical() {
+        let old = parse(OLD);
+        let renumbered = OLD.replace("\n3\n", "\n30\n");
+        let new = parse(&renumbered);
+
+        assert!(diff(&old, &new, &DiffOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_changes() {
+        let old = parse(OLD);
+        let new = parse(concat!(
+            "1\n00:00:01,000 --> 00:00:02,000\nGood morning!\n\n",
+            "2\n00:00:03,000 --> 00:00:05,000\n<i>Did you sleep well?</i>\n\n",
+            "3\n00:00:08,200 --> 00:00:09,000\nWhy?\n\n",
+            "4\n00:00:10,000 --> 00:00:11,000\nBad dreams.\n",
+        ));
+
+        let diff = diff(&old, &new, &DiffOptions::default());
+
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| {
+                    match change {
+                        Change::Inserted { new } => (None, Some(new.counter)),
+                        Change::Deleted { old } => (Some(old.counter), None),
+                        Change::Modified { old, new, .. } => (Some(old.counter), Some(new.counter)),
+                    }
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                (Some(1), Some(1)),
+                (Some(2), Some(2)),
+                (Some(3), None),
+                (Some(4), Some(3)),
+                (None, Some(4)),
+            ]
+        );
+
+        assert!(diff.changes[0].is_retexted() && !diff.changes[0].is_retimed());
+        assert!(diff.changes[1].is_retexted());
+        assert!(diff.changes[3].is_retimed() && !diff.changes[3].is_retexted());
+
+        if let Change::Modified {
+            start_delta,
+            end_delta,
+            ..
+        } = &diff.changes[3]
+        {
+            assert_eq!(*start_delta, Some(Timestamp::from_millis(200)));
+            assert_eq!(*end_delta, None);
+        }
+
+        if let Change::Deleted { old } = &diff.changes[2] {
+            assert_eq!(&OLD[old.span.clone().unwrap()], "Not well.");
+        }
+
+        assert_eq!(
+            diff.to_string(),
+            concat!(
+                "@@ -1 +1 @@ retexted\n",
+                " 00:00:01,000 --> 00:00:02,000\n",
+                "-Good morning.\n",
+                "+Good morning!\n",
+                "@@ -2 +2 @@ retexted\n",
+                " 00:00:03,000 --> 00:00:05,000\n",
+                "-How did you sleep?\n",
+                "+<i>Did you sleep well?</i>\n",
+                "@@ -3 @@ deleted\n",
+                "-00:00:06,000 --> 00:00:07,000\n",
+                "-Not well.\n",
+                "@@ -4 +3 @@ retimed (start +200ms, end +0ms)\n",
+                "-00:00:08,000 --> 00:00:09,000\n",
+                "+00:00:08,200 --> 00:00:09,000\n",
+                " Why?\n",
+                "@@ +4 @@ inserted\n",
+                "+00:00:10,000 --> 00:00:11,000\n",
+                "+Bad dreams.\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_similarity() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+
+        assert!((similarity(&chars("kitten"), &chars("sitting")) - 4.0 / 7.0).abs() < 1e-9);
+        assert!((similarity(&chars(""), &chars("")) - 1.0).abs() < 1e-9);
+        assert!(similarity(&chars("abc"), &chars("xyz")).abs() < 1e-9);
+    }
+}
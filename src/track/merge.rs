@@ -0,0 +1,412 @@
+use core::time::Duration;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::text::Color;
+use crate::{Serialize, Subtitle, Text};
+
+/// How the text of the secondary track is styled in the merged subtitles.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SecondaryStyle<'a> {
+    /// The text is inserted as it is.
+    Plain,
+    /// The text is wrapped in `<i></i>`.
+    Italic,
+    /// The text is wrapped in `<font color="..."></font>`.
+    Color(Color<'a>),
+}
+
+impl Default for SecondaryStyle<'_> {
+    #[inline]
+    fn default() -> Self { Self::Plain }
+}
+
+/// Configures how two tracks are merged by [`merge`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MergeOptions<'a> {
+    /// The style applied to the text of the secondary track.
+    pub style: SecondaryStyle<'a>,
+    /// The start and the end of a secondary subtitle are moved to the nearest
+    /// start or end of a primary subtitle, if the difference is at most this
+    /// duration.
+    ///
+    /// This prevents tracks, whose timing differs slightly, from being split
+    /// into many short subtitles.
+    pub tolerance: Duration,
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Track {
+    Primary,
+    Secondary,
+}
+
+/// A subtitle of one of the tracks with its (possibly adjusted) timing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Cue {
+    start: Duration,
+    end: Duration,
+    track: Track,
+    index: usize,
+}
+
+/// Returns the boundary in `boundaries` that is nearest to `value`, if it is
+/// not further away than `tolerance`.
+fn snap(boundaries: &[Duration], value: Duration, tolerance: Duration) -> Duration {
+    let position = boundaries.partition_point(|boundary| *boundary < value);
+
+    let before = position.checked_sub(1).map(|index| boundaries[index]);
+    let after = boundaries.get(position).copied();
+
+    let nearest = {
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                if value.abs_diff(before) <= value.abs_diff(after) {
+                    before
+                } else {
+                    after
+                }
+            }
+            (before, after) => {
+                if let Some(nearest) = before.or(after) {
+                    nearest
+                } else {
+                    return value;
+                }
+            }
+        }
+    };
+
+    if value.abs_diff(nearest) <= tolerance {
+        nearest
+    } else {
+        value
+    }
+}
+
+fn write_text(result: &mut String, text: &Text<'_>, style: &SecondaryStyle<'_>) {
+    match style {
+        SecondaryStyle::Plain => result.push_str(text),
+        SecondaryStyle::Italic => {
+            result.push_str("<i>");
+            result.push_str(text);
+            result.push_str("</i>");
+        }
+        SecondaryStyle::Color(color) => {
+            result.push_str("<font color=\"");
+            // serializing into a `String` can not fail
+            let _ = color.serialize(&mut *result);
+            result.push_str("\">");
+            result.push_str(text);
+            result.push_str("</font>");
+        }
+    }
+}
+
+/// Merges two subtitle tracks into one bilingual track.
+///
+/// The subtitles are aligned by the time in which they overlap. Every merged
+/// subtitle contains the text of all primary subtitles that are visible at
+/// that time, followed by the text of the visible secondary subtitles on
+/// additional lines, which is styled according to
+/// [`MergeOptions::style`].
+///
+/// If two subtitles only partially overlap, they are split at the points where
+/// a subtitle starts or ends, so that the merged track never contains
+/// overlapping subtitles. The subtitles of the result are numbered
+/// from `1`.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{merge, MergeOptions, SecondaryStyle};
+/// use libsrt::SubtitleIterator;
+///
+/// let english = SubtitleIterator::from("1\n00:00:01,000 --> 00:00:04,000\nHello!\n")
+///     .collect::<Result<Vec<_>, _>>()?;
+/// let german = SubtitleIterator::from("1\n00:00:01,000 --> 00:00:04,000\nHallo!\n")
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let merged = merge(
+///     &english,
+///     &german,
+///     &MergeOptions {
+///         style: SecondaryStyle::Italic,
+///         ..MergeOptions::default()
+///     },
+/// );
+///
+/// assert_eq!(merged.len(), 1);
+/// assert_eq!(merged[0].text(), "Hello!\n<i>Hallo!</i>");
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+pub fn merge<'a>(
+    primary: &[Subtitle<'a>],
+    secondary: &[Subtitle<'a>],
+    options: &MergeOptions<'_>,
+) -> Vec<Subtitle<'a>> {
+    let mut primary_boundaries = primary
+        .iter()
+        .flat_map(|subtitle| [subtitle.start(), subtitle.end()])
+        .collect::<Vec<_>>();
+    primary_boundaries.sort_unstable();
+    primary_boundaries.dedup();
+
+    let mut cues = Vec::with_capacity(primary.len() + secondary.len());
+
+    for (index, subtitle) in primary.iter().enumerate() {
+        cues.push(Cue {
+            start: subtitle.start(),
+            end: subtitle.end(),
+            track: Track::Primary,
+            index,
+        });
+    }
+
+    for (index, subtitle) in secondary.iter().enumerate() {
+        let mut start = snap(&primary_boundaries, subtitle.start(), options.tolerance);
+        let mut end = snap(&primary_boundaries, subtitle.end(), options.tolerance);
+
+        // a subtitle must not vanish, because it has been snapped
+        if start >= end {
+            start = subtitle.start();
+            end = subtitle.end();
+        }
+
+        cues.push(Cue {
+            start,
+            end,
+            track: Track::Secondary,
+            index,
+        });
+    }
+
+    cues.sort_unstable();
+
+    let mut boundaries = cues
+        .iter()
+        .flat_map(|cue| [cue.start, cue.end])
+        .collect::<Vec<_>>();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    // the cues that are visible in between two neighbouring boundaries
+    let mut segments: Vec<(Duration, Duration, Vec<Cue>)> = Vec::new();
+    let mut active: Vec<Cue> = Vec::new();
+    let mut remaining = cues.iter().peekable();
+
+    for window in boundaries.windows(2) {
+        let (start, end) = (window[0], window[1]);
+
+        active.retain(|cue| cue.end > start);
+        while let Some(cue) = remaining.next_if(|cue| cue.start <= start) {
+            active.push(*cue);
+        }
+
+        if active.is_empty() {
+            continue;
+        }
+
+        // the sort order places primary subtitles in front of secondary ones
+        let mut visible = active.clone();
+        visible.sort_unstable_by_key(|cue| (cue.track, cue.start, cue.index));
+
+        match segments.last_mut() {
+            Some((_, last_end, last_visible)) if *last_end == start && *last_visible == visible => {
+                *last_end = end;
+            }
+            _ => segments.push((start, end, visible)),
+        }
+    }
+
+    segments
+        .into_iter()
+        .enumerate()
+        .map(|(index, (start, end, visible))| {
+            let mut text = String::new();
+
+            for cue in &visible {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+
+                match cue.track {
+                    Track::Primary => text.push_str(primary[cue.index].text()),
+                    Track::Secondary => {
+                        write_text(&mut text, secondary[cue.index].text(), &options.style);
+                    }
+                }
+            }
+
+            // the boundaries are sorted and deduplicated, so a segment ends after it starts
+            #[allow(clippy::unchecked_time_subtraction)]
+            let duration = end - start;
+
+            Subtitle::new(index + 1, start, duration, Text::from(text))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize::SerializeToString;
+    use crate::track::tests::parse;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_merge_aligned() {
+        let primary = parse(concat!(
+            "1\n00:00:01,000 --> 00:00:02,000\nOne\n\n",
+            "2\n00:00:03,000 --> 00:00:04,000\nTwo\n",
+        ));
+        let secondary = parse(concat!(
+            "7\n00:00:01,000 --> 00:00:02,000\nEins\n\n",
+            "8\n00:00:03,000 --> 00:00:04,000\nZwei\n",
+        ));
+
+        assert_eq!(
+            merge(&primary, &secondary, &MergeOptions::default())[..]
+                .serialize_to_string()
+                .unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:02,000\nOne\nEins\n\n",
+                "2\n00:00:03,000 --> 00:00:04,000\nTwo\nZwei\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_partial_overlap() {
+        let primary = parse("1\n00:00:01,000 --> 00:00:05,000\nFirst\nline\n");
+        let secondary = parse(concat!(
+            "1\n00:00:03,000 --> 00:00:07,000\nErste\n\n",
+            "2\n00:00:08,000 --> 00:00:09,000\nZweite\n",
+        ));
+
+        assert_eq!(
+            merge(&primary, &secondary, &MergeOptions::default())[..]
+                .serialize_to_string()
+                .unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:03,000\nFirst\nline\n\n",
+                "2\n00:00:03,000 --> 00:00:05,000\nFirst\nline\nErste\n\n",
+                "3\n00:00:05,000 --> 00:00:07,000\nErste\n\n",
+                "4\n00:00:08,000 --> 00:00:09,000\nZweite\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_styles() {
+        let primary = parse("1\n00:00:01,000 --> 00:00:02,000\nOne\n");
+        let secondary = parse("1\n00:00:01,000 --> 00:00:02,000\nEins\n");
+
+        let merged = merge(
+            &primary,
+            &secondary,
+            &MergeOptions {
+                style: SecondaryStyle::Italic,
+                ..MergeOptions::default()
+            },
+        );
+        assert_eq!(merged[0].text(), "One\n<i>Eins</i>");
+
+        let merged = merge(
+            &primary,
+            &secondary,
+            &MergeOptions {
+                style: SecondaryStyle::Color(Color::Rgb {
+                    red: 0xFF,
+                    green: 0xFF,
+                    blue: 0x00,
+                }),
+                ..MergeOptions::default()
+            },
+        );
+        assert_eq!(merged[0].text(), "One\n<font color=\"#ffff00\">Eins</font>");
+
+        let merged = merge(
+            &primary,
+            &secondary,
+            &MergeOptions {
+                style: SecondaryStyle::Color(Color::Name("yellow")),
+                ..MergeOptions::default()
+            },
+        );
+        assert_eq!(merged[0].text(), "One\n<font color=\"yellow\">Eins</font>");
+    }
+
+    #[test]
+    fn test_merge_tolerance() {
+        let primary = parse("1\n00:00:01,000 --> 00:00:04,000\nOne\n");
+        let secondary = parse("1\n00:00:01,080 --> 00:00:03,950\nEins\n");
+
+        assert_eq!(
+            merge(&primary, &secondary, &MergeOptions::default()).len(),
+            3
+        );
+
+        let merged = merge(
+            &primary,
+            &secondary,
+            &MergeOptions {
+                tolerance: Duration::from_millis(100),
+                ..MergeOptions::default()
+            },
+        );
+
+        assert_eq!(
+            merged[..].serialize_to_string().unwrap(),
+            "1\n00:00:01,000 --> 00:00:04,000\nOne\nEins\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_overlapping_primary() {
+        let primary = parse(concat!(
+            "1\n00:00:01,000 --> 00:00:03,000\nOne\n\n",
+            "2\n00:00:02,000 --> 00:00:04,000\nTwo\n",
+        ));
+
+        assert_eq!(
+            merge(&primary, &[], &MergeOptions::default())[..]
+                .serialize_to_string()
+                .unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:02,000\nOne\n\n",
+                "2\n00:00:02,000 --> 00:00:03,000\nOne\nTwo\n\n",
+                "3\n00:00:03,000 --> 00:00:04,000\nTwo\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_snap() {
+        let boundaries = [Duration::from_secs(1), Duration::from_secs(2)];
+        let tolerance = Duration::from_millis(100);
+
+        assert_eq!(
+            snap(&boundaries, Duration::from_millis(1050), tolerance),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            snap(&boundaries, Duration::from_millis(1950), tolerance),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            snap(&boundaries, Duration::from_millis(1500), tolerance),
+            Duration::from_millis(1500)
+        );
+        assert_eq!(
+            snap(&[], Duration::from_millis(1500), tolerance),
+            Duration::from_millis(1500)
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Operations on whole subtitle tracks, like merging or splitting them.
+
+mod diff;
+mod merge;
+mod ocr;
+mod reading_speed;
+#[cfg(feature = "regex")]
+mod replace;
+// the alignment needs floating point functions, which are not part of `core`
+#[cfg(feature = "std")]
+mod resync;
+mod sdh;
+mod split;
+mod timing;
+
+pub use diff::{diff, Change, Diff, DiffCue, DiffOptions};
+pub use merge::{merge, MergeOptions, SecondaryStyle};
+pub use ocr::{
+    apply_ocr_fixes, find_ocr_errors, DictionaryError, OcrDictionary, OcrFix, OcrOptions, OcrRule,
+};
+pub use reading_speed::{
+    extend_durations, ExtendOptions, ExtendOptionsError, Extended, ReadingSpeed, Unfixed,
+};
+#[cfg(feature = "regex")]
+pub use replace::{find_all, replace_all, MatchMode, Replaced, TextMatch};
+#[cfg(feature = "std")]
+pub use resync::{resync, Anchor, ResyncOptions, Resynced, TimeMap};
+pub use sdh::remove_sdh;
+pub use split::{join, split, JoinOffsets, SplitPoint};
+pub use timing::{
+    normalize_timing, Normalized, OverlapStrategy, TimingChange, TimingOptions, TimingOptionsError,
+};
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{Subtitle, SubtitleIterator};
+
+    /// Parses the subtitles of a track, that is expected to be valid.
+    pub(crate) fn parse(input: &str) -> Vec<Subtitle<'_>> {
+        SubtitleIterator::from(input)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+}
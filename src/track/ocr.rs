@@ -0,0 +1,606 @@
+use core::ops::Range;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::text::TextEvent;
+use crate::{Subtitle, Text};
+
+const PUNCTUATION: &[char] = &[',', '.', '!', '?', ':', ';'];
+
+/// An error, that occured while parsing an [`OcrDictionary`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum DictionaryError {
+    /// The line (starting at `1`) contains more than two words.
+    #[error("line {line}: expected a word or a pair of words")]
+    InvalidLine { line: usize },
+    /// The dictionary file could not be read.
+    #[cfg(feature = "std")]
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Known words and replacements of wrongly recognized words, that are used by
+/// [`find_ocr_errors`].
+///
+/// A dictionary file contains one entry per line:
+/// - a single word is a known word, which enables the rules, that need to know
+///   whether a word exists (like `rn` to `m`),
+/// - two words separated by whitespace are a replacement of the first word by
+///   the second one, where the second word is known too.
+///
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::OcrDictionary;
+///
+/// let dictionary = OcrDictionary::parse("# english\nmodern\nlt It\n").unwrap();
+///
+/// assert!(dictionary.is_known("Modern"));
+/// assert_eq!(dictionary.replacement("lt"), Some("It"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OcrDictionary {
+    /// The known words in lower case.
+    words: BTreeSet<String>,
+    replacements: BTreeMap<String, String>,
+}
+
+impl OcrDictionary {
+    /// Creates an empty dictionary.
+    #[must_use]
+    pub fn new() -> Self { Self::default() }
+
+    /// Parses a dictionary in the format described in [`OcrDictionary`].
+    ///
+    /// # Errors
+    ///
+    /// If a line contains more than two words.
+    pub fn parse(input: &str) -> Result<Self, DictionaryError> {
+        let mut result = Self::new();
+
+        for (number, line) in input.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [word] => result.insert_word(word),
+                [wrong, correct] => result.insert_replacement(wrong, correct),
+                _ => return Err(DictionaryError::InvalidLine { line: number + 1 }),
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Reads and parses a dictionary file.
+    ///
+    /// # Errors
+    ///
+    /// If the file can not be read or [`OcrDictionary::parse`] fails.
+    #[cfg(feature = "std")]
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, DictionaryError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Adds a known word.
+    pub fn insert_word(&mut self, word: &str) { self.words.insert(word.to_lowercase()); }
+
+    /// Adds a replacement of the `wrong` word by the `correct` one.
+    pub fn insert_replacement(&mut self, wrong: &str, correct: &str) {
+        self.insert_word(correct);
+        self.replacements
+            .insert(wrong.to_string(), correct.to_string());
+    }
+
+    /// Adds all entries of the other dictionary to this one.
+    pub fn extend(&mut self, other: Self) {
+        self.words.extend(other.words);
+        self.replacements.extend(other.replacements);
+    }
+
+    /// Returns whether the word is known, ignoring its case.
+    #[must_use]
+    pub fn is_known(&self, word: &str) -> bool { self.words.contains(&word.to_lowercase()) }
+
+    /// Returns the replacement of the word, which is case sensitive.
+    #[must_use]
+    pub fn replacement(&self, word: &str) -> Option<&str> {
+        self.replacements.get(word).map(String::as_str)
+    }
+}
+
+/// Configures which rules [`find_ocr_errors`] applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct OcrOptions<'a> {
+    /// The dictionary with the replacements and known words.
+    pub dictionary: Option<&'a OcrDictionary>,
+    /// Replaces `|` with `I` or `l`, like in `|'m` or `he|lo`.
+    pub pipes: bool,
+    /// Fixes `I` and `l` confusion, like in `l am`, `lT'S` or `wiII`.
+    ///
+    /// Ambiguous words, like `Iike`, `Al` or `McIntosh`, are only changed, if
+    /// the dictionary knows the fixed word.
+    pub i_and_l: bool,
+    /// Replaces `rn` with `m`, if the word is unknown and the replaced one is
+    /// known by the dictionary.
+    pub rn_and_m: bool,
+    /// Removes whitespace in front of punctuation, like in `Hello , world !`.
+    pub space_before_punctuation: bool,
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for OcrOptions<'_> {
+    fn default() -> Self {
+        Self {
+            dictionary: None,
+            pipes: true,
+            i_and_l: true,
+            rn_and_m: true,
+            space_before_punctuation: true,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// The rule, that suggested an [`OcrFix`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum OcrRule {
+    /// A replacement of the [`OcrDictionary`].
+    Dictionary,
+    /// See [`OcrOptions::pipes`].
+    Pipe,
+    /// See [`OcrOptions::i_and_l`].
+    IAndL,
+    /// See [`OcrOptions::rn_and_m`].
+    RnAndM,
+    /// See [`OcrOptions::space_before_punctuation`].
+    SpaceBeforePunctuation,
+}
+
+/// A suggested fix of an OCR error.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OcrFix {
+    /// The index of the subtitle in the slice.
+    pub index: usize,
+    /// The counter of the subtitle.
+    pub counter: usize,
+    /// The byte range of the error in the text of the subtitle.
+    pub span: Range<usize>,
+    /// The wrong text.
+    pub original: String,
+    /// The suggested replacement.
+    pub replacement: String,
+    /// The rule, that suggested the fix.
+    pub rule: OcrRule,
+}
+
+fn is_word_char(c: char) -> bool { c.is_alphanumeric() || matches!(c, '\'' | '|') }
+
+fn fix_pipes(word: &str) -> Option<String> {
+    if !word.contains('|') {
+        return None;
+    }
+
+    let has_lowercase = word.chars().any(char::is_lowercase);
+    let mut result = String::with_capacity(word.len());
+    let mut is_start = true;
+
+    for c in word.chars() {
+        if c == '|' {
+            // `|'m` or `|T` -> `I`, `he||o` -> `hello`
+            result.push(if !is_start && has_lowercase { 'l' } else { 'I' });
+        } else {
+            result.push(c);
+            is_start = false;
+        }
+    }
+
+    Some(result)
+}
+
+fn fix_i_and_l(word: &str, dictionary: Option<&OcrDictionary>) -> Option<String> {
+    if word == "l" {
+        return Some("I".into());
+    }
+
+    let is_known = |word: &str| dictionary.is_some_and(|d| d.is_known(word));
+
+    if is_known(word) {
+        return None;
+    }
+
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+    let uppercase = word.chars().filter(|c| c.is_uppercase()).count();
+    let first = word.chars().next()?;
+
+    let (candidate, is_likely) = {
+        if word.contains('l') && uppercase > 0 && uppercase + word.matches('l').count() == letters {
+            // the word is in upper case: `lT'S` -> `IT'S`, but with a single
+            // capital letter it could be a correct word like `Al` or `I'll`
+            (word.replace('l', "I"), uppercase > 1)
+        } else if uppercase < letters && word.chars().skip(1).any(|c| c == 'I') {
+            // the word is in lower case: `wiII` -> `will`, where an `I` next to
+            // another `l` is most likely a double `l`, while other ones can be
+            // part of a name like `McIntosh` or `GIs`
+            let rest = &word[first.len_utf8()..];
+            let candidate = core::iter::once(first)
+                .chain(rest.chars().map(|c| if c == 'I' { 'l' } else { c }))
+                .collect();
+
+            (
+                candidate,
+                ["II", "Il", "lI"].iter().any(|pair| rest.contains(pair)),
+            )
+        } else if let Some(rest) = word.strip_prefix('I').filter(|rest| !rest.is_empty()) {
+            // `Iike` -> `like`
+            (["l", rest].concat(), false)
+        } else {
+            return None;
+        }
+    };
+
+    Some(candidate).filter(|candidate| is_likely || is_known(candidate))
+}
+
+fn fix_rn(word: &str, dictionary: Option<&OcrDictionary>) -> Option<String> {
+    let dictionary = dictionary?;
+
+    if !word.contains("rn") || dictionary.is_known(word) {
+        return None;
+    }
+
+    Some(word.replace("rn", "m")).filter(|candidate| dictionary.is_known(candidate))
+}
+
+/// Finds the OCR errors in a single run of text, with spans relative to the
+/// run. `previous` is the last char of the text in front of the run.
+fn find_in_run(
+    run: &str,
+    previous: Option<char>,
+    options: &OcrOptions<'_>,
+) -> Vec<(Range<usize>, String, OcrRule)> {
+    let mut result = Vec::new();
+    let mut chars = run.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if is_word_char(c) {
+            let mut end = start + c.len_utf8();
+
+            while let Some((index, c)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+                end = index + c.len_utf8();
+            }
+
+            let word = &run[start..end];
+            let fix = options
+                .dictionary
+                .and_then(|dictionary| dictionary.replacement(word))
+                .map(|replacement| (replacement.to_string(), OcrRule::Dictionary))
+                .or_else(|| {
+                    fix_pipes(word)
+                        .filter(|_| options.pipes)
+                        .map(|fixed| (fixed, OcrRule::Pipe))
+                })
+                .or_else(|| {
+                    fix_i_and_l(word, options.dictionary)
+                        .filter(|_| options.i_and_l)
+                        .map(|fixed| (fixed, OcrRule::IAndL))
+                })
+                .or_else(|| {
+                    fix_rn(word, options.dictionary)
+                        .filter(|_| options.rn_and_m)
+                        .map(|fixed| (fixed, OcrRule::RnAndM))
+                });
+
+            if let Some((replacement, rule)) = fix {
+                result.push((start..end, replacement, rule));
+            }
+        } else if c == ' ' && options.space_before_punctuation {
+            let mut end = start + 1;
+
+            while let Some((index, _)) = chars.next_if(|(_, c)| *c == ' ') {
+                end = index + 1;
+            }
+
+            let is_after_word = run[..start]
+                .chars()
+                .next_back()
+                .or(previous)
+                .is_some_and(|c| !c.is_whitespace());
+            let next = run[end..].chars().collect::<Vec<_>>();
+
+            // an ellipsis like in `Wait ...` is not an error
+            let is_error = match next.as_slice() {
+                ['.', '.', ..] | [] => false,
+                [c, ..] => PUNCTUATION.contains(c),
+            };
+
+            if is_after_word && is_error {
+                result.push((start..end, String::new(), OcrRule::SpaceBeforePunctuation));
+            }
+        }
+    }
+
+    result
+}
+
+/// Finds likely OCR errors in the text runs of the subtitles and suggests a
+/// fix for each of them.
+///
+/// The tags are never modified, so a fix can be applied with
+/// [`apply_ocr_fixes`] after it has been reviewed.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{find_ocr_errors, OcrOptions, OcrRule};
+/// use libsrt::SubtitleIterator;
+///
+/// let subtitles =
+///     SubtitleIterator::from("1\n00:00:01,000 --> 00:00:02,000\n<i>l am here !</i>\n")
+///         .collect::<Result<Vec<_>, _>>()?;
+///
+/// let fixes = find_ocr_errors(&subtitles, &OcrOptions::default());
+///
+/// assert_eq!(fixes[0].span, 3..4);
+/// assert_eq!(fixes[0].replacement, "I");
+/// assert_eq!(fixes[0].rule, OcrRule::IAndL);
+/// assert_eq!(fixes[1].rule, OcrRule::SpaceBeforePunctuation);
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+pub fn find_ocr_errors(subtitles: &[Subtitle<'_>], options: &OcrOptions<'_>) -> Vec<OcrFix> {
+    let mut result = Vec::new();
+
+    for (index, subtitle) in subtitles.iter().enumerate() {
+        let raw = subtitle.text().as_raw();
+        let mut iterator = subtitle.text().iter();
+        let mut previous = None;
+
+        loop {
+            let offset = iterator.offset();
+
+            match iterator.next() {
+                Some(Ok(TextEvent::Text(run))) => {
+                    for (span, replacement, rule) in find_in_run(run, previous, options) {
+                        let span = offset + span.start..offset + span.end;
+
+                        result.push(OcrFix {
+                            index,
+                            counter: subtitle.counter(),
+                            original: raw[span.clone()].to_string(),
+                            span,
+                            replacement,
+                            rule,
+                        });
+                    }
+
+                    previous = run.chars().next_back().or(previous);
+                }
+                Some(Ok(TextEvent::Tag(_))) => {}
+                // the text of a subtitle with invalid tags is left unchanged
+                Some(Err(_)) => {
+                    result.retain(|fix: &OcrFix| fix.index != index);
+                    break;
+                }
+                None => break,
+            }
+        }
+    }
+
+    result
+}
+
+/// Applies the (accepted) fixes returned by [`find_ocr_errors`] to the
+/// subtitles.
+///
+/// Fixes, that overlap a previous fix of the same subtitle, are ignored.
+#[must_use]
+pub fn apply_ocr_fixes<'a>(subtitles: &[Subtitle<'a>], fixes: &[OcrFix]) -> Vec<Subtitle<'a>> {
+    let mut fixes = fixes.iter().collect::<Vec<_>>();
+    fixes.sort_by_key(|fix| (fix.index, fix.span.start));
+
+    subtitles
+        .iter()
+        .enumerate()
+        .map(|(index, subtitle)| {
+            let start = fixes.partition_point(|fix| fix.index < index);
+            let end = fixes.partition_point(|fix| fix.index <= index);
+
+            if start == end {
+                return subtitle.clone();
+            }
+
+            let raw = subtitle.text().as_raw();
+            let mut text = String::with_capacity(raw.len());
+            let mut position = 0;
+
+            for fix in &fixes[start..end] {
+                if fix.span.start < position || fix.span.end > raw.len() {
+                    continue;
+                }
+
+                text.push_str(&raw[position..fix.span.start]);
+                text.push_str(&fix.replacement);
+                position = fix.span.end;
+            }
+
+            text.push_str(&raw[position..]);
+
+            Subtitle::new(
+                subtitle.counter(),
+                subtitle.start(),
+                subtitle.duration(),
+                Text::from(text),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use crate::serialize::SerializeToString;
+    use crate::track::tests::parse;
+    use pretty_assertions::assert_eq;
+
+    fn fixes(text: &str, options: &OcrOptions<'_>) -> Vec<(Range<usize>, String, OcrRule)> {
+        find_in_run(text, None, options)
+    }
+
+    fn fixed(text: &str, dictionary: &OcrDictionary) -> String {
+        let options = OcrOptions {
+            dictionary: Some(dictionary),
+            ..OcrOptions::default()
+        };
+        let mut result = String::from(text);
+
+        for (span, replacement, _) in fixes(text, &options).into_iter().rev() {
+            result.replace_range(span, &replacement);
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_parse_dictionary() {
+        let dictionary = OcrDictionary::parse("# comment\n\nmodern\n  Wel| Well  \n").unwrap();
+
+        assert!(dictionary.is_known("modern"));
+        assert!(dictionary.is_known("well"));
+        assert!(!dictionary.is_known("Wel|"));
+        assert_eq!(dictionary.replacement("Wel|"), Some("Well"));
+        assert_eq!(dictionary.replacement("wel|"), None);
+
+        assert!(matches!(
+            OcrDictionary::parse("modern\na b c\n"),
+            Err(DictionaryError::InvalidLine { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_pipes() {
+        let dictionary = OcrDictionary::new();
+
+        assert_eq!(
+            fixed("|'m he||o, |T'S ME", &dictionary),
+            "I'm hello, IT'S ME"
+        );
+        assert_eq!(fixed("A|ex", &dictionary), "Alex");
+    }
+
+    #[test]
+    fn test_i_and_l() {
+        let dictionary = OcrDictionary::parse("like").unwrap();
+
+        assert_eq!(fixed("l wiII do it", &dictionary), "I will do it");
+        assert_eq!(fixed("lT'S OK", &dictionary), "IT'S OK");
+        assert_eq!(fixed("I Iike It", &dictionary), "I like It");
+        assert_eq!(fixed("Iittle", &dictionary), "Iittle");
+        assert_eq!(fixed("heIlo", &dictionary), "hello");
+    }
+
+    #[test]
+    fn test_i_and_l_correct_words() {
+        let dictionary = OcrDictionary::new();
+
+        assert_eq!(fixed("I'll", &dictionary), "I'll");
+        assert_eq!(fixed("Al", &dictionary), "Al");
+        assert_eq!(fixed("McIntosh", &dictionary), "McIntosh");
+        assert_eq!(fixed("GIs", &dictionary), "GIs");
+
+        let dictionary = OcrDictionary::parse("glad\nGIs").unwrap();
+
+        assert_eq!(fixed("GIad", &dictionary), "Glad");
+        assert_eq!(fixed("GIs", &dictionary), "GIs");
+    }
+
+    #[test]
+    fn test_rn_and_m() {
+        let dictionary = OcrDictionary::parse("modern\nmodem\nmore").unwrap();
+
+        assert_eq!(
+            fixed("rnore modern modern", &dictionary),
+            "more modern modern"
+        );
+        assert_eq!(fixed("rnore", &OcrDictionary::new()), "rnore");
+    }
+
+    #[test]
+    fn test_space_before_punctuation() {
+        let dictionary = OcrDictionary::new();
+
+        assert_eq!(fixed("Hello , world  !", &dictionary), "Hello, world!");
+        assert_eq!(fixed("Wait ... what ?", &dictionary), "Wait ... what?");
+        assert_eq!(fixed(" .", &dictionary), " .");
+    }
+
+    #[test]
+    fn test_dictionary_first() {
+        let dictionary = OcrDictionary::parse("l'll I'll").unwrap();
+
+        assert_eq!(
+            fixes(
+                "l'll",
+                &OcrOptions {
+                    dictionary: Some(&dictionary),
+                    ..OcrOptions::default()
+                }
+            ),
+            vec![(0..4, "I'll".into(), OcrRule::Dictionary)]
+        );
+    }
+
+    #[test]
+    fn test_find_and_apply() {
+        let subtitles = parse(
+            "1\n00:00:01,000 --> 00:00:02,000\n<font color=\"red\">|t's</font> <i>wiII</i> !\n\n\
+             2\n00:00:03,000 --> 00:00:04,000\nFine .\n",
+        );
+
+        let fixes = find_ocr_errors(&subtitles, &OcrOptions::default());
+
+        assert_eq!(
+            fixes
+                .iter()
+                .map(|fix| {
+                    (
+                        fix.counter,
+                        fix.span.clone(),
+                        fix.original.as_str(),
+                        fix.rule,
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 18..22, "|t's", OcrRule::Pipe),
+                (1, 33..37, "wiII", OcrRule::IAndL),
+                (1, 41..42, " ", OcrRule::SpaceBeforePunctuation),
+                (2, 4..5, " ", OcrRule::SpaceBeforePunctuation),
+            ]
+        );
+
+        // reject the last fix
+        let result = apply_ocr_fixes(&subtitles, &fixes[..3]);
+
+        assert_eq!(
+            result[..].serialize_to_string().unwrap(),
+            "1\n00:00:01,000 --> 00:00:02,000\n<font color=\"red\">It's</font> <i>will</i>!\n\n\
+             2\n00:00:03,000 --> 00:00:04,000\nFine .\n"
+        );
+    }
+}
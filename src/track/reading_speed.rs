@@ -0,0 +1,322 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::Subtitle;
+
+/// How fast the text of a subtitle has to be read.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ReadingSpeed {
+    /// The number of characters of the text without tags and line breaks.
+    pub characters: usize,
+    /// The number of words (separated by whitespace) of the text.
+    pub words: usize,
+    /// The number of characters per second.
+    pub cps: f64,
+    /// The number of words per minute.
+    pub wpm: f64,
+}
+
+impl ReadingSpeed {
+    /// Computes the reading speed of the subtitle from its plain text.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use libsrt::track::ReadingSpeed;
+    /// use libsrt::Subtitle;
+    /// use std::convert::TryFrom;
+    ///
+    /// let subtitle = Subtitle::try_from("1\n00:00:01,000 --> 00:00:03,000\n<i>Hello</i>\nworld!\n")?;
+    /// let speed = ReadingSpeed::new(&subtitle);
+    ///
+    /// assert_eq!(speed.characters, 11);
+    /// assert_eq!(speed.cps, 5.5);
+    /// assert_eq!(speed.wpm, 60.0);
+    /// # Ok::<(), libsrt::SubtitleError>(())
+    /// ```
+    #[must_use]
+    pub fn new(subtitle: &Subtitle<'_>) -> Self {
+        Self::with_duration(subtitle, subtitle.duration())
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn with_duration(subtitle: &Subtitle<'_>, duration: Duration) -> Self {
+        let text = subtitle.text().plain_text();
+
+        let characters = text.chars().filter(|c| !matches!(c, '\n' | '\r')).count();
+        let words = text.split_whitespace().count();
+        let seconds = duration.as_secs_f64();
+
+        Self {
+            characters,
+            words,
+            cps: characters as f64 / seconds,
+            wpm: words as f64 * 60.0 / seconds,
+        }
+    }
+}
+
+/// Configures how [`extend_durations`] extends the subtitles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendOptions {
+    /// The maximum number of characters per second, which must be positive.
+    pub target_cps: f64,
+    /// The minimum time in between the end of a subtitle and the start of the
+    /// next one.
+    pub min_gap: Duration,
+    /// A subtitle is not extended beyond this duration.
+    pub max_duration: Duration,
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for ExtendOptions {
+    fn default() -> Self {
+        Self {
+            target_cps: 17.0,
+            min_gap: Duration::from_millis(83),
+            max_duration: Duration::from_secs(7),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// An error, that is returned by [`extend_durations`] for invalid
+/// [`ExtendOptions`].
+#[derive(Debug, Copy, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum ExtendOptionsError {
+    /// [`ExtendOptions::target_cps`] is not a positive number.
+    #[error("the target of {0} characters per second is not a positive number")]
+    InvalidTargetCps(f64),
+}
+
+/// A subtitle that is still too fast after [`extend_durations`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Unfixed {
+    /// The index of the subtitle in the slice.
+    pub index: usize,
+    /// The counter of the subtitle.
+    pub counter: usize,
+    /// The reading speed after the subtitle has been extended as far as
+    /// possible.
+    pub speed: ReadingSpeed,
+}
+
+/// The result of [`extend_durations`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extended<'a> {
+    /// All subtitles, with the extended durations.
+    pub subtitles: Vec<Subtitle<'a>>,
+    /// The subtitles, whose reading speed is still above the target.
+    pub unfixed: Vec<Unfixed>,
+}
+
+/// Extends the end of subtitles, that are too fast to read, until
+/// [`ExtendOptions::target_cps`] is reached.
+///
+/// A subtitle is never extended past the start of the next subtitle minus
+/// [`ExtendOptions::min_gap`] or beyond [`ExtendOptions::max_duration`].
+/// Subtitles are never shortened. Subtitles, that can not be extended far
+/// enough, are reported in [`Extended::unfixed`].
+///
+/// # Errors
+///
+/// If [`ExtendOptions::target_cps`] is zero, negative or not a number.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{extend_durations, ExtendOptions};
+/// use libsrt::SubtitleIterator;
+/// use std::time::Duration;
+///
+/// let subtitles = SubtitleIterator::from(concat!(
+///     "1\n00:00:01,000 --> 00:00:01,500\nThis is way too fast.\n\n",
+///     "2\n00:00:10,000 --> 00:00:11,000\nThis too, but there is no time.\n\n",
+///     "3\n00:00:11,100 --> 00:00:13,000\nThe end\n",
+/// ))
+/// .collect::<Result<Vec<_>, _>>()?;
+///
+/// let extended = extend_durations(&subtitles, &ExtendOptions::default())?;
+///
+/// assert_eq!(
+///     extended.subtitles[0].duration(),
+///     Duration::from_secs_f64(21.0 / 17.0)
+/// );
+/// assert_eq!(extended.subtitles[1].end(), Duration::from_millis(11_017));
+/// assert_eq!(extended.unfixed.len(), 1);
+/// assert_eq!(extended.unfixed[0].counter, 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[allow(clippy::cast_precision_loss)]
+pub fn extend_durations<'a>(
+    subtitles: &[Subtitle<'a>],
+    options: &ExtendOptions,
+) -> Result<Extended<'a>, ExtendOptionsError> {
+    if options.target_cps.is_nan() || options.target_cps <= 0.0 {
+        return Err(ExtendOptionsError::InvalidTargetCps(options.target_cps));
+    }
+
+    let mut starts = subtitles.iter().map(Subtitle::start).collect::<Vec<_>>();
+    starts.sort_unstable();
+
+    let mut result = Vec::with_capacity(subtitles.len());
+    let mut unfixed = Vec::new();
+
+    for (index, subtitle) in subtitles.iter().enumerate() {
+        let speed = ReadingSpeed::new(subtitle);
+        let mut duration = subtitle.duration();
+
+        if speed.cps > options.target_cps {
+            // a tiny target can require more time than a `Duration` can hold,
+            // then the subtitle is extended as far as possible and stays unfixed
+            let required =
+                Duration::try_from_secs_f64(speed.characters as f64 / options.target_cps)
+                    .unwrap_or(Duration::MAX);
+
+            // the start of the next subtitle
+            let next_start = starts
+                .get(starts.partition_point(|start| *start <= subtitle.start()))
+                .copied();
+
+            let mut limit = options.max_duration;
+
+            if let Some(next_start) = next_start {
+                limit = limit.min(
+                    next_start
+                        .saturating_sub(subtitle.start())
+                        .saturating_sub(options.min_gap),
+                );
+            }
+
+            duration = duration.max(required.min(limit));
+
+            let speed = ReadingSpeed::with_duration(subtitle, duration);
+
+            if speed.cps > options.target_cps {
+                unfixed.push(Unfixed {
+                    index,
+                    counter: subtitle.counter(),
+                    speed,
+                });
+            }
+        }
+
+        result.push(Subtitle::new(
+            subtitle.counter(),
+            subtitle.start(),
+            duration,
+            subtitle.text().clone(),
+        ));
+    }
+
+    Ok(Extended {
+        subtitles: result,
+        unfixed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use crate::track::tests::parse;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_reading_speed() {
+        let subtitles = parse(concat!(
+            "1\n00:00:01,000 --> 00:00:03,000\n<i>Hello</i>\nworld!\n\n",
+            "2\n00:00:05,000 --> 00:00:05,500\n<font color=\"red\">你好</font>\n",
+        ));
+
+        assert_eq!(
+            ReadingSpeed::new(&subtitles[0]),
+            ReadingSpeed {
+                characters: 11,
+                words: 2,
+                cps: 5.5,
+                wpm: 60.0,
+            }
+        );
+        assert_eq!(
+            ReadingSpeed::new(&subtitles[1]),
+            ReadingSpeed {
+                characters: 2,
+                words: 1,
+                cps: 4.0,
+                wpm: 120.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_extend_durations() {
+        let subtitles = parse(concat!(
+            // 34 characters need 2s at 17 cps
+            "1\n00:00:01,000 --> 00:00:02,000\nThis subtitle needs 2 seconds, ok?\n\n",
+            // limited by the next subtitle
+            "2\n00:00:03,500 --> 00:00:04,000\nThis subtitle needs 2 seconds, ok?\n\n",
+            "3\n00:00:04,500 --> 00:00:05,000\nFine\n\n",
+            // limited by the maximum duration
+            "4\n00:00:10,000 --> 00:00:11,000\n",
+            "This subtitle needs a lot more time than that, so it is cut at exactly\n",
+            "seven seconds, which is the maximum duration, and then it is reported\n",
+        ));
+
+        let extended = extend_durations(&subtitles, &ExtendOptions::default()).unwrap();
+
+        assert_eq!(extended.subtitles[0].end(), Duration::from_secs(3));
+        assert_eq!(extended.subtitles[1].end(), Duration::from_millis(4417));
+        assert_eq!(extended.subtitles[2], subtitles[2]);
+        assert_eq!(extended.subtitles[3].end(), Duration::from_secs(17));
+
+        assert_eq!(
+            extended
+                .unfixed
+                .iter()
+                .map(|unfixed| (unfixed.index, unfixed.counter))
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (3, 4)]
+        );
+        assert!(extended.unfixed[1].speed.cps > 17.0);
+    }
+
+    #[test]
+    fn test_invalid_target_cps() {
+        let subtitles = parse("1\n00:00:01,000 --> 00:00:02,000\nHello\n");
+
+        for target_cps in [0.0, -17.0, f64::NAN] {
+            assert!(matches!(
+                extend_durations(
+                    &subtitles,
+                    &ExtendOptions {
+                        target_cps,
+                        ..ExtendOptions::default()
+                    }
+                ),
+                Err(ExtendOptionsError::InvalidTargetCps(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_tiny_target_cps() {
+        let subtitles = parse("1\n00:00:01,000 --> 00:00:02,000\nHello\n");
+        let options = ExtendOptions {
+            target_cps: 1e-300,
+            ..ExtendOptions::default()
+        };
+
+        let extended = extend_durations(&subtitles, &options).unwrap();
+
+        assert_eq!(extended.subtitles[0].duration(), options.max_duration);
+        assert_eq!(extended.unfixed.len(), 1);
+        assert_eq!(extended.unfixed[0].counter, 1);
+    }
+}
@@ -0,0 +1,487 @@
+use core::ops::Range;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use regex::Regex;
+
+use crate::text::TextEvent;
+use crate::{Subtitle, Text};
+
+/// Against what the regex of [`find_all`] and [`replace_all`] is matched.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MatchMode {
+    /// The regex is matched against each run of text in between two tags
+    /// separately, so a match never contains a tag.
+    TextRuns,
+    /// The regex is matched against the text without any tags, so a match can
+    /// span over tags like `...` in `..<b>.</b>`.
+    ///
+    /// On replacement, the tags inside of a match are kept and the
+    /// replacement is inserted in front of the first tag.
+    PlainText,
+}
+
+impl Default for MatchMode {
+    #[inline]
+    fn default() -> Self { Self::TextRuns }
+}
+
+/// A match of the regex in the text of a subtitle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextMatch {
+    /// The index of the subtitle in the slice.
+    pub index: usize,
+    /// The counter of the subtitle.
+    pub counter: usize,
+    /// The byte range of the match in the (unmodified) text of the subtitle,
+    /// which includes the tags inside of the match.
+    pub span: Range<usize>,
+    /// The matched text without any tags.
+    pub matched: String,
+    /// The text, that replaced the match (empty for [`find_all`]).
+    pub replacement: String,
+}
+
+/// The result of [`replace_all`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replaced<'a> {
+    /// The subtitles with the replaced text numbered from `1`.
+    ///
+    /// Subtitles without any text left are removed.
+    pub subtitles: Vec<Subtitle<'a>>,
+    /// Every replacement in the order in which it occurred.
+    pub replacements: Vec<TextMatch>,
+}
+
+/// A run of text in between two tags.
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    /// The start in the text without tags.
+    plain: usize,
+    /// The start in the raw text.
+    raw: usize,
+    len: usize,
+}
+
+/// The text of a subtitle without tags and the information to map positions
+/// back to the raw text.
+#[derive(Debug)]
+struct Projection {
+    plain: String,
+    runs: Vec<Run>,
+}
+
+impl Projection {
+    /// Falls back to [`Projection::raw`], if a tag can not be parsed, so that
+    /// no match in the text of the subtitle is lost.
+    fn new(text: &Text<'_>) -> Self {
+        let mut plain = String::with_capacity(text.len());
+        let mut runs = Vec::new();
+        let mut iterator = text.iter();
+
+        loop {
+            let raw = iterator.offset();
+
+            match iterator.next() {
+                Some(Ok(TextEvent::Text(run))) => {
+                    runs.push(Run {
+                        plain: plain.len(),
+                        raw,
+                        len: run.len(),
+                    });
+                    plain.push_str(run);
+                }
+                Some(Ok(TextEvent::Tag(_))) => {}
+                Some(Err(_)) => return Self::raw(text),
+                None => break,
+            }
+        }
+
+        Self { plain, runs }
+    }
+
+    /// Treats all of the raw text, including the tags, as a single run of
+    /// text.
+    fn raw(text: &Text<'_>) -> Self {
+        let raw = text.as_raw();
+
+        Self {
+            plain: String::from(raw),
+            runs: vec![Run {
+                plain: 0,
+                raw: 0,
+                len: raw.len(),
+            }],
+        }
+    }
+
+    /// Maps the start of a range in the plain text to the raw text.
+    fn raw_start(&self, position: usize) -> usize {
+        self.runs
+            .iter()
+            .find(|run| run.plain + run.len > position)
+            .or_else(|| self.runs.last())
+            .map_or(0, |run| {
+                run.raw + position.min(run.plain + run.len) - run.plain
+            })
+    }
+
+    /// Maps the end of a range in the plain text to the raw text.
+    fn raw_end(&self, position: usize) -> usize {
+        self.runs
+            .iter()
+            .rev()
+            .find(|run| run.plain < position)
+            .map_or_else(
+                || self.raw_start(position),
+                |run| run.raw + position - run.plain,
+            )
+    }
+
+    /// Returns the ranges in the raw text, that contain the chars of the
+    /// range in the plain text.
+    fn raw_ranges(&self, range: Range<usize>) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.runs.iter().filter_map(move |run| {
+            let start = range.start.max(run.plain);
+            let end = range.end.min(run.plain + run.len);
+
+            if start < end {
+                Some(run.raw + start - run.plain..run.raw + end - run.plain)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// A match in a single subtitle with the edits of the raw text.
+struct Edit {
+    span: Range<usize>,
+    matched: String,
+    replacement: String,
+    /// The ranges in the raw text, where the first one is replaced and the
+    /// others are removed.
+    ranges: Vec<Range<usize>>,
+}
+
+fn edits(text: &Text<'_>, regex: &Regex, replacement: Option<&str>, mode: MatchMode) -> Vec<Edit> {
+    let projection = Projection::new(text);
+
+    let mut result = Vec::new();
+
+    let mut push = |captures: &regex::Captures<'_>, plain: Range<usize>, span: Range<usize>| {
+        let mut expanded = String::new();
+
+        if let Some(replacement) = replacement {
+            captures.expand(replacement, &mut expanded);
+        }
+
+        let mut ranges = projection.raw_ranges(plain.clone()).collect::<Vec<_>>();
+
+        // an empty match inserts the replacement
+        if ranges.is_empty() {
+            ranges.push(span.start..span.start);
+        }
+
+        result.push(Edit {
+            matched: projection.plain[plain].into(),
+            span,
+            replacement: expanded,
+            ranges,
+        });
+    };
+
+    match mode {
+        MatchMode::TextRuns => {
+            for run in &projection.runs {
+                let haystack = &projection.plain[run.plain..run.plain + run.len];
+
+                for captures in regex.captures_iter(haystack) {
+                    let range = captures.get(0).map_or(0..0, |matched| matched.range());
+
+                    push(
+                        &captures,
+                        run.plain + range.start..run.plain + range.end,
+                        run.raw + range.start..run.raw + range.end,
+                    );
+                }
+            }
+        }
+        MatchMode::PlainText => {
+            for captures in regex.captures_iter(&projection.plain) {
+                let range = captures.get(0).map_or(0..0, |matched| matched.range());
+                let start = projection.raw_start(range.start);
+                let end = {
+                    if range.is_empty() {
+                        start
+                    } else {
+                        projection.raw_end(range.end)
+                    }
+                };
+
+                push(&captures, range, start..end);
+            }
+        }
+    }
+
+    result
+}
+
+fn matches(
+    subtitles: &[Subtitle<'_>],
+    regex: &Regex,
+    replacement: Option<&str>,
+    mode: MatchMode,
+) -> Vec<(usize, Vec<Edit>)> {
+    subtitles
+        .iter()
+        .enumerate()
+        .map(|(index, subtitle)| (index, edits(subtitle.text(), regex, replacement, mode)))
+        .collect()
+}
+
+fn to_match(index: usize, subtitle: &Subtitle<'_>, edit: Edit) -> TextMatch {
+    TextMatch {
+        index,
+        counter: subtitle.counter(),
+        span: edit.span,
+        matched: edit.matched,
+        replacement: edit.replacement,
+    }
+}
+
+/// Finds all matches of the regex in the text of the subtitles.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{find_all, MatchMode};
+/// use libsrt::SubtitleIterator;
+/// use regex::Regex;
+///
+/// let subtitles = SubtitleIterator::from("1\n00:00:01,000 --> 00:00:02,000\nWait..<b>.</b>\n")
+///     .collect::<Result<Vec<_>, _>>()?;
+/// let regex = Regex::new(r"\.\.\.").unwrap();
+///
+/// assert_eq!(find_all(&subtitles, &regex, MatchMode::TextRuns), vec![]);
+///
+/// let matches = find_all(&subtitles, &regex, MatchMode::PlainText);
+///
+/// assert_eq!(matches[0].counter, 1);
+/// assert_eq!(matches[0].span, 4..10);
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+pub fn find_all(subtitles: &[Subtitle<'_>], regex: &Regex, mode: MatchMode) -> Vec<TextMatch> {
+    matches(subtitles, regex, None, mode)
+        .into_iter()
+        .flat_map(|(index, edits)| {
+            edits
+                .into_iter()
+                .map(move |edit| to_match(index, &subtitles[index], edit))
+        })
+        .collect()
+}
+
+/// Replaces all matches of the regex in the text of the subtitles, without
+/// modifying any tags.
+///
+/// The replacement can refer to capture groups like [`Regex::replace_all`].
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{replace_all, MatchMode};
+/// use libsrt::SubtitleIterator;
+/// use regex::Regex;
+///
+/// let subtitles = SubtitleIterator::from(
+///     "1\n00:00:01,000 --> 00:00:02,000\n<font color=\"red\">Wait...</font>\n",
+/// )
+/// .collect::<Result<Vec<_>, _>>()?;
+/// let regex = Regex::new(r"\.\.\.").unwrap();
+///
+/// let replaced = replace_all(&subtitles, &regex, "…", MatchMode::TextRuns);
+///
+/// assert_eq!(
+///     replaced.subtitles[0].text(),
+///     "<font color=\"red\">Wait…</font>"
+/// );
+/// assert_eq!(replaced.replacements[0].span, 22..25);
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+pub fn replace_all<'a>(
+    subtitles: &[Subtitle<'a>],
+    regex: &Regex,
+    replacement: &str,
+    mode: MatchMode,
+) -> Replaced<'a> {
+    let mut result = Vec::with_capacity(subtitles.len());
+    let mut replacements = Vec::new();
+
+    for (index, edits) in matches(subtitles, regex, Some(replacement), mode) {
+        let subtitle = &subtitles[index];
+
+        let text = {
+            if edits.is_empty() {
+                subtitle.text().clone()
+            } else {
+                let raw = subtitle.text().as_raw();
+                let mut text = String::with_capacity(raw.len());
+                let mut position = 0;
+
+                for edit in &edits {
+                    for (number, range) in edit.ranges.iter().enumerate() {
+                        text.push_str(&raw[position..range.start]);
+
+                        if number == 0 {
+                            text.push_str(&edit.replacement);
+                        }
+
+                        position = range.end;
+                    }
+                }
+
+                text.push_str(&raw[position..]);
+                Text::from(text)
+            }
+        };
+
+        replacements.extend(
+            edits
+                .into_iter()
+                .map(|edit| to_match(index, subtitle, edit)),
+        );
+
+        if !text.plain_text().trim().is_empty() {
+            result.push(Subtitle::new(
+                result.len() + 1,
+                subtitle.start(),
+                subtitle.duration(),
+                text,
+            ));
+        }
+    }
+
+    Replaced {
+        subtitles: result,
+        replacements,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::trivial_regex)] // plain words are enough to test the replacement
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use crate::serialize::SerializeToString;
+    use crate::track::tests::parse;
+    use pretty_assertions::assert_eq;
+
+    const INPUT: &str = "1\n00:00:01,000 --> 00:00:02,000\n<i>Hello</i> wor<b>ld</b>!\n\n2\n00:00:03,000 --> 00:00:04,000\n<font color=\"red\">red</font>\n";
+
+    #[test]
+    fn test_projection_raw() {
+        let projection = Projection::raw(&Text::from("<i>Hello</i>"));
+
+        assert_eq!(projection.plain, "<i>Hello</i>");
+        assert_eq!(projection.raw_ranges(3..8).collect::<Vec<_>>(), vec![3..8]);
+        assert_eq!(projection.raw_start(12), 12);
+    }
+
+    #[test]
+    fn test_find_text_runs() {
+        let subtitles = parse(INPUT);
+        let regex = Regex::new("l+").unwrap();
+
+        let matches = find_all(&subtitles, &regex, MatchMode::TextRuns);
+
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.counter, m.span.clone(), m.matched.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, 5..7, "ll"), (1, 19..20, "l")]
+        );
+    }
+
+    #[test]
+    fn test_find_plain_text() {
+        let subtitles = parse(INPUT);
+        let regex = Regex::new("world|red").unwrap();
+
+        let matches = find_all(&subtitles, &regex, MatchMode::PlainText);
+
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.index, m.span.clone(), m.matched.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(0, 13..21, "world"), (1, 18..21, "red")]
+        );
+        assert_eq!(&subtitles[0].text()[13..21], "wor<b>ld");
+    }
+
+    #[test]
+    fn test_replace_text_runs() {
+        let subtitles = parse(INPUT);
+        let regex = Regex::new("(e)(l+)").unwrap();
+
+        let replaced = replace_all(&subtitles, &regex, "${2}$1", MatchMode::TextRuns);
+
+        assert_eq!(
+            replaced.subtitles[..].serialize_to_string().unwrap(),
+            "1\n00:00:01,000 --> 00:00:02,000\n<i>Hlleo</i> wor<b>ld</b>!\n\n2\n00:00:03,000 --> 00:00:04,000\n<font color=\"red\">red</font>\n"
+        );
+        assert_eq!(
+            replaced.replacements,
+            vec![TextMatch {
+                index: 0,
+                counter: 1,
+                span: 4..7,
+                matched: "ell".into(),
+                replacement: "lle".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_replace_plain_text() {
+        let subtitles = parse(INPUT);
+        let regex = Regex::new("world").unwrap();
+
+        let replaced = replace_all(&subtitles, &regex, "there", MatchMode::PlainText);
+
+        assert_eq!(replaced.subtitles[0].text(), "<i>Hello</i> there<b></b>!");
+        assert_eq!(replaced.replacements[0].span, 13..21);
+    }
+
+    #[test]
+    fn test_replace_empty_match() {
+        let subtitles = parse(INPUT);
+        let regex = Regex::new("^").unwrap();
+
+        let replaced = replace_all(&subtitles, &regex, "- ", MatchMode::PlainText);
+
+        assert_eq!(replaced.subtitles[0].text(), "<i>- Hello</i> wor<b>ld</b>!");
+        assert_eq!(
+            replaced.subtitles[1].text(),
+            "<font color=\"red\">- red</font>"
+        );
+    }
+
+    #[test]
+    fn test_remove_empty() {
+        let subtitles = parse(INPUT);
+        let regex = Regex::new("red").unwrap();
+
+        let replaced = replace_all(&subtitles, &regex, "", MatchMode::TextRuns);
+
+        assert_eq!(replaced.subtitles.len(), 1);
+        assert_eq!(replaced.subtitles[0].counter(), 1);
+    }
+}
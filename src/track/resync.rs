@@ -0,0 +1,553 @@
+use core::time::Duration;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Subtitle;
+
+/// Configures [`resync`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResyncOptions {
+    /// The cost of leaving a cue of either track unmatched.
+    ///
+    /// The cost of matching two cues is the sum of the (absolute) logarithms
+    /// of the ratios of their durations and the distances to the next cues, so
+    /// a penalty of `1.0` allows the timing of a match to differ by a factor of
+    /// about `e`.
+    pub skip_penalty: f64,
+    /// Matches, that deviate more than this from the offset of the
+    /// surrounding matches, are discarded as outliers.
+    pub max_deviation: Duration,
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for ResyncOptions {
+    fn default() -> Self {
+        Self {
+            skip_penalty: 0.6,
+            max_deviation: Duration::from_secs(1),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// A cue of the mistimed track, that has been matched with a cue of the
+/// reference track.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Anchor {
+    /// The index of the cue in the reference track.
+    pub reference: usize,
+    /// The index of the cue in the mistimed track.
+    pub target: usize,
+    /// The start of the cue in the reference track.
+    pub reference_time: Duration,
+    /// The start of the cue in the mistimed track.
+    pub target_time: Duration,
+    /// How different the timing of both cues is, where `0.0` is identical.
+    pub cost: f64,
+}
+
+/// A monotonic, piecewise-linear map from the times of the mistimed track to
+/// the times of the reference track.
+///
+/// In between two points the time is interpolated, outside of them it is
+/// extrapolated with the average slope of all points.
+///
+/// ## Example
+///
+/// ```
+/// use core::time::Duration;
+/// use libsrt::track::TimeMap;
+///
+/// let map = TimeMap::new(vec![
+///     (Duration::from_secs(10), Duration::from_secs(12)),
+///     (Duration::from_secs(20), Duration::from_secs(32)),
+/// ]);
+///
+/// assert_eq!(map.map(Duration::from_secs(15)), Duration::from_secs(22));
+/// assert_eq!(map.map(Duration::from_secs(25)), Duration::from_secs(42));
+/// assert_eq!(map.map(Duration::from_secs(0)), Duration::from_secs(0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TimeMap {
+    points: Vec<(Duration, Duration)>,
+}
+
+impl TimeMap {
+    /// Creates a map through the given `(from, to)` points.
+    ///
+    /// Points, that would make the map decreasing, are ignored.
+    #[must_use]
+    pub fn new(mut points: Vec<(Duration, Duration)>) -> Self {
+        points.sort();
+        points.dedup_by(|(from, _), (previous, _)| from == previous);
+
+        let mut result: Vec<(Duration, Duration)> = Vec::with_capacity(points.len());
+
+        for point in points {
+            if result.last().is_none_or(|last| last.1 < point.1) {
+                result.push(point);
+            }
+        }
+
+        Self { points: result }
+    }
+
+    /// Returns the `(from, to)` points of the map.
+    #[must_use]
+    pub fn points(&self) -> &[(Duration, Duration)] { &self.points }
+
+    fn slope(&self) -> f64 {
+        match self.points.as_slice() {
+            [(first_from, first_to), .., (last_from, last_to)] => {
+                (last_to.as_secs_f64() - first_to.as_secs_f64())
+                    / (last_from.as_secs_f64() - first_from.as_secs_f64())
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Maps a time of the mistimed track to the reference track.
+    ///
+    /// A map without any points returns the time unchanged and times before
+    /// zero are clamped to zero.
+    #[must_use]
+    pub fn map(&self, time: Duration) -> Duration {
+        let index = self.points.partition_point(|(from, _)| *from <= time);
+
+        let ((from, to), slope) = {
+            match (
+                index
+                    .checked_sub(1)
+                    .and_then(|index| self.points.get(index)),
+                self.points.get(index),
+            ) {
+                (Some(start), Some(end)) => {
+                    (
+                        *start,
+                        (end.1.as_secs_f64() - start.1.as_secs_f64())
+                            / (end.0.as_secs_f64() - start.0.as_secs_f64()),
+                    )
+                }
+                (Some(point), None) | (None, Some(point)) => (*point, self.slope()),
+                (None, None) => return time,
+            }
+        };
+
+        from_secs(slope.mul_add(time.as_secs_f64() - from.as_secs_f64(), to.as_secs_f64()))
+    }
+}
+
+/// The result of [`resync`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resynced<'a> {
+    /// The mistimed subtitles with the corrected timings.
+    pub subtitles: Vec<Subtitle<'a>>,
+    /// The map, that has been used to correct the timings.
+    pub map: TimeMap,
+    /// The matched cues, that have not been discarded as outliers.
+    pub anchors: Vec<Anchor>,
+    /// The indices of the mistimed cues, that are not part of `subtitles`,
+    /// because they would end before they start, for example cues in front of
+    /// the first anchor, that are mapped to a time before zero.
+    pub removed: Vec<usize>,
+    /// How confident the alignment is, from `0.0` (no match) to `1.0`
+    /// (every cue matched with identical timing).
+    pub confidence: f64,
+}
+
+/// Rounds the seconds to milliseconds, the precision of a srt timestamp.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_secs(seconds: f64) -> Duration {
+    Duration::from_millis((seconds.max(0.0) * 1000.0).round() as u64)
+}
+
+/// The timing of a cue, which does not depend on an offset of the track.
+#[derive(Debug, Copy, Clone)]
+struct Rhythm {
+    duration: f64,
+    /// The time until the start of the next cue.
+    interval: Option<f64>,
+}
+
+impl Rhythm {
+    fn of(subtitles: &[Subtitle<'_>]) -> Vec<Self> {
+        subtitles
+            .iter()
+            .enumerate()
+            .map(|(index, subtitle)| {
+                Self {
+                    duration: subtitle.duration().as_secs_f64(),
+                    interval: subtitles
+                        .get(index + 1)
+                        .map(|next| next.start().as_secs_f64() - subtitle.start().as_secs_f64()),
+                }
+            })
+            .collect()
+    }
+
+    fn cost(self, other: Self) -> f64 {
+        // the smoothing prevents that short cues dominate the cost
+        let ratio = |a: f64, b: f64| ((a.max(0.0) + 0.1) / (b.max(0.0) + 0.1)).ln().abs();
+
+        ratio(self.duration, other.duration)
+            + match (self.interval, other.interval) {
+                (Some(a), Some(b)) => ratio(a, b),
+                (None, None) => 0.0,
+                _ => 0.5,
+            }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Step {
+    Match,
+    SkipReference,
+    SkipTarget,
+}
+
+/// Aligns both sequences like dynamic time warping, but each cue can be
+/// matched at most once, and returns the matched pairs of indices.
+fn align(reference: &[Rhythm], target: &[Rhythm], skip_penalty: f64) -> Vec<(usize, usize, f64)> {
+    let width = target.len() + 1;
+    let mut costs = vec![0.0; (reference.len() + 1) * width];
+    let mut steps = vec![Step::Match; (reference.len() + 1) * width];
+
+    for i in 0..=reference.len() {
+        for j in 0..=target.len() {
+            let mut best = (f64::INFINITY, Step::Match);
+
+            if i == 0 && j == 0 {
+                continue;
+            }
+
+            if i > 0 && j > 0 {
+                let cost = costs[(i - 1) * width + j - 1] + reference[i - 1].cost(target[j - 1]);
+                best = (cost, Step::Match);
+            }
+
+            if i > 0 && costs[(i - 1) * width + j] + skip_penalty < best.0 {
+                best = (
+                    costs[(i - 1) * width + j] + skip_penalty,
+                    Step::SkipReference,
+                );
+            }
+
+            if j > 0 && costs[i * width + j - 1] + skip_penalty < best.0 {
+                best = (costs[i * width + j - 1] + skip_penalty, Step::SkipTarget);
+            }
+
+            costs[i * width + j] = best.0;
+            steps[i * width + j] = best.1;
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (reference.len(), target.len());
+
+    while i > 0 || j > 0 {
+        match steps[i * width + j] {
+            Step::Match => {
+                result.push((i - 1, j - 1, reference[i - 1].cost(target[j - 1])));
+                i -= 1;
+                j -= 1;
+            }
+            Step::SkipReference => i -= 1,
+            Step::SkipTarget => j -= 1,
+        }
+    }
+
+    result.reverse();
+    result
+}
+
+/// Discards anchors, whose offset deviates from the median offset of their
+/// neighbours.
+fn remove_outliers(anchors: &[Anchor], max_deviation: Duration) -> Vec<Anchor> {
+    const NEIGHBOURS: usize = 3;
+
+    let offset =
+        |anchor: &Anchor| anchor.reference_time.as_secs_f64() - anchor.target_time.as_secs_f64();
+
+    anchors
+        .iter()
+        .enumerate()
+        .filter(|(index, anchor)| {
+            let mut offsets = anchors[index.saturating_sub(NEIGHBOURS)..]
+                .iter()
+                .take(2 * NEIGHBOURS + 1)
+                .map(offset)
+                .collect::<Vec<_>>();
+
+            offsets.sort_by(f64::total_cmp);
+
+            (offset(anchor) - offsets[offsets.len() / 2]).abs() <= max_deviation.as_secs_f64()
+        })
+        .map(|(_, anchor)| *anchor)
+        .collect()
+}
+
+/// Corrects the timings of a mistimed track with the help of a correctly
+/// timed reference track, for example in another language.
+///
+/// The cues of both tracks are aligned by their durations and the distances
+/// between them, where cues without a counterpart in the other track are
+/// skipped. The matched cues (anchors) form a piecewise-linear [`TimeMap`],
+/// which corrects offsets and different frame rates, even if they change
+/// in between, like after a commercial break.
+///
+/// ## Example
+///
+/// ```
+/// use core::time::Duration;
+/// use libsrt::track::{resync, ResyncOptions};
+/// use libsrt::SubtitleIterator;
+///
+/// let reference = SubtitleIterator::from(
+///     "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n\
+///      2\n00:00:04,000 --> 00:00:07,000\nHow are you?\n\n\
+///      3\n00:00:07,500 --> 00:00:08,000\nFine.\n",
+/// )
+/// .collect::<Result<Vec<_>, _>>()?;
+/// let mistimed = SubtitleIterator::from(
+///     "1\n00:00:03,000 --> 00:00:04,000\nHallo\n\n\
+///      2\n00:00:06,000 --> 00:00:09,000\nWie geht's?\n\n\
+///      3\n00:00:09,500 --> 00:00:10,000\nGut.\n",
+/// )
+/// .collect::<Result<Vec<_>, _>>()?;
+///
+/// let resynced = resync(&reference, &mistimed, &ResyncOptions::default());
+///
+/// assert_eq!(resynced.subtitles[1].start(), Duration::from_secs(4));
+/// assert_eq!(resynced.anchors.len(), 3);
+/// assert!(resynced.confidence > 0.9);
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn resync<'a>(
+    reference: &[Subtitle<'_>],
+    mistimed: &[Subtitle<'a>],
+    options: &ResyncOptions,
+) -> Resynced<'a> {
+    let anchors = align(
+        &Rhythm::of(reference),
+        &Rhythm::of(mistimed),
+        options.skip_penalty,
+    )
+    .into_iter()
+    .map(|(reference_index, target, cost)| {
+        Anchor {
+            reference: reference_index,
+            target,
+            reference_time: reference[reference_index].start(),
+            target_time: mistimed[target].start(),
+            cost,
+        }
+    })
+    .collect::<Vec<_>>();
+
+    let anchors = remove_outliers(&anchors, options.max_deviation);
+
+    let map = TimeMap::new(
+        anchors
+            .iter()
+            .map(|anchor| (anchor.target_time, anchor.reference_time))
+            .collect(),
+    );
+
+    let mut subtitles = Vec::with_capacity(mistimed.len());
+    let mut removed = Vec::new();
+
+    for (index, subtitle) in mistimed.iter().enumerate() {
+        let start = map.map(subtitle.start());
+        let end = map.map(subtitle.end());
+
+        // a subtitle must not have a duration of zero
+        if end <= start {
+            removed.push(index);
+            continue;
+        }
+
+        // the end is after the start, which has been checked above
+        #[allow(clippy::unchecked_time_subtraction)]
+        let duration = end - start;
+
+        subtitles.push(Subtitle::new(
+            subtitle.counter(),
+            start,
+            duration,
+            subtitle.text().clone(),
+        ));
+    }
+
+    let confidence = {
+        let total = reference.len().max(mistimed.len());
+
+        if total == 0 {
+            0.0
+        } else {
+            let cost = anchors.iter().map(|anchor| anchor.cost).sum::<f64>();
+            let matched = anchors.len() as f64;
+
+            matched / total as f64 * (-cost / matched.max(1.0)).exp()
+        }
+    };
+
+    Resynced {
+        subtitles,
+        map,
+        anchors,
+        removed,
+        confidence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Text;
+    use pretty_assertions::assert_eq;
+
+    fn track(cues: &[(u64, u64)]) -> Vec<Subtitle<'static>> {
+        cues.iter()
+            .enumerate()
+            .map(|(index, (start, end))| {
+                Subtitle::new(
+                    index + 1,
+                    Duration::from_millis(*start),
+                    Duration::from_millis(end - start),
+                    Text::from("text"),
+                )
+            })
+            .collect()
+    }
+
+    const REFERENCE: &[(u64, u64)] = &[
+        (1_000, 2_500),
+        (3_000, 6_000),
+        (6_200, 7_000),
+        (9_000, 13_000),
+        (15_000, 16_000),
+        (16_500, 19_500),
+        (22_000, 23_200),
+        (25_000, 29_000),
+        (29_100, 30_000),
+        (34_000, 36_500),
+    ];
+
+    #[test]
+    fn test_time_map() {
+        let map = TimeMap::new(vec![
+            (Duration::from_secs(20), Duration::from_secs(30)),
+            (Duration::from_secs(10), Duration::from_secs(10)),
+            // would be decreasing
+            (Duration::from_secs(15), Duration::from_secs(5)),
+        ]);
+
+        assert_eq!(
+            map.points(),
+            &[
+                (Duration::from_secs(10), Duration::from_secs(10)),
+                (Duration::from_secs(20), Duration::from_secs(30)),
+            ]
+        );
+        assert_eq!(map.map(Duration::from_secs(12)), Duration::from_secs(14));
+        assert_eq!(map.map(Duration::from_secs(4)), Duration::ZERO);
+        assert_eq!(
+            TimeMap::default().map(Duration::from_secs(4)),
+            Duration::from_secs(4)
+        );
+    }
+
+    #[test]
+    fn test_offset_and_frame_rate() {
+        let reference = track(REFERENCE);
+        // 25 fps instead of 23.976 fps and an offset of 2.5 seconds
+        let mistimed = track(
+            &REFERENCE
+                .iter()
+                .map(|(start, end)| (start * 1000 / 1043 + 2_500, end * 1000 / 1043 + 2_500))
+                .collect::<Vec<_>>(),
+        );
+
+        let resynced = resync(&reference, &mistimed, &ResyncOptions::default());
+
+        assert_eq!(resynced.anchors.len(), REFERENCE.len());
+        assert!(resynced.confidence > 0.9);
+
+        for (resynced, expected) in resynced.subtitles.iter().zip(&reference) {
+            assert!(resynced.start().abs_diff(expected.start()) <= Duration::from_millis(2));
+            assert!(resynced.end().abs_diff(expected.end()) <= Duration::from_millis(2));
+        }
+    }
+
+    #[test]
+    fn test_different_cues() {
+        let reference = track(REFERENCE);
+        let mut cues = REFERENCE
+            .iter()
+            .map(|(start, end)| (start + 4_000, end + 4_000))
+            .collect::<Vec<_>>();
+        // a missing and an additional cue
+        cues.remove(4);
+        cues.insert(5, (24_000, 24_400));
+
+        let mistimed = track(&cues);
+        let resynced = resync(&reference, &mistimed, &ResyncOptions::default());
+
+        assert!(resynced.anchors.len() >= 7);
+        assert!(resynced.confidence > 0.5);
+        assert!(resynced.confidence < 1.0);
+
+        for anchor in &resynced.anchors {
+            assert_eq!(
+                anchor.reference_time + Duration::from_secs(4),
+                anchor.target_time
+            );
+        }
+
+        assert_eq!(resynced.subtitles[0].start(), Duration::from_secs(1));
+        assert_eq!(resynced.subtitles[9].start(), Duration::from_secs(34));
+    }
+
+    #[test]
+    fn test_empty() {
+        let resynced = resync(&[], &track(REFERENCE), &ResyncOptions::default());
+
+        assert_eq!(resynced.anchors, vec![]);
+        assert_eq!(resynced.removed, vec![]);
+        assert!(resynced.confidence.abs() < f64::EPSILON);
+        assert_eq!(resynced.subtitles, track(REFERENCE));
+    }
+
+    #[test]
+    fn test_cue_before_zero() {
+        let reference = track(&[(1_000, 2_000), (4_000, 7_000), (7_500, 8_000)]);
+        // an additional cue in front of the others, which are 5 seconds late
+        let mistimed = track(&[
+            (100, 500),
+            (6_000, 7_000),
+            (9_000, 12_000),
+            (12_500, 13_000),
+        ]);
+
+        let resynced = resync(&reference, &mistimed, &ResyncOptions::default());
+
+        assert_eq!(resynced.removed, vec![0]);
+        assert_eq!(
+            resynced
+                .subtitles
+                .iter()
+                .map(|subtitle| (subtitle.counter(), subtitle.start(), subtitle.end()))
+                .collect::<Vec<_>>(),
+            vec![
+                (2, Duration::from_secs(1), Duration::from_secs(2)),
+                (3, Duration::from_secs(4), Duration::from_secs(7)),
+                (4, Duration::from_millis(7_500), Duration::from_secs(8)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::vec::Vec;
+
+use crate::text::SdhOptions;
+use crate::Subtitle;
+
+/// Removes annotations for the hearing impaired from all subtitles with
+/// [`Text::remove_sdh`].
+///
+/// Subtitles without any text left are dropped and the remaining subtitles
+/// are numbered from `1`.
+///
+/// [`Text::remove_sdh`]: crate::Text::remove_sdh
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::text::SdhOptions;
+/// use libsrt::track::remove_sdh;
+/// use libsrt::SubtitleIterator;
+///
+/// let subtitles = SubtitleIterator::from(concat!(
+///     "1\n00:00:01,000 --> 00:00:02,000\n[DOOR SLAMS]\n\n",
+///     "2\n00:00:02,000 --> 00:00:03,000\nJOHN: Who's there?\n",
+/// ))
+/// .collect::<Result<Vec<_>, _>>()?;
+///
+/// let subtitles = remove_sdh(&subtitles, &SdhOptions::default());
+///
+/// assert_eq!(subtitles.len(), 1);
+/// assert_eq!(subtitles[0].counter(), 1);
+/// assert_eq!(subtitles[0].text(), "Who's there?");
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+pub fn remove_sdh<'a>(subtitles: &[Subtitle<'a>], options: &SdhOptions) -> Vec<Subtitle<'a>> {
+    subtitles
+        .iter()
+        .filter_map(|subtitle| {
+            let text = subtitle.text().remove_sdh(options);
+
+            if text.plain_text().trim().is_empty() {
+                None
+            } else {
+                Some((subtitle, text))
+            }
+        })
+        .enumerate()
+        .map(|(index, (subtitle, text))| {
+            Subtitle::new(index + 1, subtitle.start(), subtitle.duration(), text)
+        })
+        .collect()
+}
@@ -0,0 +1,292 @@
+use core::time::Duration;
+
+use alloc::vec::Vec;
+
+use crate::Subtitle;
+
+/// The point at which [`split`] divides a track.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SplitPoint {
+    /// Splits the track at a point in time.
+    ///
+    /// A subtitle that is visible at this point in time is cut into two
+    /// subtitles, one for each part.
+    Time(Duration),
+    /// Splits the track in front of the subtitle with the index (not the
+    /// counter) in the provided slice.
+    Index(usize),
+}
+
+/// Divides a track into two parts at the provided [`SplitPoint`].
+///
+/// The subtitles of the second part are moved, so that the second part
+/// starts at zero. If the track is split at an index, the second part starts
+/// with the subtitle at that index. The subtitles of both parts are numbered
+/// from `1`.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{split, SplitPoint};
+/// use libsrt::SubtitleIterator;
+/// use std::time::Duration;
+///
+/// let subtitles = SubtitleIterator::from(concat!(
+///     "1\n00:00:01,000 --> 00:00:02,000\nFirst\n\n",
+///     "2\n00:20:01,000 --> 00:20:02,000\nSecond\n",
+/// ))
+/// .collect::<Result<Vec<_>, _>>()?;
+///
+/// let (first, second) = split(&subtitles, SplitPoint::Time(Duration::from_secs(20 * 60)));
+///
+/// assert_eq!(first.len(), 1);
+/// assert_eq!(second[0].counter(), 1);
+/// assert_eq!(second[0].start(), Duration::from_secs(1));
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+// every subtraction is guarded by a comparison in front of it
+#[allow(clippy::unchecked_time_subtraction)]
+pub fn split<'a>(
+    subtitles: &[Subtitle<'a>],
+    point: SplitPoint,
+) -> (Vec<Subtitle<'a>>, Vec<Subtitle<'a>>) {
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+
+    match point {
+        SplitPoint::Time(time) => {
+            for subtitle in subtitles {
+                if subtitle.end() <= time {
+                    first.push((subtitle.start(), subtitle.end(), subtitle));
+                } else if subtitle.start() >= time {
+                    second.push((subtitle.start() - time, subtitle.end() - time, subtitle));
+                } else {
+                    first.push((subtitle.start(), time, subtitle));
+                    second.push((Duration::default(), subtitle.end() - time, subtitle));
+                }
+            }
+        }
+        SplitPoint::Index(index) => {
+            let index = index.min(subtitles.len());
+            let offset = subtitles
+                .get(index)
+                .map_or_else(Duration::default, Subtitle::start);
+
+            for subtitle in &subtitles[..index] {
+                first.push((subtitle.start(), subtitle.end(), subtitle));
+            }
+
+            for subtitle in &subtitles[index..] {
+                // subtitles in front of the first one of the second part are cut off
+                if subtitle.end() > offset {
+                    let start = subtitle.start().max(offset) - offset;
+                    second.push((start, subtitle.end() - offset, subtitle));
+                }
+            }
+        }
+    }
+
+    (renumber(first), renumber(second))
+}
+
+// the parts of a subtitle never end in front of their start
+#[allow(clippy::unchecked_time_subtraction)]
+fn renumber<'a>(subtitles: Vec<(Duration, Duration, &Subtitle<'a>)>) -> Vec<Subtitle<'a>> {
+    subtitles
+        .into_iter()
+        .enumerate()
+        .map(|(index, (start, end, subtitle))| {
+            Subtitle::new(index + 1, start, end - start, subtitle.text().clone())
+        })
+        .collect()
+}
+
+/// Determines where each part begins in the track created by [`join`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JoinOffsets<'a> {
+    /// The duration of each part (for example the length of the video file).
+    ///
+    /// A part begins after all previous parts ended. If there are fewer
+    /// durations than parts, a part without a duration ends with its last
+    /// subtitle.
+    Durations(&'a [Duration]),
+    /// A part begins after the last subtitle of the previous part with this
+    /// gap in between.
+    Gap(Duration),
+}
+
+/// Concatenates several tracks into a single one.
+///
+/// Each part is moved by an offset, which is determined by [`JoinOffsets`].
+/// The subtitles of the result are numbered from `1`.
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{join, JoinOffsets};
+/// use libsrt::SubtitleIterator;
+/// use std::time::Duration;
+///
+/// let first = SubtitleIterator::from("1\n00:00:01,000 --> 00:00:02,000\nFirst\n")
+///     .collect::<Result<Vec<_>, _>>()?;
+/// let second = SubtitleIterator::from("1\n00:00:01,000 --> 00:00:02,000\nSecond\n")
+///     .collect::<Result<Vec<_>, _>>()?;
+///
+/// let joined = join(
+///     &[&first, &second],
+///     JoinOffsets::Durations(&[Duration::from_secs(20 * 60)]),
+/// );
+///
+/// assert_eq!(joined[1].counter(), 2);
+/// assert_eq!(joined[1].start(), Duration::from_secs(20 * 60 + 1));
+/// # Ok::<(), libsrt::SubtitleError>(())
+/// ```
+#[must_use]
+pub fn join<'a>(parts: &[&[Subtitle<'a>]], offsets: JoinOffsets<'_>) -> Vec<Subtitle<'a>> {
+    let mut result = Vec::with_capacity(parts.iter().map(|part| part.len()).sum());
+    let mut offset = Duration::default();
+
+    for (index, part) in parts.iter().enumerate() {
+        for subtitle in *part {
+            result.push(Subtitle::new(
+                result.len() + 1,
+                subtitle.start() + offset,
+                subtitle.duration(),
+                subtitle.text().clone(),
+            ));
+        }
+
+        let last_end = part.iter().map(Subtitle::end).max();
+
+        match offsets {
+            JoinOffsets::Durations(durations) => {
+                offset += durations
+                    .get(index)
+                    .copied()
+                    .unwrap_or_else(|| last_end.unwrap_or_default());
+            }
+            JoinOffsets::Gap(gap) => {
+                // an empty part does not take up any time
+                if let Some(last_end) = last_end {
+                    offset += last_end + gap;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serialize::SerializeToString;
+    use crate::track::tests::parse;
+    use pretty_assertions::assert_eq;
+
+    const TRACK: &str = concat!(
+        "1\n00:00:01,000 --> 00:00:02,000\nOne\n\n",
+        "2\n00:00:09,000 --> 00:00:11,000\nTwo\n\n",
+        "3\n00:00:12,000 --> 00:00:13,500\nThree\n",
+    );
+
+    #[test]
+    fn test_split_at_time() {
+        let (first, second) = split(&parse(TRACK), SplitPoint::Time(Duration::from_secs(10)));
+
+        assert_eq!(
+            first[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:02,000\nOne\n\n",
+                "2\n00:00:09,000 --> 00:00:10,000\nTwo\n",
+            )
+        );
+        assert_eq!(
+            second[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:00,000 --> 00:00:01,000\nTwo\n\n",
+                "2\n00:00:02,000 --> 00:00:03,500\nThree\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_split_at_index() {
+        let (first, second) = split(&parse(TRACK), SplitPoint::Index(1));
+
+        assert_eq!(
+            first[..].serialize_to_string().unwrap(),
+            "1\n00:00:01,000 --> 00:00:02,000\nOne\n"
+        );
+        assert_eq!(
+            second[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:00,000 --> 00:00:02,000\nTwo\n\n",
+                "2\n00:00:03,000 --> 00:00:04,500\nThree\n",
+            )
+        );
+
+        let (first, second) = split(&parse(TRACK), SplitPoint::Index(10));
+
+        assert_eq!(first.len(), 3);
+        assert_eq!(second, Vec::new());
+    }
+
+    #[test]
+    fn test_join_durations() {
+        let first = parse(TRACK);
+        let second = parse("5\n00:00:01,000 --> 00:00:02,000\nFour\n");
+
+        assert_eq!(
+            join(
+                &[&first, &second, &second],
+                JoinOffsets::Durations(&[Duration::from_mins(1)])
+            )[..]
+                .serialize_to_string()
+                .unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:02,000\nOne\n\n",
+                "2\n00:00:09,000 --> 00:00:11,000\nTwo\n\n",
+                "3\n00:00:12,000 --> 00:00:13,500\nThree\n\n",
+                "4\n00:01:01,000 --> 00:01:02,000\nFour\n\n",
+                "5\n00:01:03,000 --> 00:01:04,000\nFour\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_join_gap() {
+        let first = parse(TRACK);
+        let second = parse("5\n00:00:01,000 --> 00:00:02,000\nFour\n");
+
+        assert_eq!(
+            join(
+                &[&first, &[], &second],
+                JoinOffsets::Gap(Duration::from_millis(500))
+            )[..]
+                .serialize_to_string()
+                .unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:02,000\nOne\n\n",
+                "2\n00:00:09,000 --> 00:00:11,000\nTwo\n\n",
+                "3\n00:00:12,000 --> 00:00:13,500\nThree\n\n",
+                "4\n00:00:15,000 --> 00:00:16,000\nFour\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_split_join_round_trip() {
+        let subtitles = parse(TRACK);
+        let (first, second) = split(&subtitles, SplitPoint::Index(2));
+
+        assert_eq!(
+            join(
+                &[&first, &second],
+                JoinOffsets::Durations(&[Duration::from_secs(12)])
+            ),
+            subtitles
+        );
+    }
+}
@@ -0,0 +1,531 @@
+use core::time::Duration;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+use crate::{Subtitle, Text};
+
+/// How [`normalize_timing`] resolves two overlapping subtitles.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum OverlapStrategy {
+    /// The earlier subtitle ends before the later one starts.
+    ///
+    /// If the earlier subtitle would become shorter than
+    /// [`TimingOptions::min_duration`], the later one is shifted instead.
+    TrimEarlier,
+    /// The later subtitle is moved, so that it starts after the earlier one
+    /// ended. Its duration is not changed.
+    ShiftLater,
+    /// Both subtitles are merged into one subtitle with the text of both on
+    /// separate lines.
+    Merge,
+}
+
+impl Default for OverlapStrategy {
+    #[inline]
+    fn default() -> Self { Self::TrimEarlier }
+}
+
+/// Configures [`normalize_timing`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimingOptions {
+    /// How overlapping subtitles are resolved.
+    pub strategy: OverlapStrategy,
+    /// The minimum time in between the end of a subtitle and the start of the
+    /// next one (the default is two frames at 24 fps).
+    pub min_gap: Duration,
+    /// Shorter subtitles are extended, if there is enough time until the next
+    /// subtitle starts.
+    pub min_duration: Duration,
+    /// Longer subtitles are shortened, which must not be zero.
+    pub max_duration: Duration,
+    // the #[non_exhaustive] attribute does not work with the `..Default::default()` syntax
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for TimingOptions {
+    fn default() -> Self {
+        Self {
+            strategy: OverlapStrategy::default(),
+            min_gap: Duration::from_millis(83),
+            min_duration: Duration::from_millis(833),
+            max_duration: Duration::from_secs(7),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// An error, that is returned by [`normalize_timing`] for invalid
+/// [`TimingOptions`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum TimingOptionsError {
+    /// [`TimingOptions::max_duration`] is zero.
+    #[error("the maximum duration must not be zero")]
+    ZeroMaxDuration,
+    /// [`TimingOptions::min_duration`] is longer than
+    /// [`TimingOptions::max_duration`].
+    #[error("the minimum duration {min:?} is longer than the maximum duration {max:?}")]
+    MinAboveMax { min: Duration, max: Duration },
+}
+
+impl TimingOptions {
+    fn validate(&self) -> Result<(), TimingOptionsError> {
+        if self.max_duration == Duration::ZERO {
+            return Err(TimingOptionsError::ZeroMaxDuration);
+        }
+
+        if self.min_duration > self.max_duration {
+            return Err(TimingOptionsError::MinAboveMax {
+                min: self.min_duration,
+                max: self.max_duration,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Describes how [`normalize_timing`] changed a subtitle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimingChange {
+    /// The counter of the subtitle in the input.
+    pub counter: usize,
+    /// The start of the subtitle in the input.
+    pub old_start: Duration,
+    /// The end of the subtitle in the input.
+    pub old_end: Duration,
+    /// The start of the subtitle in the output, which is the start of the
+    /// subtitle it has been merged into, if it has been merged.
+    pub new_start: Duration,
+    /// The end of the subtitle in the output, which is the end of the subtitle
+    /// it has been merged into, if it has been merged.
+    pub new_end: Duration,
+    /// The counter (in the input) of the subtitle into which this subtitle has
+    /// been merged.
+    pub merged_into: Option<usize>,
+}
+
+/// The result of [`normalize_timing`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized<'a> {
+    /// The subtitles ordered by their start and numbered from `1`.
+    pub subtitles: Vec<Subtitle<'a>>,
+    /// The changed subtitles in the order of the output.
+    pub changes: Vec<TimingChange>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<'a> {
+    counter: usize,
+    old_start: Duration,
+    old_end: Duration,
+    start: Duration,
+    end: Duration,
+    text: Text<'a>,
+    /// The counters and the original times of the merged subtitles.
+    merged: Vec<(usize, Duration, Duration)>,
+}
+
+impl Entry<'_> {
+    // the end of an entry is never moved in front of its start
+    #[allow(clippy::unchecked_time_subtraction)]
+    fn duration(&self) -> Duration { self.end - self.start }
+}
+
+/// Resolves overlapping subtitles and enforces a minimum gap in between
+/// subtitles and a minimum and maximum duration for each subtitle.
+///
+/// The subtitles are first extended to [`TimingOptions::min_duration`] (as far
+/// as the next subtitle permits) and shortened to
+/// [`TimingOptions::max_duration`]. Afterwards, subtitles that are closer to
+/// each other than [`TimingOptions::min_gap`] are resolved with the
+/// [`OverlapStrategy`]. If two subtitles are too close, but do not overlap,
+/// [`OverlapStrategy::Merge`] trims the earlier one instead.
+///
+/// Neither trimming nor merging subtitles violates the minimum and maximum
+/// duration: a subtitle is only trimmed as far as the minimum duration permits
+/// (otherwise the later one is shifted) and merged subtitles are shortened to
+/// the maximum duration.
+///
+/// # Errors
+///
+/// If [`TimingOptions::max_duration`] is zero or shorter than
+/// [`TimingOptions::min_duration`].
+///
+/// ## Example
+///
+/// ```
+/// use libsrt::track::{normalize_timing, OverlapStrategy, TimingOptions};
+/// use libsrt::SubtitleIterator;
+/// use std::time::Duration;
+///
+/// let subtitles = SubtitleIterator::from(concat!(
+///     "1\n00:00:01,000 --> 00:00:03,000\nFirst\n\n",
+///     "2\n00:00:02,000 --> 00:00:04,000\nSecond\n",
+/// ))
+/// .collect::<Result<Vec<_>, _>>()?;
+///
+/// let normalized = normalize_timing(
+///     &subtitles,
+///     &TimingOptions {
+///         strategy: OverlapStrategy::Merge,
+///         ..TimingOptions::default()
+///     },
+/// )?;
+///
+/// assert_eq!(normalized.subtitles.len(), 1);
+/// assert_eq!(normalized.subtitles[0].text(), "First\nSecond");
+/// assert_eq!(normalized.changes[1].merged_into, Some(1));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn normalize_timing<'a>(
+    subtitles: &[Subtitle<'a>],
+    options: &TimingOptions,
+) -> Result<Normalized<'a>, TimingOptionsError> {
+    options.validate()?;
+
+    let mut entries = subtitles
+        .iter()
+        .map(|subtitle| {
+            Entry {
+                counter: subtitle.counter(),
+                old_start: subtitle.start(),
+                old_end: subtitle.end(),
+                start: subtitle.start(),
+                end: subtitle.end(),
+                text: subtitle.text().clone(),
+                merged: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.start);
+
+    // enforce the minimum and maximum duration
+    for index in 0..entries.len() {
+        let next_start = entries.get(index + 1).map(|next| next.start);
+        let entry = &mut entries[index];
+
+        if entry.duration() < options.min_duration {
+            let mut end = entry.start + options.min_duration;
+
+            if let Some(next_start) = next_start {
+                end = end.min(next_start.saturating_sub(options.min_gap));
+            }
+
+            entry.end = entry.end.max(end);
+        }
+
+        entry.end = entry.end.min(entry.start + options.max_duration);
+    }
+
+    // resolve overlaps and enforce the minimum gap
+    let mut result: Vec<Entry<'a>> = Vec::with_capacity(entries.len());
+
+    for mut entry in entries {
+        if let Some(previous) = result.last_mut() {
+            let required_start = previous.end + options.min_gap;
+
+            if entry.start < required_start {
+                let overlaps = entry.start < previous.end;
+                let trimmed_end = entry.start.saturating_sub(options.min_gap);
+
+                match options.strategy {
+                    OverlapStrategy::Merge if overlaps => {
+                        let mut text = String::from(previous.text.as_raw());
+                        text.push('\n');
+                        text.push_str(entry.text.as_raw());
+
+                        previous.text = Text::from(text);
+                        previous.end = previous
+                            .end
+                            .max(entry.end)
+                            .min(previous.start + options.max_duration);
+                        previous
+                            .merged
+                            .push((entry.counter, entry.old_start, entry.old_end));
+                        previous.merged.append(&mut entry.merged);
+                        continue;
+                    }
+                    OverlapStrategy::TrimEarlier | OverlapStrategy::Merge
+                        if trimmed_end > previous.start
+                            && trimmed_end.saturating_sub(previous.start)
+                                >= options.min_duration =>
+                    {
+                        previous.end = trimmed_end;
+                    }
+                    _ => {
+                        entry.end = required_start + entry.duration();
+                        entry.start = required_start;
+                    }
+                }
+            }
+        }
+
+        result.push(entry);
+    }
+
+    let mut changes = Vec::new();
+
+    for entry in &result {
+        if entry.start != entry.old_start || entry.end != entry.old_end {
+            changes.push(TimingChange {
+                counter: entry.counter,
+                old_start: entry.old_start,
+                old_end: entry.old_end,
+                new_start: entry.start,
+                new_end: entry.end,
+                merged_into: None,
+            });
+        }
+
+        for (counter, old_start, old_end) in &entry.merged {
+            changes.push(TimingChange {
+                counter: *counter,
+                old_start: *old_start,
+                old_end: *old_end,
+                new_start: entry.start,
+                new_end: entry.end,
+                merged_into: Some(entry.counter),
+            });
+        }
+    }
+
+    Ok(Normalized {
+        subtitles: result
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                Subtitle::new(index + 1, entry.start, entry.duration(), entry.text)
+            })
+            .collect(),
+        changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use crate::serialize::SerializeToString;
+    use crate::track::tests::parse;
+    use pretty_assertions::assert_eq;
+
+    fn options(strategy: OverlapStrategy) -> TimingOptions {
+        TimingOptions {
+            strategy,
+            min_gap: Duration::from_millis(100),
+            min_duration: Duration::from_secs(1),
+            max_duration: Duration::from_secs(5),
+            ..TimingOptions::default()
+        }
+    }
+
+    const OVERLAPPING: &str = concat!(
+        "1\n00:00:01,000 --> 00:00:03,000\nOne\n\n",
+        "2\n00:00:02,500 --> 00:00:04,000\nTwo\n\n",
+        "3\n00:00:04,050 --> 00:00:05,500\nThree\n",
+    );
+
+    #[test]
+    fn test_trim_earlier() {
+        let normalized =
+            normalize_timing(&parse(OVERLAPPING), &options(OverlapStrategy::TrimEarlier)).unwrap();
+
+        assert_eq!(
+            normalized.subtitles[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:02,400\nOne\n\n",
+                "2\n00:00:02,500 --> 00:00:03,950\nTwo\n\n",
+                "3\n00:00:04,050 --> 00:00:05,500\nThree\n",
+            )
+        );
+        assert_eq!(
+            normalized.changes,
+            vec![
+                TimingChange {
+                    counter: 1,
+                    old_start: Duration::from_secs(1),
+                    old_end: Duration::from_secs(3),
+                    new_start: Duration::from_secs(1),
+                    new_end: Duration::from_millis(2400),
+                    merged_into: None,
+                },
+                TimingChange {
+                    counter: 2,
+                    old_start: Duration::from_millis(2500),
+                    old_end: Duration::from_secs(4),
+                    new_start: Duration::from_millis(2500),
+                    new_end: Duration::from_millis(3950),
+                    merged_into: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shift_later() {
+        let normalized =
+            normalize_timing(&parse(OVERLAPPING), &options(OverlapStrategy::ShiftLater)).unwrap();
+
+        assert_eq!(
+            normalized.subtitles[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:03,000\nOne\n\n",
+                "2\n00:00:03,100 --> 00:00:04,600\nTwo\n\n",
+                "3\n00:00:04,700 --> 00:00:06,150\nThree\n",
+            )
+        );
+        assert_eq!(normalized.changes.len(), 2);
+    }
+
+    #[test]
+    fn test_merge() {
+        let normalized =
+            normalize_timing(&parse(OVERLAPPING), &options(OverlapStrategy::Merge)).unwrap();
+
+        assert_eq!(
+            normalized.subtitles[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:03,950\nOne\nTwo\n\n",
+                "2\n00:00:04,050 --> 00:00:05,500\nThree\n",
+            )
+        );
+        assert_eq!(
+            normalized.changes,
+            vec![
+                TimingChange {
+                    counter: 1,
+                    old_start: Duration::from_secs(1),
+                    old_end: Duration::from_secs(3),
+                    new_start: Duration::from_secs(1),
+                    new_end: Duration::from_millis(3950),
+                    merged_into: None,
+                },
+                TimingChange {
+                    counter: 2,
+                    old_start: Duration::from_millis(2500),
+                    old_end: Duration::from_secs(4),
+                    new_start: Duration::from_secs(1),
+                    new_end: Duration::from_millis(3950),
+                    merged_into: Some(1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_durations() {
+        let normalized = normalize_timing(
+            &parse(concat!(
+                "1\n00:00:01,000 --> 00:00:01,200\nShort\n\n",
+                "2\n00:00:01,500 --> 00:00:01,700\nShort, but no room\n\n",
+                "3\n00:00:01,800 --> 00:00:10,000\nLong\n",
+            )),
+            &options(OverlapStrategy::TrimEarlier),
+        )
+        .unwrap();
+
+        assert_eq!(
+            normalized.subtitles[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:01,400\nShort\n\n",
+                "2\n00:00:01,500 --> 00:00:01,700\nShort, but no room\n\n",
+                "3\n00:00:01,800 --> 00:00:06,800\nLong\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_trim_fallback() {
+        let normalized = normalize_timing(
+            &parse(concat!(
+                "1\n00:00:01,000 --> 00:00:03,000\nOne\n\n",
+                "2\n00:00:01,050 --> 00:00:02,050\nTwo\n",
+            )),
+            &options(OverlapStrategy::TrimEarlier),
+        )
+        .unwrap();
+
+        assert_eq!(
+            normalized.subtitles[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:03,000\nOne\n\n",
+                "2\n00:00:03,100 --> 00:00:04,100\nTwo\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_trim_min_duration() {
+        let normalized = normalize_timing(
+            &parse(concat!(
+                "1\n00:00:01,000 --> 00:00:03,000\nOne\n\n",
+                "2\n00:00:01,800 --> 00:00:02,800\nTwo\n",
+            )),
+            &options(OverlapStrategy::TrimEarlier),
+        )
+        .unwrap();
+
+        // trimming the first subtitle would make it shorter than a second
+        assert_eq!(
+            normalized.subtitles[..].serialize_to_string().unwrap(),
+            concat!(
+                "1\n00:00:01,000 --> 00:00:03,000\nOne\n\n",
+                "2\n00:00:03,100 --> 00:00:04,100\nTwo\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_max_duration() {
+        let normalized = normalize_timing(
+            &parse(concat!(
+                "1\n00:00:01,000 --> 00:00:05,000\nOne\n\n",
+                "2\n00:00:04,000 --> 00:00:09,000\nTwo\n",
+            )),
+            &options(OverlapStrategy::Merge),
+        )
+        .unwrap();
+
+        assert_eq!(
+            normalized.subtitles[..].serialize_to_string().unwrap(),
+            "1\n00:00:01,000 --> 00:00:06,000\nOne\nTwo\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let subtitles = parse(OVERLAPPING);
+
+        assert_eq!(
+            normalize_timing(
+                &subtitles,
+                &TimingOptions {
+                    min_duration: Duration::ZERO,
+                    max_duration: Duration::ZERO,
+                    ..TimingOptions::default()
+                }
+            ),
+            Err(TimingOptionsError::ZeroMaxDuration)
+        );
+        assert_eq!(
+            normalize_timing(
+                &subtitles,
+                &TimingOptions {
+                    min_duration: Duration::from_secs(2),
+                    max_duration: Duration::from_secs(1),
+                    ..TimingOptions::default()
+                }
+            ),
+            Err(TimingOptionsError::MinAboveMax {
+                min: Duration::from_secs(2),
+                max: Duration::from_secs(1),
+            })
+        );
+    }
+}
@@ -1,5 +1,5 @@
 // See https://internals.rust-lang.org/t/pre-rfc-tryfromiterator-and-try-collect-to-enable-collecting-to-arrays/14423/14
-use core::mem::{self, MaybeUninit};
+use core::convert::Infallible;
 
 pub trait TryFromIterator<A>: Sized {
     type Error;
@@ -15,57 +15,40 @@ pub struct NotEnoughItems {
 impl<A, const N: usize> TryFromIterator<A> for [A; N] {
     type Error = NotEnoughItems;
 
+    #[cfg(feature = "nightly")]
     fn try_from_iter<T: IntoIterator<Item = A>>(iter: T) -> Result<Self, Self::Error> {
-        let mut array: [MaybeUninit<A>; N] = MaybeUninit::uninit_array();
-        let mut iterator = iter.into_iter();
-
-        for (i, item) in array.iter_mut().enumerate() {
-            if let Some(value) = iterator.next() {
-                *item = MaybeUninit::new(value);
-            } else {
-                return Err(NotEnoughItems { missing: N - i });
+        iter.into_iter().next_chunk().map_err(|items| {
+            NotEnoughItems {
+                missing: N - items.len(),
             }
-        }
-
-        // One can not simply use mem::transmute, because of this issue:
-        // https://github.com/rust-lang/rust/issues/61956
-        let result: [A; N] = unsafe {
-            // assert that we have exclusive ownership of the array
-            let pointer: *mut [A; N] = &mut array as *mut _ as *mut [A; N];
-            let result: [A; N] = pointer.read();
-            // forget about the old array
-            mem::forget(array);
+        })
+    }
 
-            result
+    #[cfg(not(feature = "nightly"))]
+    fn try_from_iter<T: IntoIterator<Item = A>>(iter: T) -> Result<Self, Self::Error> {
+        let array: [Option<A>; N] = match TryFromIterator::try_from_iter(iter) {
+            Ok(array) => array,
+            Err(never) => match never {},
         };
 
-        Ok(result)
+        let missing = array.iter().filter(|item| item.is_none()).count();
+
+        if missing > 0 {
+            return Err(NotEnoughItems { missing });
+        }
+
+        // all items are `Some`, so this will never panic
+        Ok(array.map(|item| item.unwrap()))
     }
 }
 
 impl<A, const N: usize> TryFromIterator<A> for [Option<A>; N] {
-    type Error = !;
+    type Error = Infallible;
 
     fn try_from_iter<T: IntoIterator<Item = A>>(iter: T) -> Result<Self, Self::Error> {
-        let mut array: [MaybeUninit<Option<A>>; N] = MaybeUninit::uninit_array();
         let mut iterator = iter.into_iter();
 
-        for item in array.iter_mut() {
-            *item = MaybeUninit::new(iterator.next());
-        }
-
-        let result: [Option<A>; N] = unsafe {
-            // assert that we have exclusive ownership of the array
-            let pointer: *mut [Option<A>; N] = &mut array as *mut _ as *mut [Option<A>; N];
-            let result: [Option<A>; N] = pointer.read();
-
-            // forget about the old array
-            mem::forget(array);
-
-            result
-        };
-
-        Ok(result)
+        Ok(core::array::from_fn(|_| iterator.next()))
     }
 }
 
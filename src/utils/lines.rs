@@ -1,16 +1,16 @@
 use core::ops::RangeBounds;
 
-use logical_pattern::OrPattern;
-
-use super::pattern::PatternExt;
-use super::Span;
+use super::scan::LineTerminator;
 use super::spanned::SplitTerminator;
+#[cfg(feature = "rayon")]
+use super::split::SplitInternal;
+use super::Span;
 use crate::utils::Spanned;
 
-// TODO: implement basic traits
+#[derive(Debug, Clone)]
 pub(crate) struct Lines<'a> {
     start: usize,
-    iterator: SplitTerminator<'a, OrPattern<&'a str, char>>,
+    iterator: SplitTerminator<'a, LineTerminator>,
 }
 
 impl<'a> Lines<'a> {
@@ -22,11 +22,24 @@ impl<'a> Lines<'a> {
     #[must_use]
     pub fn new_spanned(string: Spanned<&'a str>) -> Self {
         Self {
-            iterator: string.split_terminator(PatternExt::or("\r\n", '\n')),
+            iterator: string.split_terminator(LineTerminator),
             start: string.span().map_or(0, Span::start),
         }
     }
 
+    /// Returns an iterator over the lines of `string[position..]`, whose spans
+    /// are relative to the start of the `string`.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub const fn new_at(string: &'a str, position: usize) -> Self {
+        Self {
+            iterator: SplitTerminator(
+                SplitInternal::new(string, LineTerminator, false).starting_at(position),
+            ),
+            start: 0,
+        }
+    }
+
     #[must_use]
     pub fn get<R: RangeBounds<usize>>(&self, range: R) -> Option<Spanned<&'a str>> {
         let spanned = Spanned::from((self.iterator.0.haystack(), self.start..));
@@ -36,13 +49,13 @@ impl<'a> Lines<'a> {
 
     #[inline]
     #[must_use]
-    pub fn index(&self) -> usize { self.iterator.0.matcher.index() }
+    pub const fn index(&self) -> usize { self.iterator.0.index() }
 }
 
 impl<'a> Iterator for Lines<'a> {
     type Item = Spanned<&'a str>;
 
-    fn next(&mut self) -> Option<Self::Item> { Some(self.iterator.next()?) }
+    fn next(&mut self) -> Option<Self::Item> { self.iterator.next() }
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 mod collect_arrays;
 mod lines;
 mod pattern;
+mod scan;
 mod span;
 mod spannable;
 mod spanned;
@@ -10,6 +11,9 @@ mod split_iter_n;
 
 pub(crate) use collect_arrays::IteratorExt;
 pub(crate) use lines::Lines;
+#[cfg(feature = "alloc")]
+pub(crate) use pattern::Pattern;
+pub(crate) use scan::{find_any_byte, find_byte, find_either_byte, Arrow};
 pub(crate) use span::Span;
 pub(crate) use spannable::Spannable;
 pub(crate) use spanned::Spanned;
@@ -1,39 +1,109 @@
 use core::cmp::PartialOrd;
-use core::str::pattern::{Pattern, SearchStep, Searcher};
 
-use logical_pattern::OrPattern;
+/// A stable replacement for the unstable `core::str::pattern::Pattern` trait,
+/// which only supports the operations needed by this crate.
+pub trait Pattern: Copy {
+    /// Returns the byte range of the first match in `haystack`.
+    fn find_in(&self, haystack: &str) -> Option<(usize, usize)>;
+}
 
-pub trait PatternExt<'a>: Pattern<'a> {
-    fn ignoring(self) -> IgnoringPattern<Self> { IgnoringPattern(self) }
+impl Pattern for char {
+    #[inline]
+    fn find_in(&self, haystack: &str) -> Option<(usize, usize)> {
+        haystack
+            .find(*self)
+            .map(|start| (start, start + self.len_utf8()))
+    }
+}
 
-    fn or<'b, P: Pattern<'b>>(self, other: P) -> OrPattern<Self, P> { OrPattern::new(self, other) }
+impl Pattern for &str {
+    #[inline]
+    fn find_in(&self, haystack: &str) -> Option<(usize, usize)> {
+        haystack
+            .find(*self)
+            .map(|start| (start, start + self.len()))
+    }
 }
 
-impl<'a, P: Pattern<'a>> PatternExt<'a> for P {}
+impl Pattern for fn(char) -> bool {
+    #[inline]
+    fn find_in(&self, haystack: &str) -> Option<(usize, usize)> {
+        haystack
+            .char_indices()
+            .find(|(_, c)| self(*c))
+            .map(|(start, c)| (start, start + c.len_utf8()))
+    }
+}
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct IgnoringPattern<T>(pub(super) T);
+/// A [`Pattern`], that can only match a single `char`.
+pub trait CharPattern: Copy {
+    fn matches(&self, c: char) -> bool;
+}
 
-impl<'a, T: Pattern<'a>> Pattern<'a> for IgnoringPattern<T> {
-    type Searcher = IgnoringSearcher<T::Searcher>;
+impl CharPattern for char {
+    #[inline]
+    fn matches(&self, c: char) -> bool { *self == c }
+}
 
-    fn into_searcher(self, haystack: &'a str) -> Self::Searcher {
-        IgnoringSearcher::from(self.0.into_searcher(haystack))
-    }
+impl CharPattern for fn(char) -> bool {
+    #[inline]
+    fn matches(&self, c: char) -> bool { self(c) }
+}
+
+pub trait PatternExt: Pattern {
+    fn ignoring(self) -> IgnoringPattern<Self> { IgnoringPattern(self) }
+}
+
+impl<P: Pattern> PatternExt for P {}
+
+/// Searches for matches in a `haystack`, starting at a `position`, which is
+/// needed by patterns, that depend on the text in front of a match.
+pub trait Searcher: Copy {
+    /// Returns the byte range of the first match in `haystack[position..]`,
+    /// relative to the start of the `haystack`.
+    fn find_from(&self, haystack: &str, position: usize) -> Option<(usize, usize)>;
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
-pub struct IgnoringSearcher<S> {
-    searcher: S,
-    reviewer: Reviewer<char, 2>,
+impl<P: Pattern> Searcher for P {
+    #[inline]
+    fn find_from(&self, haystack: &str, position: usize) -> Option<(usize, usize)> {
+        self.find_in(&haystack[position..])
+            .map(|(start, end)| (position + start, position + end))
+    }
 }
 
-impl<'a, 'b, S: Searcher<'a>> From<S> for IgnoringSearcher<S> {
-    fn from(value: S) -> Self {
-        Self {
-            searcher: value,
-            reviewer: Reviewer::new(['\'', '\"']),
+/// A pattern that ignores all matches, which are inside of quotes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IgnoringPattern<T>(pub(super) T);
+
+impl<T: Pattern> Searcher for IgnoringPattern<T> {
+    fn find_from(&self, haystack: &str, mut position: usize) -> Option<(usize, usize)> {
+        let mut reviewer = Reviewer::new(['\'', '\"']);
+        // the quotes in front of this index have been visited
+        let mut visited = 0;
+
+        while let Some((start, end)) = self.0.find_from(haystack, position) {
+            for c in haystack[visited..start].chars() {
+                reviewer.visit(&c);
+            }
+
+            visited = start;
+
+            if !reviewer.partial() {
+                return Some((start, end));
+            }
+
+            // an empty match would result in an infinite loop
+            position = {
+                if end == start {
+                    end + haystack[end..].chars().next()?.len_utf8()
+                } else {
+                    end
+                }
+            };
         }
+
+        None
     }
 }
 
@@ -43,12 +113,6 @@ pub struct Reviewer<T, const N: usize>([(T, bool); N]);
 impl<T, const N: usize> Reviewer<T, N> {
     #[must_use]
     pub fn new(ignore: [T; N]) -> Self { Self(ignore.map(|value| (value, false))) }
-
-    pub fn reset(&mut self) {
-        for (_, state) in self.0.iter_mut() {
-            *state = false;
-        }
-    }
 }
 
 impl<T: PartialEq<T>, const N: usize> Reviewer<T, N> {
@@ -68,36 +132,6 @@ impl<T: PartialEq<T>, const N: usize> Reviewer<T, N> {
     }
 }
 
-unsafe impl<'a, S: Searcher<'a>> Searcher<'a> for IgnoringSearcher<S> {
-    fn haystack(&self) -> &'a str { self.searcher.haystack() }
-
-    fn next(&mut self) -> SearchStep {
-        let step = self.searcher.next();
-
-        if let SearchStep::Match(start, end) = step {
-            // TODO: start from the last match?
-            for c in self.haystack()[..start].chars() {
-                self.reviewer.visit(&c);
-            }
-
-            let result = {
-                if self.reviewer.partial() {
-                    SearchStep::Reject(start, end)
-                } else {
-                    SearchStep::Match(start, end)
-                }
-            };
-
-            // TODO: do you have to reset?
-            self.reviewer.reset();
-
-            result
-        } else {
-            step
-        }
-    }
-}
-
 // TODO: splitting "\"hello '\" world" should return ["\"hello '\"", "world"]
 
 #[cfg(test)]
@@ -107,24 +141,24 @@ mod tests {
 
     #[test]
     fn test_pattern_normal_string() {
-        let mut iterator = "this is an example string".split(" ".ignoring());
-
-        assert_eq!(iterator.next(), Some("this"));
-        assert_eq!(iterator.next(), Some("is"));
-        assert_eq!(iterator.next(), Some("an"));
-        assert_eq!(iterator.next(), Some("example"));
-        assert_eq!(iterator.next(), Some("string"));
-        assert_eq!(iterator.next(), None);
+        let haystack = "this is an example string";
+        let pattern = " ".ignoring();
+
+        assert_eq!(pattern.find_from(haystack, 0), Some((4, 5)));
+        assert_eq!(pattern.find_from(haystack, 5), Some((7, 8)));
+        assert_eq!(pattern.find_from(haystack, 8), Some((10, 11)));
+        assert_eq!(pattern.find_from(haystack, 11), Some((18, 19)));
+        assert_eq!(pattern.find_from(haystack, 19), None);
     }
 
     #[test]
     fn test_pattern_single_quotes() {
-        let mut iterator = "this is an' 'example string".split(' '.ignoring());
+        let haystack = "this is an' 'example string";
+        let pattern = ' '.ignoring();
 
-        assert_eq!(iterator.next(), Some("this"));
-        assert_eq!(iterator.next(), Some("is"));
-        assert_eq!(iterator.next(), Some("an' 'example"));
-        assert_eq!(iterator.next(), Some("string"));
-        assert_eq!(iterator.next(), None);
+        assert_eq!(pattern.find_from(haystack, 0), Some((4, 5)));
+        assert_eq!(pattern.find_from(haystack, 5), Some((7, 8)));
+        assert_eq!(pattern.find_from(haystack, 8), Some((20, 21)));
+        assert_eq!(pattern.find_from(haystack, 21), None);
     }
 }
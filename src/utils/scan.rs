@@ -0,0 +1,139 @@
+//! Searches for the characters, that structure an `.srt` file.
+//!
+//! With the `memchr` feature the searches use the vectorized routines of the
+//! `memchr` crate, otherwise they use the searches of `core`. Both find the
+//! same matches, because all needles are ASCII and therefore can not be part of
+//! a multi-byte `char`.
+use super::pattern::Pattern;
+
+/// Returns the index of the first `needle` in the `haystack`.
+#[inline]
+#[must_use]
+pub(crate) fn find_byte(needle: u8, haystack: &str) -> Option<usize> {
+    debug_assert!(needle.is_ascii());
+
+    #[cfg(feature = "memchr")]
+    {
+        memchr::memchr(needle, haystack.as_bytes())
+    }
+
+    #[cfg(not(feature = "memchr"))]
+    {
+        haystack.find(char::from(needle))
+    }
+}
+
+/// Returns the index of the first of the two `needles` in the `haystack`.
+#[inline]
+#[must_use]
+pub(crate) fn find_either_byte([a, b]: [u8; 2], haystack: &str) -> Option<usize> {
+    debug_assert!(a.is_ascii() && b.is_ascii());
+
+    #[cfg(feature = "memchr")]
+    {
+        memchr::memchr2(a, b, haystack.as_bytes())
+    }
+
+    #[cfg(not(feature = "memchr"))]
+    {
+        haystack.bytes().position(|byte| byte == a || byte == b)
+    }
+}
+
+/// Returns the index of the first of the `needles` in the `haystack`.
+#[inline]
+#[must_use]
+pub(crate) fn find_any_byte([a, b, c]: [u8; 3], haystack: &str) -> Option<usize> {
+    debug_assert!(a.is_ascii() && b.is_ascii() && c.is_ascii());
+
+    #[cfg(feature = "memchr")]
+    {
+        memchr::memchr3(a, b, c, haystack.as_bytes())
+    }
+
+    #[cfg(not(feature = "memchr"))]
+    {
+        haystack.find(&[char::from(a), char::from(b), char::from(c)][..])
+    }
+}
+
+/// Matches the end of a line, which is either `\r\n` or `\n`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct LineTerminator;
+
+impl Pattern for LineTerminator {
+    #[inline]
+    fn find_in(&self, haystack: &str) -> Option<(usize, usize)> {
+        let end = find_byte(b'\n', haystack)?;
+
+        // a `\r\n` can not start in front of the first `\n`, so there is no
+        // need to search the entire haystack for it
+        if end > 0 && haystack.as_bytes()[end - 1] == b'\r' {
+            Some((end - 1, end + 1))
+        } else {
+            Some((end, end + 1))
+        }
+    }
+}
+
+/// Matches the ` --> ` between the start and the end of a subtitle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Arrow;
+
+impl Arrow {
+    const ARROW: &'static str = " --> ";
+}
+
+impl Pattern for Arrow {
+    #[inline]
+    fn find_in(&self, haystack: &str) -> Option<(usize, usize)> {
+        #[cfg(feature = "memchr")]
+        {
+            memchr::memmem::find(haystack.as_bytes(), Self::ARROW.as_bytes())
+                .map(|start| (start, start + Self::ARROW.len()))
+        }
+
+        #[cfg(not(feature = "memchr"))]
+        {
+            Self::ARROW.find_in(haystack)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_find_byte() {
+        assert_eq!(find_byte(b'<', "äb<c<"), Some(3));
+        assert_eq!(find_byte(b'<', "äbc"), None);
+        assert_eq!(find_either_byte([b'<', b'{'], "ä{<"), Some(2));
+        assert_eq!(find_either_byte([b'<', b'{'], "ä<{"), Some(2));
+        assert_eq!(find_either_byte([b'<', b'{'], "äbc"), None);
+        assert_eq!(find_any_byte([b'>', b'"', b'\''], "ä'>\""), Some(2));
+        assert_eq!(find_any_byte([b'>', b'"', b'\''], "ä\"'>"), Some(2));
+        assert_eq!(find_any_byte([b'>', b'"', b'\''], "äbc"), None);
+    }
+
+    #[test]
+    fn test_line_terminator() {
+        assert_eq!(LineTerminator.find_in("a\r\nb"), Some((1, 3)));
+        assert_eq!(LineTerminator.find_in("a\nb\r\n"), Some((1, 2)));
+        assert_eq!(LineTerminator.find_in("\r\n"), Some((0, 2)));
+        assert_eq!(LineTerminator.find_in("\n"), Some((0, 1)));
+        assert_eq!(LineTerminator.find_in("a\rb\r"), None);
+        assert_eq!(LineTerminator.find_in("ä"), None);
+    }
+
+    #[test]
+    fn test_arrow() {
+        assert_eq!(
+            Arrow.find_in("00:00:01,000 --> 00:00:02,000"),
+            Some((12, 17))
+        );
+        assert_eq!(Arrow.find_in("00:00:01,000 -> 00:00:02,000"), None);
+        assert_eq!(Arrow.find_in("- --> -->"), Some((1, 6)));
+    }
+}
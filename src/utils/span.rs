@@ -1,5 +1,5 @@
+use core::fmt;
 use core::ops::{Add, Range, RangeInclusive, RangeTo, RangeToInclusive};
-use core::{fmt, usize};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Span {
@@ -26,7 +26,7 @@ impl Span {
 
     // TODO: test me!
     #[must_use]
-    pub fn sub_span(self, span: impl Into<Span>) -> Option<Self> {
+    pub fn sub_span(self, span: impl Into<Self>) -> Option<Self> {
         let span = span.into();
 
         if span.start() + self.start() > self.end() || span.length() > self.length() {
@@ -41,15 +41,15 @@ impl Span {
 
     /// Returns the `start` of the `Span`.
     #[must_use]
-    pub fn start(self) -> usize { self.start }
+    pub const fn start(self) -> usize { self.start }
 
     /// Returns the `length` of the `Span`.
     #[must_use]
-    pub fn length(self) -> usize { self.length }
+    pub const fn length(self) -> usize { self.length }
 
     /// Returns the `end` of the `Span`.
     #[must_use]
-    pub fn end(self) -> usize { self.start() + self.length() }
+    pub const fn end(self) -> usize { self.start() + self.length() }
 }
 
 impl Add<usize> for Span {
@@ -89,11 +89,11 @@ impl From<RangeToInclusive<usize>> for Span {
     fn from(range: RangeToInclusive<usize>) -> Self { Self::from(0..=range.end) }
 }
 
-impl Into<Range<usize>> for Span {
-    fn into(self) -> Range<usize> {
-        Range {
-            start: self.start(),
-            end: self.end(),
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        Self {
+            start: span.start(),
+            end: span.end(),
         }
     }
 }
@@ -118,6 +118,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::reversed_empty_ranges)]
     fn test_span_from_range() {
         assert_eq!(Span::from(1..3), Span::from(1..=2));
         assert_eq!(Span::from(..3), Span::from(..=2));
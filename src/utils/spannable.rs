@@ -8,6 +8,6 @@ impl Spannable for str {
     fn span(&self) -> Span { Span::from(0..self.len()) }
 }
 
-impl<'a> Spannable for &'a str {
+impl Spannable for &str {
     fn span(&self) -> Span { Span::from(0..self.len()) }
 }
@@ -1,23 +1,21 @@
-use core::str::pattern::{DoubleEndedSearcher, Pattern, ReverseSearcher};
-use core::{array, fmt};
+use core::fmt;
+use core::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
 use core::{num::ParseIntError, ops::RangeFrom};
-use core::{
-    ops::{Bound, Deref, DerefMut, Range, RangeBounds},
-    str::pattern::Searcher,
-};
 
+use super::pattern::{CharPattern, Pattern};
 use super::split::SplitInternal;
 use super::{IteratorExt, Span, Spannable, SplitIter, SplitIterN};
 
-pub struct SplitTerminator<'a, P: Pattern<'a>>(pub(super) SplitInternal<'a, P>);
+#[derive(Debug, Clone)]
+pub struct SplitTerminator<'a, P: Pattern>(pub(super) SplitInternal<'a, P>);
 
-impl<'a, P: Pattern<'a>> Iterator for SplitTerminator<'a, P> {
+impl<'a, P: Pattern> Iterator for SplitTerminator<'a, P> {
     type Item = Spanned<&'a str>;
 
     fn next(&mut self) -> Option<Self::Item> { self.0.next() }
 }
 
-impl<'a, P: Pattern<'a>> SplitTerminator<'a, P> {
+impl<'a, P: Pattern> SplitTerminator<'a, P> {
     pub fn as_str(&self) -> &'a str { self.0.as_str() }
 }
 
@@ -81,7 +79,7 @@ impl<T> Spanned<T> {
     }
 
     #[must_use]
-    pub fn map_span<F>(mut self, f: F) -> Spanned<T>
+    pub fn map_span<F>(mut self, f: F) -> Self
     where
         F: FnOnce(Option<Span>) -> Option<Span>,
     {
@@ -103,7 +101,7 @@ impl<T: Spannable> Spanned<T> {
 }
 
 impl<'a> Spanned<&'a str> {
-    fn splitn<P: Pattern<'a>>(
+    fn splitn<P: Pattern>(
         &self,
         remaining: usize,
         pattern: P,
@@ -111,38 +109,29 @@ impl<'a> Spanned<&'a str> {
         SplitIterN::new_spanned(remaining, *self, pattern)
     }
 
-    pub fn split<P: Pattern<'a>>(&self, pattern: P) -> SplitIter<'a, P> {
+    pub fn split<P: Pattern>(&self, pattern: P) -> SplitIter<'a, P> {
         SplitIter::new_spanned(*self, pattern)
     }
 
-    pub fn trim(&self) -> Self { self.trim_matches(char::is_whitespace) }
+    pub fn trim(&self) -> Self { self.trim_matches(char::is_whitespace as fn(char) -> bool) }
 
-    fn trim_matches<P>(&self, pattern: P) -> Self
-    where
-        P: Pattern<'a, Searcher: DoubleEndedSearcher<'a>>,
-    {
-        let mut i = 0;
-        let mut j = 0;
-
-        let mut matcher = pattern.into_searcher(self);
-
-        if let Some((a, b)) = matcher.next_reject() {
-            i = a;
-            j = b;
-        }
-
-        if let Some((_, b)) = matcher.next_reject_back() {
-            j = b;
-        }
+    fn trim_matches<P: CharPattern>(&self, pattern: P) -> Self {
+        let i = self
+            .char_indices()
+            .find(|(_, c)| !pattern.matches(*c))
+            .map_or(self.len(), |(start, _)| start);
+        let j = self[i..]
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !pattern.matches(*c))
+            .map_or(i, |(start, c)| i + start + c.len_utf8());
 
-        // SAFETY: `Searcher` is known to return valid indices.
-        let string = unsafe { self.get_unchecked(i..j) };
+        let string = &self.inner[i..j];
 
-        // TODO: adjust with self!
-        (string, i..).into()
+        (string, self.start() + i..).into()
     }
 
-    pub fn split_once<P: Pattern<'a>>(&self, pattern: P) -> (Self, Option<Self>) {
+    pub fn split_once<P: Pattern>(&self, pattern: P) -> (Self, Option<Self>) {
         if let [Some(first), second] = self.split_at_most::<_, 2>(pattern) {
             (first, second)
         } else {
@@ -152,7 +141,7 @@ impl<'a> Spanned<&'a str> {
 
     pub fn split_at_most<P, const N: usize>(&self, pattern: P) -> [Option<Self>; N]
     where
-        P: Pattern<'a>,
+        P: Pattern,
     {
         self.splitn(N, pattern).try_collect().unwrap()
     }
@@ -172,14 +161,8 @@ impl<'a> Spanned<&'a str> {
             })
     }
 
-    pub fn split_terminator<P: Pattern<'a>>(&self, pattern: P) -> SplitTerminator<'a, P> {
-        SplitTerminator(SplitInternal {
-            start: 0,
-            end: self.len(),
-            matcher: pattern.into_searcher(self),
-            allow_trailing_empty: false,
-            finished: false,
-        })
+    pub const fn split_terminator<P: Pattern>(&self, pattern: P) -> SplitTerminator<'a, P> {
+        SplitTerminator(SplitInternal::new(self.inner, pattern, false))
     }
 
     // we can not use SliceIndex as trait bound, because all methods are unstable
@@ -224,7 +207,7 @@ impl<'a> Spanned<&'a str> {
     ) -> [Option<Self>; N] {
         let mut result = [None; N];
 
-        for (i, range) in array::IntoIter::new(ranges).enumerate() {
+        for (i, range) in IntoIterator::into_iter(ranges).enumerate() {
             result[i] = self.get(range);
         }
 
@@ -247,35 +230,32 @@ impl<'a> Spanned<&'a str> {
         None
     }
 
-    pub fn trim_start_matches<P: Pattern<'a>>(&self, pattern: P) -> Self {
-        let mut index = self.len();
-        let mut matcher = pattern.into_searcher(self);
+    pub fn trim_start_matches<P: CharPattern>(&self, pattern: P) -> Self {
+        let index = self
+            .char_indices()
+            .find(|(_, c)| !pattern.matches(*c))
+            .map_or(self.len(), |(start, _)| start);
 
-        if let Some((start, _)) = matcher.next_reject() {
-            index = start;
-        }
-
-        // SAFETY: `Searcher` is known to return valid indices.
-        let string = unsafe { self.get_unchecked(index..self.len()) };
+        let string = &self.inner[index..];
 
-        // TODO: adjust with self!
-        (string, index..).into()
+        (string, self.start() + index..).into()
     }
 
-    pub fn trim_end_matches<P: Pattern<'a>>(&self, pattern: P) -> Self {
-        let mut index = self.len();
-        let mut matcher = pattern.into_searcher(self);
-
-        if let Some((start, _)) = matcher.next_reject() {
-            index = start;
-        }
+    pub fn trim_end_matches<P: CharPattern>(&self, pattern: P) -> Self {
+        let index = self
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !pattern.matches(*c))
+            .map_or(0, |(start, c)| start + c.len_utf8());
 
-        // SAFETY: `Searcher` is known to return valid indices.
-        let string = unsafe { self.get_unchecked(index..self.len()) };
+        let string = &self.inner[..index];
 
-        (string, index..).into()
+        (string, self.start()..).into()
     }
 
+    /// Returns the start of the span or `0` if there is none.
+    fn start(&self) -> usize { self.span.map_or(0, Span::start) }
+
     // TODO: make use of this function/improve
     #[must_use]
     pub fn str_span(&self) -> Span { self.span.unwrap_or_else(|| (0..self.len()).into()) }
@@ -307,10 +287,8 @@ impl<T: fmt::Display> fmt::Display for Spanned<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.inner.fmt(f) }
 }
 
-// TODO: needed?
-#[cfg(feature = "std")]
-impl<T: ::std::error::Error> ::std::error::Error for Spanned<T> {
-    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> { self.inner.source() }
+impl<T: core::error::Error> core::error::Error for Spanned<T> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> { self.inner.source() }
 }
 
 /*
@@ -350,6 +328,35 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_trim() {
+        let spanned = Spanned::new("  hello  ");
+
+        assert_eq!(spanned.trim(), Spanned::new("hello").with_span(2..7));
+        assert_eq!(
+            spanned.trim_start_matches(' '),
+            Spanned::new("hello  ").with_span(2..9)
+        );
+        assert_eq!(
+            spanned.trim_end_matches(' '),
+            Spanned::new("  hello").with_span(0..7)
+        );
+        assert_eq!(Spanned::new("   ").trim().into_inner(), "");
+
+        // the spans are relative to the span of the trimmed string
+        let spanned = Spanned::new(" hello ").with_span(3..10);
+
+        assert_eq!(spanned.trim(), Spanned::new("hello").with_span(4..9));
+        assert_eq!(
+            spanned.trim_start_matches(' '),
+            Spanned::new("hello ").with_span(4..10)
+        );
+        assert_eq!(
+            spanned.trim_end_matches(' '),
+            Spanned::new(" hello").with_span(3..9)
+        );
+    }
+
     #[test]
     fn test_get() {
         // TODO: verify that spans have the correct length! (length must be equal to the
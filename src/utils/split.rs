@@ -1,30 +1,18 @@
-//! This file is a clone/copy of the rust source code for the Split Iterator
-use core::fmt;
-use core::str::pattern::{Pattern, Searcher};
-
+//! This file is a simplified clone of the rust source code for the Split
+//! Iterator.
+use super::pattern::Searcher;
 use crate::utils::Spanned;
 
 // TODO: simplify
 
-pub(super) struct SplitNInternal<'a, P: Pattern<'a>> {
+#[derive(Debug, Clone)]
+pub(super) struct SplitNInternal<'a, P> {
     pub(super) iter: SplitInternal<'a, P>,
     /// The number of splits remaining
     pub(super) count: usize,
 }
 
-impl<'a, P> fmt::Debug for SplitNInternal<'a, P>
-where
-    P: Pattern<'a, Searcher: fmt::Debug>,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("SplitNInternal")
-            .field("iter", &self.iter)
-            .field("count", &self.count)
-            .finish()
-    }
-}
-
-impl<'a, P: Pattern<'a>> SplitNInternal<'a, P> {
+impl<'a, P: Searcher> SplitNInternal<'a, P> {
     #[inline]
     pub fn next(&mut self) -> Option<Spanned<&'a str>> {
         match self.count {
@@ -41,58 +29,48 @@ impl<'a, P: Pattern<'a>> SplitNInternal<'a, P> {
     }
 }
 
-pub struct SplitInternal<'a, P: Pattern<'a>> {
+#[derive(Debug, Clone)]
+pub struct SplitInternal<'a, P> {
     pub(super) start: usize,
     pub(super) end: usize,
-    pub(super) matcher: P::Searcher,
+    pub(super) haystack: &'a str,
+    pub(super) matcher: P,
+    /// The index from which the next search starts.
+    pub(super) position: usize,
     pub(super) allow_trailing_empty: bool,
     pub(super) finished: bool,
 }
 
-macro_rules! derive_pattern_clone {
-    (clone $t:ident with | $s:ident | $e:expr) => {
-        impl<'a, P> Clone for $t<'a, P>
-        where
-            P: Pattern<'a, Searcher: Clone>,
-        {
-            fn clone(&self) -> Self {
-                let $s = self;
-                $e
-            }
+impl<'a, P: Searcher> SplitInternal<'a, P> {
+    #[must_use]
+    pub(super) const fn new(haystack: &'a str, matcher: P, allow_trailing_empty: bool) -> Self {
+        Self {
+            start: 0,
+            end: haystack.len(),
+            haystack,
+            matcher,
+            position: 0,
+            allow_trailing_empty,
+            finished: false,
         }
-    };
-}
-
-derive_pattern_clone! {
-    clone SplitInternal
-    with |s| SplitInternal { matcher: s.matcher.clone(), ..*s }
-}
+    }
 
-impl<'a, P> fmt::Debug for SplitInternal<'a, P>
-where
-    P: Pattern<'a, Searcher: fmt::Debug>,
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("SplitInternal")
-            .field("start", &self.start)
-            .field("end", &self.end)
-            .field("matcher", &self.matcher)
-            .field("allow_trailing_empty", &self.allow_trailing_empty)
-            .field("finished", &self.finished)
-            .finish()
+    /// Starts splitting at the byte offset `position` instead of the start of
+    /// the `haystack`.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub(super) const fn starting_at(mut self, position: usize) -> Self {
+        self.start = position;
+        self.position = position;
+        self
     }
-}
 
-impl<'a, P: Pattern<'a>> SplitInternal<'a, P> {
     #[inline]
     pub fn get_end(&mut self) -> Option<Spanned<&'a str>> {
         if !self.finished && (self.allow_trailing_empty || self.end - self.start > 0) {
             self.finished = true;
-            // SAFETY: `self.start` and `self.end` always lie on unicode boundaries.
-            unsafe {
-                let string = self.matcher.haystack().get_unchecked(self.start..self.end);
-                Some(Spanned::new(string).with_span(self.start..self.end))
-            }
+            let string = &self.haystack[self.start..self.end];
+            Some(Spanned::new(string).with_span(self.start..self.end))
         } else {
             None
         }
@@ -104,20 +82,23 @@ impl<'a, P: Pattern<'a>> SplitInternal<'a, P> {
             return None;
         }
 
-        let haystack = self.matcher.haystack();
-        match self.matcher.next_match() {
-            // SAFETY: `Searcher` guarantees that `a` and `b` lie on unicode boundaries.
-            Some((a, b)) => unsafe {
-                let result = (haystack.get_unchecked(self.start..a), self.start..).into();
-                self.start = b;
-                Some(result)
-            },
-            None => self.get_end(),
+        if let Some((a, b)) = self.matcher.find_from(self.haystack, self.position) {
+            let result = (&self.haystack[self.start..a], self.start..).into();
+            self.start = b;
+            self.position = b;
+            Some(result)
+        } else {
+            self.position = self.haystack.len();
+            self.get_end()
         }
     }
 
     #[must_use]
-    pub fn haystack(&self) -> &'a str { self.matcher.haystack() }
+    pub const fn haystack(&self) -> &'a str { self.haystack }
+
+    /// Returns the index after the last match.
+    #[must_use]
+    pub const fn index(&self) -> usize { self.position }
 
     #[inline]
     pub fn as_str(&self) -> &'a str {
@@ -126,7 +107,6 @@ impl<'a, P: Pattern<'a>> SplitInternal<'a, P> {
             return "";
         }
 
-        // SAFETY: `self.start` and `self.end` always lie on unicode boundaries.
-        unsafe { self.matcher.haystack().get_unchecked(self.start..self.end) }
+        &self.haystack[self.start..self.end]
     }
 }
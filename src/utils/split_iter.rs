@@ -1,36 +1,26 @@
 use core::fmt;
-use core::str::pattern::{Pattern, Searcher};
 
 use crate::utils::Spanned;
 
-use super::pattern::{IgnoringPattern, PatternExt};
+use super::pattern::{IgnoringPattern, Pattern, PatternExt};
 
 #[must_use]
-pub struct SplitIter<'a, P: Pattern<'a>> {
+pub struct SplitIter<'a, P: Pattern> {
     pub(super) iterator: super::split::SplitInternal<'a, IgnoringPattern<P>>,
     // where the string is located in the original string (this is the start of a span)
     pub(super) start: usize,
 }
 
-impl<'a, P: Pattern<'a>> SplitIter<'a, P> {
-    #[must_use]
+impl<'a, P: Pattern> SplitIter<'a, P> {
     fn new_(string: &'a str, pattern: P, start: usize) -> Self {
         Self {
-            iterator: super::split::SplitInternal {
-                start: 0,
-                end: string.len(),
-                matcher: pattern.ignoring().into_searcher(string),
-                allow_trailing_empty: true,
-                finished: false,
-            },
+            iterator: super::split::SplitInternal::new(string, pattern.ignoring(), true),
             start,
         }
     }
 
-    #[must_use]
     pub fn new(string: &'a str, pattern: P) -> Self { Self::new_(string, pattern, 0) }
 
-    #[must_use]
     pub fn new_spanned(string: Spanned<&'a str>, pattern: P) -> Self {
         let (string, span) = string.into_parts();
 
@@ -38,17 +28,17 @@ impl<'a, P: Pattern<'a>> SplitIter<'a, P> {
     }
 }
 
-impl<'a, P: Pattern<'a>> SplitIter<'a, P> {
+impl<'a, P: Pattern> SplitIter<'a, P> {
     #[must_use]
-    pub fn split_remaining(&mut self) -> Option<Spanned<&'a str>> {
-        Some((self.iterator.as_str(), self.start + self.iterator.start..).into())
+    pub fn split_remaining(&self) -> Spanned<&'a str> {
+        (self.iterator.as_str(), self.start + self.iterator.start..).into()
     }
 
     // TODO: as spanned str?
-    pub fn as_str(&self) -> &'a str { self.iterator.matcher.haystack() }
+    pub const fn as_str(&self) -> &'a str { self.iterator.haystack() }
 }
 
-impl<'a, P: Pattern<'a>> Iterator for SplitIter<'a, P> {
+impl<'a, P: Pattern> Iterator for SplitIter<'a, P> {
     type Item = Spanned<&'a str>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -59,7 +49,7 @@ impl<'a, P: Pattern<'a>> Iterator for SplitIter<'a, P> {
     }
 }
 
-impl<'a, P: Pattern<'a>> fmt::Debug for SplitIter<'a, P> {
+impl<P: Pattern> fmt::Debug for SplitIter<'_, P> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SplitIter")
             .field("start", &self.start)
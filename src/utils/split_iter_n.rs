@@ -1,26 +1,22 @@
-use core::str::pattern::Pattern;
-
-use super::pattern::{IgnoringPattern, PatternExt};
+use super::pattern::{IgnoringPattern, Pattern, PatternExt};
 use super::split::SplitNInternal;
 use crate::utils::{Span, Spanned};
 
 #[must_use]
-pub struct SplitIterN<'a, P: Pattern<'a>> {
+pub struct SplitIterN<'a, P: Pattern> {
     iterator: SplitNInternal<'a, IgnoringPattern<P>>,
     span: Option<Span>,
 }
 
-impl<'a, P: Pattern<'a>> SplitIterN<'a, P> {
+impl<'a, P: Pattern> SplitIterN<'a, P> {
     pub fn new_spanned(n: usize, string: Spanned<&'a str>, pattern: P) -> Self {
         Self {
             iterator: SplitNInternal {
-                iter: super::split::SplitInternal {
-                    start: 0,
-                    end: string.len(),
-                    matcher: pattern.ignoring().into_searcher(&string),
-                    allow_trailing_empty: true,
-                    finished: false,
-                },
+                iter: super::split::SplitInternal::new(
+                    string.into_inner(),
+                    pattern.ignoring(),
+                    true,
+                ),
                 count: n,
             },
             span: string.span(),
@@ -28,7 +24,7 @@ impl<'a, P: Pattern<'a>> SplitIterN<'a, P> {
     }
 }
 
-impl<'a, P: Pattern<'a>> Iterator for SplitIterN<'a, P> {
+impl<'a, P: Pattern> Iterator for SplitIterN<'a, P> {
     type Item = Spanned<&'a str>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -0,0 +1,111 @@
+//! Checks, that parsing with the [`SubtitleIterator`] and formatting its errors
+//! does not allocate, so it works without the `alloc` feature.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use libsrt::text::TextEvent;
+use libsrt::{ArrayBuffer, Buffer, Serialize, SubtitleError, SubtitleIterator};
+use pretty_assertions::assert_eq;
+
+/// An allocator, that counts the allocations of the current thread, because
+/// the test harness allocates in other threads.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the result of `f` and the number of allocations it made.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+const INPUT: &str = concat!(
+    "1\n",
+    "00:00:01,000 --> 00:00:02,500\n",
+    "<i>Hello</i> <font color=\"#ff0000\">World</font>\n",
+    "\n",
+    "2\n",
+    "00:00:03,000 --> 00:00:04,000\n",
+    "<b>Bye</b>\n",
+    "\n",
+);
+
+#[test]
+fn test_parse_without_allocations() {
+    let ((subtitles, events), allocations) = count_allocations(|| {
+        let mut subtitles = 0;
+        let mut events = 0;
+        let mut buffer = ArrayBuffer::<256>::new();
+
+        for subtitle in SubtitleIterator::from(INPUT) {
+            let subtitle = subtitle.unwrap();
+
+            events += subtitle
+                .text()
+                .iter()
+                .filter(|event| matches!(event, Ok(TextEvent::Tag(_))))
+                .count();
+
+            subtitle.serialize(&mut buffer).unwrap();
+            buffer.clear();
+            subtitles += 1;
+        }
+
+        (subtitles, events)
+    });
+
+    assert_eq!((subtitles, events), (2, 6));
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_display_errors_without_allocations() {
+    let inputs = [
+        "1\n00:00:01,000 --> 00:00:0x,000\nHello\n\n",
+        "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\nHello\n\n",
+        "1\n00:00:02,000 --> 00:00:02,000\nHello\n\n",
+    ];
+
+    let (messages, allocations) = count_allocations(|| {
+        let mut buffer = ArrayBuffer::<128>::new();
+
+        inputs.map(|input| {
+            let error: SubtitleError = SubtitleIterator::from(input).find_map(Result::err).unwrap();
+
+            buffer.clear();
+            write!(buffer, "{}", error).unwrap();
+            buffer
+        })
+    });
+
+    assert_eq!(allocations, 0);
+    assert_eq!(
+        messages
+            .iter()
+            .map(|buffer| buffer.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "invalid digit found in string",
+            "invalid duration",
+            "duration should not be `0s`",
+        ]
+    );
+}
@@ -80,6 +80,7 @@ macro_rules! assert_into_span {
 }
 
 #[test]
+#[allow(clippy::approx_constant)] // 3.14 is the duration of a subtitle and not pi
 fn parse_chinese_subtitle() {
     let mut parser = SubtitleIterator::from(CHINESE_SUBTITLE);
 